@@ -102,8 +102,7 @@ pub fn deposit<'a, 'b, 'c, 'info>(
 /// See [raydium_contract_instructions::amm_instruction::WithdrawInstruction].
 ///
 /// * `amount` - Pool token amount to transfer. token_a and token_b amount are set by
-///             the current exchange rate and size of the pool.
-
+///   the current exchange rate and size of the pool.
 pub fn withdraw<'a, 'b, 'c, 'info>(
     ctx: CpiContext<'a, 'b, 'c, 'info, Withdraw<'info>>,
     amount: u64,
@@ -6,9 +6,13 @@
 
 mod accounts;
 mod instructions;
+#[cfg(feature = "validate")]
+mod validation;
 
 pub use accounts::*;
 pub use instructions::*;
+#[cfg(feature = "validate")]
+pub use validation::*;
 
 use anchor_lang::prelude::*;
 /// aaa
@@ -0,0 +1,98 @@
+//! Optional checks of the CPI accounts against the AMM account state.
+
+use crate::*;
+use anchor_lang::{prelude::*, solana_program};
+use raydium_contract_instructions::{amm_stats::AmmInfo, validation};
+
+fn load_amm(program_id: &Pubkey, amm: &AccountInfo) -> Result<AmmInfo> {
+    if amm.owner != program_id {
+        return Err(ProgramError::IllegalOwner.into());
+    }
+    Ok(AmmInfo::unpack(&amm.try_borrow_data()?)?)
+}
+
+fn check(report: validation::AccountsReport) -> Result<()> {
+    if !report.is_valid() {
+        solana_program::msg!("amm accounts mismatch: {}", report);
+    }
+    Ok(report.into_result()?)
+}
+
+/// Checks the accounts of a [deposit] CPI match the AMM account, to call
+/// before it.
+pub fn validate_deposit_accounts<'a, 'b, 'c, 'info>(
+    ctx: &CpiContext<'a, 'b, 'c, 'info, Deposit<'info>>,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let amm = load_amm(ctx.program.key, &accounts.amm)?;
+    check(validation::validate_deposit_accounts(
+        ctx.program.key,
+        &amm,
+        accounts.amm_authority.key,
+        accounts.amm_open_orders.key,
+        accounts.amm_target_orders.key,
+        accounts.amm_lp_mint.key,
+        accounts.amm_coin_vault.key,
+        accounts.amm_pc_vault.key,
+        accounts.market.key,
+    ))
+}
+
+/// Checks the accounts of a [withdraw] CPI match the AMM account, to call
+/// before it.
+pub fn validate_withdraw_accounts<'a, 'b, 'c, 'info>(
+    ctx: &CpiContext<'a, 'b, 'c, 'info, Withdraw<'info>>,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let amm = load_amm(ctx.program.key, &accounts.amm)?;
+    check(validation::validate_withdraw_accounts(
+        ctx.program.key,
+        &amm,
+        accounts.amm_authority.key,
+        accounts.amm_open_orders.key,
+        accounts.amm_target_orders.key,
+        accounts.amm_lp_mint.key,
+        accounts.amm_coin_vault.key,
+        accounts.amm_pc_vault.key,
+        accounts.market_program.key,
+        accounts.market.key,
+    ))
+}
+
+/// Checks the accounts of a [swap_base_in] CPI match the AMM account, to call
+/// before it.
+pub fn validate_swap_base_in_accounts<'a, 'b, 'c, 'info>(
+    ctx: &CpiContext<'a, 'b, 'c, 'info, SwapBaseIn<'info>>,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let amm = load_amm(ctx.program.key, &accounts.amm)?;
+    check(validation::validate_swap_accounts(
+        ctx.program.key,
+        &amm,
+        accounts.amm_authority.key,
+        accounts.amm_open_orders.key,
+        accounts.amm_coin_vault.key,
+        accounts.amm_pc_vault.key,
+        accounts.market_program.key,
+        accounts.market.key,
+    ))
+}
+
+/// Checks the accounts of a [swap_base_out] CPI match the AMM account, to
+/// call before it.
+pub fn validate_swap_base_out_accounts<'a, 'b, 'c, 'info>(
+    ctx: &CpiContext<'a, 'b, 'c, 'info, SwapBaseOut<'info>>,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let amm = load_amm(ctx.program.key, &accounts.amm)?;
+    check(validation::validate_swap_accounts(
+        ctx.program.key,
+        &amm,
+        accounts.amm_authority.key,
+        accounts.amm_open_orders.key,
+        accounts.amm_coin_vault.key,
+        accounts.amm_pc_vault.key,
+        accounts.market_program.key,
+        accounts.market.key,
+    ))
+}
@@ -0,0 +1,250 @@
+#![cfg(feature = "validate")]
+
+//! The AMM account data is synthetic, with only the fields the validation
+//! reads written at their offsets.
+
+use amm_anchor::*;
+use anchor_lang::{prelude::*, solana_program::program_error::ProgramError};
+use anchor_spl::token::Token;
+use raydium_contract_instructions::{amm_instruction::AUTHORITY_AMM, amm_stats::AmmInfo};
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+/// Account info living for the test, owned by `owner`.
+fn account_info(
+    key: Pubkey,
+    owner: Pubkey,
+    data: Vec<u8>,
+    is_signer: bool,
+    executable: bool,
+) -> &'static AccountInfo<'static> {
+    Box::leak(Box::new(AccountInfo::new(
+        Box::leak(Box::new(key)),
+        is_signer,
+        true,
+        Box::leak(Box::new(0)),
+        data.leak(),
+        Box::leak(Box::new(owner)),
+        executable,
+        0,
+    )))
+}
+
+fn unchecked(n: u8) -> UncheckedAccount<'static> {
+    UncheckedAccount::try_from(account_info(
+        key(n),
+        Pubkey::default(),
+        vec![],
+        false,
+        false,
+    ))
+}
+
+fn signer() -> Signer<'static> {
+    Signer::try_from(account_info(
+        key(100),
+        Pubkey::default(),
+        vec![],
+        true,
+        false,
+    ))
+    .unwrap()
+}
+
+fn token_program() -> Program<'static, Token> {
+    Program::try_from(account_info(
+        Token::id(),
+        Pubkey::default(),
+        vec![],
+        false,
+        true,
+    ))
+    .unwrap()
+}
+
+fn program() -> AccountInfo<'static> {
+    account_info(amm_anchor::ID, Pubkey::default(), vec![], false, true).clone()
+}
+
+/// AMM account of the pool with the vaults 1 and 2, lp mint 3, open orders
+/// 4, market 5 on the market program 6 and target orders 7.
+fn amm(owner: Pubkey) -> UncheckedAccount<'static> {
+    let (_, nonce) = Pubkey::find_program_address(&[AUTHORITY_AMM], &amm_anchor::ID);
+    let mut data = vec![0; AmmInfo::LEN];
+    data[8..16].copy_from_slice(&(nonce as u64).to_le_bytes());
+    for (n, offset) in [
+        (1, 336),
+        (2, 368),
+        (3, 464),
+        (4, 496),
+        (5, 528),
+        (6, 560),
+        (7, 592),
+    ] {
+        data[offset..offset + 32].copy_from_slice(key(n).as_ref());
+    }
+    UncheckedAccount::try_from(account_info(key(99), owner, data, false, false))
+}
+
+fn amm_authority() -> UncheckedAccount<'static> {
+    let (authority, _) = Pubkey::find_program_address(&[AUTHORITY_AMM], &amm_anchor::ID);
+    UncheckedAccount::try_from(account_info(
+        authority,
+        Pubkey::default(),
+        vec![],
+        false,
+        false,
+    ))
+}
+
+fn deposit(amm: UncheckedAccount<'static>, amm_coin_vault: u8) -> Deposit<'static> {
+    Deposit {
+        amm,
+        amm_authority: amm_authority(),
+        amm_open_orders: unchecked(4),
+        amm_target_orders: unchecked(7),
+        amm_lp_mint: unchecked(3),
+        amm_coin_vault: unchecked(amm_coin_vault),
+        amm_pc_vault: unchecked(2),
+        market: unchecked(5),
+        market_event_queue: unchecked(20),
+        user_token_coin: unchecked(21),
+        user_token_pc: unchecked(22),
+        user_token_lp: unchecked(23),
+        user_owner: signer(),
+        token_program: token_program(),
+    }
+}
+
+fn withdraw(market_program: u8) -> Withdraw<'static> {
+    Withdraw {
+        amm: amm(amm_anchor::ID),
+        amm_authority: amm_authority(),
+        amm_open_orders: unchecked(4),
+        amm_target_orders: unchecked(7),
+        amm_lp_mint: unchecked(3),
+        amm_coin_vault: unchecked(1),
+        amm_pc_vault: unchecked(2),
+        market_program: unchecked(market_program),
+        market: unchecked(5),
+        market_coin_vault: unchecked(20),
+        market_pc_vault: unchecked(21),
+        market_vault_signer: unchecked(22),
+        user_token_lp: unchecked(23),
+        user_token_coin: unchecked(24),
+        user_token_pc: unchecked(25),
+        user_owner: signer(),
+        market_event_q: unchecked(26),
+        market_bids: unchecked(27),
+        market_asks: unchecked(28),
+        token_program: token_program(),
+    }
+}
+
+fn validate<T>(result: Result<T>) -> std::result::Result<T, ProgramError> {
+    result.map_err(ProgramError::from)
+}
+
+#[test]
+fn deposit_accounts() {
+    let ctx = CpiContext::new(program(), deposit(amm(amm_anchor::ID), 1));
+    assert_eq!(validate(validate_deposit_accounts(&ctx)), Ok(()));
+
+    let ctx = CpiContext::new(program(), deposit(amm(amm_anchor::ID), 2));
+    assert_eq!(
+        validate(validate_deposit_accounts(&ctx)),
+        Err(ProgramError::InvalidArgument)
+    );
+
+    // an AMM account of another program
+    let ctx = CpiContext::new(program(), deposit(amm(key(98)), 1));
+    assert_eq!(
+        validate(validate_deposit_accounts(&ctx)),
+        Err(ProgramError::IllegalOwner)
+    );
+
+    // not an AMM account
+    let short = UncheckedAccount::try_from(account_info(
+        key(99),
+        amm_anchor::ID,
+        vec![0; AmmInfo::LEN - 1],
+        false,
+        false,
+    ));
+    let ctx = CpiContext::new(program(), deposit(short, 1));
+    assert_eq!(
+        validate(validate_deposit_accounts(&ctx)),
+        Err(ProgramError::InvalidAccountData)
+    );
+}
+
+#[test]
+fn withdraw_accounts() {
+    let ctx = CpiContext::new(program(), withdraw(6));
+    assert_eq!(validate(validate_withdraw_accounts(&ctx)), Ok(()));
+
+    let ctx = CpiContext::new(program(), withdraw(5));
+    assert_eq!(
+        validate(validate_withdraw_accounts(&ctx)),
+        Err(ProgramError::InvalidArgument)
+    );
+}
+
+#[test]
+fn swap_accounts() {
+    let swap_base_in = |amm_open_orders| SwapBaseIn {
+        amm: amm(amm_anchor::ID),
+        amm_authority: amm_authority(),
+        amm_open_orders: unchecked(amm_open_orders),
+        amm_coin_vault: unchecked(1),
+        amm_pc_vault: unchecked(2),
+        market_program: unchecked(6),
+        market: unchecked(5),
+        market_bids: unchecked(20),
+        market_asks: unchecked(21),
+        market_event_queue: unchecked(22),
+        market_coin_vault: unchecked(23),
+        market_pc_vault: unchecked(24),
+        market_vault_signer: unchecked(25),
+        user_token_source: unchecked(26),
+        user_token_destination: unchecked(27),
+        user_source_owner: signer(),
+        token_program: token_program(),
+    };
+    let ctx = CpiContext::new(program(), swap_base_in(4));
+    assert_eq!(validate(validate_swap_base_in_accounts(&ctx)), Ok(()));
+    let ctx = CpiContext::new(program(), swap_base_in(7));
+    assert_eq!(
+        validate(validate_swap_base_in_accounts(&ctx)),
+        Err(ProgramError::InvalidArgument)
+    );
+
+    let swap_base_out = |amm_authority| SwapBaseOut {
+        amm: amm(amm_anchor::ID),
+        amm_authority,
+        amm_open_orders: unchecked(4),
+        amm_coin_vault: unchecked(1),
+        amm_pc_vault: unchecked(2),
+        market_program: unchecked(6),
+        market: unchecked(5),
+        market_bids: unchecked(20),
+        market_asks: unchecked(21),
+        market_event_queue: unchecked(22),
+        market_coin_vault: unchecked(23),
+        market_pc_vault: unchecked(24),
+        market_vault_signer: unchecked(25),
+        user_token_source: unchecked(26),
+        user_token_destination: unchecked(27),
+        user_source_owner: signer(),
+        token_program: token_program(),
+    };
+    let ctx = CpiContext::new(program(), swap_base_out(amm_authority()));
+    assert_eq!(validate(validate_swap_base_out_accounts(&ctx)), Ok(()));
+    let ctx = CpiContext::new(program(), swap_base_out(unchecked(8)));
+    assert_eq!(
+        validate(validate_swap_base_out_accounts(&ctx)),
+        Err(ProgramError::InvalidArgument)
+    );
+}
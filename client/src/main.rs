@@ -14,7 +14,13 @@ use std::str::FromStr;
 
 use raydium_contract_instructions::{
     amm_instruction::{swap_base_in as amm_swap, ID as ammProgramID},
+    amm_stats::AmmInfo,
+    market_stats::{MarketState, OpenOrders},
+    pool::{AmmPool, RaydiumPool, StablePool},
+    slippage::minimum_amount_out,
     stable_instruction::{swap_base_in as stable_swap, ID as stableProgramID},
+    stable_math::ModelData,
+    stable_stats::AmmInfo as StableAmmInfo,
 };
 
 fn read_keypair_file(s: &str) -> Result<Keypair> {
@@ -71,6 +77,30 @@ fn main() -> Result<()> {
     //     "marketEventQueue": "EkKZwBeKWPvhraYERfUNr2fdh1eazrbTrQXYkRZs24XB",
     // },
 
+    // quote 1 RAY on the pool, accept 3% of slippage below the quote
+    let amm_pool = Pubkey::from_str("6UmmUiYoBjSrhakAobJw8BvkmJtDVxaeBtbt7rxWo1mg")?;
+    let amm = AmmInfo::unpack(&rpc.get_account_data(&amm_pool)?)?;
+    let market = MarketState::unpack(&rpc.get_account_data(&amm.market)?)?;
+    let open_orders = OpenOrders::unpack(&rpc.get_account_data(&amm.open_orders)?)?;
+    let coin_vault_amount = rpc
+        .get_token_account_balance(&amm.coin_vault)?
+        .amount
+        .parse()?;
+    let pc_vault_amount = rpc
+        .get_token_account_balance(&amm.pc_vault)?
+        .amount
+        .parse()?;
+    let pool = AmmPool::new(
+        &amm_pool,
+        &amm,
+        &market,
+        &open_orders,
+        coin_vault_amount,
+        pc_vault_amount,
+    )?;
+    let amount_in = 1000000;
+    let amount_out = pool.quote_in(&amm.coin_vault_mint, amount_in)?;
+    let minimum_amount_out = minimum_amount_out(amount_out, 300)?;
     let instr = amm_swap(
         &ammProgramID,
         &Pubkey::from_str("6UmmUiYoBjSrhakAobJw8BvkmJtDVxaeBtbt7rxWo1mg")?,
@@ -89,8 +119,8 @@ fn main() -> Result<()> {
         &user_ray_account,
         &user_usdc_account,
         &payer.pubkey(),
-        1000000,
-        2000000,
+        amount_in,
+        minimum_amount_out,
     )?;
 
     let instructions = vec![instr];
@@ -145,6 +175,32 @@ fn main() -> Result<()> {
     //     "modelDataAccount":"CDSr3ssLcRB6XYPJwAfFt18MZvEZp4LjHcvzBVZ45duo"
     //     }
 
+    // quote 1 USDT on the pool, accept 3% of slippage below the quote
+    let stable_amm = Pubkey::from_str("2EXiumdi14E9b8Fy62QcA5Uh6WdHS2b38wtSxp72Mibj")?;
+    let amm = StableAmmInfo::unpack(&rpc.get_account_data(&stable_amm)?)?;
+    let model_data = ModelData::unpack(&rpc.get_account_data(&amm.model_data_key)?)?;
+    let market = MarketState::unpack(&rpc.get_account_data(&amm.serum_market)?)?;
+    let open_orders = OpenOrders::unpack(&rpc.get_account_data(&amm.open_orders)?)?;
+    let coin_vault_amount = rpc
+        .get_token_account_balance(&amm.coin_vault)?
+        .amount
+        .parse()?;
+    let pc_vault_amount = rpc
+        .get_token_account_balance(&amm.pc_vault)?
+        .amount
+        .parse()?;
+    let pool = StablePool::new(
+        &stable_amm,
+        &amm,
+        &model_data,
+        &market,
+        &open_orders,
+        coin_vault_amount,
+        pc_vault_amount,
+    )?;
+    let amount_in = 1000000;
+    let amount_out = pool.quote_in(&amm.coin_mint, amount_in)?;
+    let minimum_amount_out = minimum_amount_out(amount_out, 300)?;
     let instr = stable_swap(
         &stableProgramID,
         &Pubkey::from_str("2EXiumdi14E9b8Fy62QcA5Uh6WdHS2b38wtSxp72Mibj")?,
@@ -164,8 +220,8 @@ fn main() -> Result<()> {
         &user_usdt_account,
         &user_usdc_account,
         &payer.pubkey(),
-        1000000,
-        970000,
+        amount_in,
+        minimum_amount_out,
     )?;
 
     let instructions = vec![instr];
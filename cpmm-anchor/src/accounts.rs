@@ -0,0 +1,235 @@
+//! Accounts structs for Raydium CPMM.
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::Token2022;
+use raydium_contract_instructions::cpmm_instruction::{
+    AUTH_SEED, MEMO_PROGRAM_ID, OBSERVATION_SEED, POOL_LP_MINT_SEED, POOL_SEED, POOL_VAULT_SEED,
+};
+
+/// Accounts for an `initialize` instruction.
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    /// CHECK: Safe. Pool creator, pays the pool creation
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    /// CHECK: Safe. Amm config Account, the fee tier of the pool
+    pub amm_config: UncheckedAccount<'info>,
+    /// CHECK: Safe. Vault and lp mint authority, a PDA create with seed = [AUTH_SEED]
+    #[account(
+        seeds = [AUTH_SEED],
+        bump,
+        seeds::program = crate::id(),
+    )]
+    pub authority: UncheckedAccount<'info>,
+    /// CHECK: Safe. The new pool state Account, a PDA create with seed = [POOL_SEED, amm_config, token_0_mint, token_1_mint]
+    #[account(
+        mut,
+        seeds = [
+            POOL_SEED,
+            amm_config.key().as_ref(),
+            token_0_mint.key().as_ref(),
+            token_1_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = crate::id(),
+    )]
+    pub pool_state: UncheckedAccount<'info>,
+    /// CHECK: Safe. Token 0 mint, the key must be smaller than token 1 mint
+    pub token_0_mint: UncheckedAccount<'info>,
+    /// CHECK: Safe. Token 1 mint
+    pub token_1_mint: UncheckedAccount<'info>,
+    /// CHECK: Safe. Pool lp mint, a PDA create with seed = [POOL_LP_MINT_SEED, pool_state]
+    #[account(
+        mut,
+        seeds = [POOL_LP_MINT_SEED, pool_state.key().as_ref()],
+        bump,
+        seeds::program = crate::id(),
+    )]
+    pub lp_mint: UncheckedAccount<'info>,
+    /// CHECK: Safe. Creator token 0 Account
+    #[account(mut)]
+    pub creator_token_0: UncheckedAccount<'info>,
+    /// CHECK: Safe. Creator token 1 Account
+    #[account(mut)]
+    pub creator_token_1: UncheckedAccount<'info>,
+    /// CHECK: Safe. Creator lp token Account, created by the program
+    #[account(mut)]
+    pub creator_lp_token: UncheckedAccount<'info>,
+    /// CHECK: Safe. Token 0 vault, a PDA create with seed = [POOL_VAULT_SEED, pool_state, token_0_mint]
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED, pool_state.key().as_ref(), token_0_mint.key().as_ref()],
+        bump,
+        seeds::program = crate::id(),
+    )]
+    pub token_0_vault: UncheckedAccount<'info>,
+    /// CHECK: Safe. Token 1 vault, a PDA create with seed = [POOL_VAULT_SEED, pool_state, token_1_mint]
+    #[account(
+        mut,
+        seeds = [POOL_VAULT_SEED, pool_state.key().as_ref(), token_1_mint.key().as_ref()],
+        bump,
+        seeds::program = crate::id(),
+    )]
+    pub token_1_vault: UncheckedAccount<'info>,
+    /// CHECK: Safe. Pool creation fee receiver
+    #[account(
+        mut,
+        address = crate::create_pool_fee_address::id(),
+    )]
+    pub create_pool_fee: UncheckedAccount<'info>,
+    /// CHECK: Safe. Observation state Account, a PDA create with seed = [OBSERVATION_SEED, pool_state]
+    #[account(
+        mut,
+        seeds = [OBSERVATION_SEED, pool_state.key().as_ref()],
+        bump,
+        seeds::program = crate::id(),
+    )]
+    pub observation_state: UncheckedAccount<'info>,
+    /// CHECK: Safe. The spl token program, lp mint program
+    pub token_program: Program<'info, Token>,
+    /// CHECK: Safe. Token program of token 0
+    pub token_0_program: UncheckedAccount<'info>,
+    /// CHECK: Safe. Token program of token 1
+    pub token_1_program: UncheckedAccount<'info>,
+    /// CHECK: Safe. The associated token program
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// CHECK: Safe. System program
+    pub system_program: Program<'info, System>,
+    /// CHECK: Safe. Rent sysvar
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Accounts for a `deposit` instruction.
+#[derive(Accounts, Clone)]
+pub struct Deposit<'info> {
+    /// CHECK: Safe. User wallet Account
+    pub owner: Signer<'info>,
+    /// CHECK: Safe. Vault and lp mint authority
+    #[account(
+        seeds = [AUTH_SEED],
+        bump,
+        seeds::program = crate::id(),
+    )]
+    pub authority: UncheckedAccount<'info>,
+    /// CHECK: Safe. Pool state Account
+    #[account(mut)]
+    pub pool_state: UncheckedAccount<'info>,
+    /// CHECK: Safe. User lp token Account, lp tokens are minted to
+    #[account(mut)]
+    pub owner_lp_token: UncheckedAccount<'info>,
+    /// CHECK: Safe. User token 0 Account, deposits from
+    #[account(mut)]
+    pub token_0_account: UncheckedAccount<'info>,
+    /// CHECK: Safe. User token 1 Account, deposits from
+    #[account(mut)]
+    pub token_1_account: UncheckedAccount<'info>,
+    /// CHECK: Safe. Pool token 0 vault
+    #[account(mut)]
+    pub token_0_vault: UncheckedAccount<'info>,
+    /// CHECK: Safe. Pool token 1 vault
+    #[account(mut)]
+    pub token_1_vault: UncheckedAccount<'info>,
+    /// CHECK: Safe. The spl token program
+    pub token_program: Program<'info, Token>,
+    /// CHECK: Safe. The token 2022 program
+    pub token_program_2022: Program<'info, Token2022>,
+    /// CHECK: Safe. Token 0 mint
+    pub vault_0_mint: UncheckedAccount<'info>,
+    /// CHECK: Safe. Token 1 mint
+    pub vault_1_mint: UncheckedAccount<'info>,
+    /// CHECK: Safe. Pool lp mint
+    #[account(mut)]
+    pub lp_mint: UncheckedAccount<'info>,
+}
+
+/// Accounts for a `withdraw` instruction.
+#[derive(Accounts, Clone)]
+pub struct Withdraw<'info> {
+    /// CHECK: Safe. User wallet Account
+    pub owner: Signer<'info>,
+    /// CHECK: Safe. Vault and lp mint authority
+    #[account(
+        seeds = [AUTH_SEED],
+        bump,
+        seeds::program = crate::id(),
+    )]
+    pub authority: UncheckedAccount<'info>,
+    /// CHECK: Safe. Pool state Account
+    #[account(mut)]
+    pub pool_state: UncheckedAccount<'info>,
+    /// CHECK: Safe. User lp token Account, lp tokens are burnt from
+    #[account(mut)]
+    pub owner_lp_token: UncheckedAccount<'info>,
+    /// CHECK: Safe. User token 0 Account, withdraws to
+    #[account(mut)]
+    pub token_0_account: UncheckedAccount<'info>,
+    /// CHECK: Safe. User token 1 Account, withdraws to
+    #[account(mut)]
+    pub token_1_account: UncheckedAccount<'info>,
+    /// CHECK: Safe. Pool token 0 vault
+    #[account(mut)]
+    pub token_0_vault: UncheckedAccount<'info>,
+    /// CHECK: Safe. Pool token 1 vault
+    #[account(mut)]
+    pub token_1_vault: UncheckedAccount<'info>,
+    /// CHECK: Safe. The spl token program
+    pub token_program: Program<'info, Token>,
+    /// CHECK: Safe. The token 2022 program
+    pub token_program_2022: Program<'info, Token2022>,
+    /// CHECK: Safe. Token 0 mint
+    pub vault_0_mint: UncheckedAccount<'info>,
+    /// CHECK: Safe. Token 1 mint
+    pub vault_1_mint: UncheckedAccount<'info>,
+    /// CHECK: Safe. Pool lp mint
+    #[account(mut)]
+    pub lp_mint: UncheckedAccount<'info>,
+    /// CHECK: Safe. The memo program
+    #[account(address = MEMO_PROGRAM_ID)]
+    pub memo_program: UncheckedAccount<'info>,
+}
+
+/// Accounts for a `swap_base_input` instruction.
+#[derive(Accounts, Clone)]
+pub struct SwapBaseInput<'info> {
+    /// CHECK: Safe. User wallet Account
+    pub payer: Signer<'info>,
+    /// CHECK: Safe. Vault and lp mint authority
+    #[account(
+        seeds = [AUTH_SEED],
+        bump,
+        seeds::program = crate::id(),
+    )]
+    pub authority: UncheckedAccount<'info>,
+    /// CHECK: Safe. Amm config Account of the pool
+    pub amm_config: UncheckedAccount<'info>,
+    /// CHECK: Safe. Pool state Account
+    #[account(mut)]
+    pub pool_state: UncheckedAccount<'info>,
+    /// CHECK: Safe. User input token Account, swaps from
+    #[account(mut)]
+    pub input_token_account: UncheckedAccount<'info>,
+    /// CHECK: Safe. User output token Account, swaps to
+    #[account(mut)]
+    pub output_token_account: UncheckedAccount<'info>,
+    /// CHECK: Safe. Pool vault of the input token
+    #[account(mut)]
+    pub input_vault: UncheckedAccount<'info>,
+    /// CHECK: Safe. Pool vault of the output token
+    #[account(mut)]
+    pub output_vault: UncheckedAccount<'info>,
+    /// CHECK: Safe. Token program of the input token
+    pub input_token_program: UncheckedAccount<'info>,
+    /// CHECK: Safe. Token program of the output token
+    pub output_token_program: UncheckedAccount<'info>,
+    /// CHECK: Safe. Input token mint
+    pub input_token_mint: UncheckedAccount<'info>,
+    /// CHECK: Safe. Output token mint
+    pub output_token_mint: UncheckedAccount<'info>,
+    /// CHECK: Safe. Observation state Account of the pool
+    #[account(mut)]
+    pub observation_state: UncheckedAccount<'info>,
+}
+
+/// Accounts for a `swap_base_output` instruction.
+pub type SwapBaseOutput<'info> = SwapBaseInput<'info>;
@@ -0,0 +1,208 @@
+//! Instruction builders and invokers for CPMM instructions.
+
+use crate::*;
+use anchor_lang::{prelude::*, solana_program};
+use raydium_contract_instructions::cpmm_instruction;
+
+/// Creates and invokes a [raydium_contract_instructions::cpmm_instruction::initialize] instruction.
+///
+/// # Arguments
+///
+/// See [raydium_contract_instructions::cpmm_instruction::InitializeInstruction].
+///
+/// * `init_amount_0` - The token 0 amount transfer to pool.
+/// * `init_amount_1` - The token 1 amount transfer to pool.
+/// * `open_time` - The effective time.
+pub fn initialize<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, Initialize<'info>>,
+    init_amount_0: u64,
+    init_amount_1: u64,
+    open_time: u64,
+) -> Result<()> {
+    let ix = cpmm_instruction::initialize(
+        ctx.program.key,
+        ctx.accounts.amm_config.key,
+        ctx.accounts.authority.key,
+        ctx.accounts.pool_state.key,
+        ctx.accounts.token_0_mint.key,
+        ctx.accounts.token_1_mint.key,
+        ctx.accounts.lp_mint.key,
+        ctx.accounts.token_0_vault.key,
+        ctx.accounts.token_1_vault.key,
+        ctx.accounts.create_pool_fee.key,
+        ctx.accounts.observation_state.key,
+        ctx.accounts.token_0_program.key,
+        ctx.accounts.token_1_program.key,
+        ctx.accounts.creator.key,
+        ctx.accounts.creator_token_0.key,
+        ctx.accounts.creator_token_1.key,
+        ctx.accounts.creator_lp_token.key,
+        init_amount_0,
+        init_amount_1,
+        open_time,
+    )?;
+    solana_program::program::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Creates and invokes a [raydium_contract_instructions::cpmm_instruction::deposit] instruction.
+///
+/// # Arguments
+///
+/// See [raydium_contract_instructions::cpmm_instruction::DepositInstruction].
+///
+/// * `lp_token_amount` - The lp amount to mint, token amounts are set by the pool reserves ratio.
+/// * `maximum_token_0_amount` - Maximum token 0 amount to deposit, prevents excessive slippage.
+/// * `maximum_token_1_amount` - Maximum token 1 amount to deposit, prevents excessive slippage.
+pub fn deposit<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, Deposit<'info>>,
+    lp_token_amount: u64,
+    maximum_token_0_amount: u64,
+    maximum_token_1_amount: u64,
+) -> Result<()> {
+    let ix = cpmm_instruction::deposit(
+        ctx.program.key,
+        ctx.accounts.authority.key,
+        ctx.accounts.pool_state.key,
+        ctx.accounts.token_0_vault.key,
+        ctx.accounts.token_1_vault.key,
+        ctx.accounts.vault_0_mint.key,
+        ctx.accounts.vault_1_mint.key,
+        ctx.accounts.lp_mint.key,
+        ctx.accounts.owner.key,
+        ctx.accounts.token_0_account.key,
+        ctx.accounts.token_1_account.key,
+        ctx.accounts.owner_lp_token.key,
+        lp_token_amount,
+        maximum_token_0_amount,
+        maximum_token_1_amount,
+    )?;
+    solana_program::program::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Creates and invokes a [raydium_contract_instructions::cpmm_instruction::withdraw] instruction.
+///
+/// # Arguments
+///
+/// See [raydium_contract_instructions::cpmm_instruction::WithdrawInstruction].
+///
+/// * `lp_token_amount` - The lp amount to burn, token amounts are set by the pool reserves ratio.
+/// * `minimum_token_0_amount` - Minimum token 0 amount to receive, prevents excessive slippage.
+/// * `minimum_token_1_amount` - Minimum token 1 amount to receive, prevents excessive slippage.
+pub fn withdraw<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, Withdraw<'info>>,
+    lp_token_amount: u64,
+    minimum_token_0_amount: u64,
+    minimum_token_1_amount: u64,
+) -> Result<()> {
+    let ix = cpmm_instruction::withdraw(
+        ctx.program.key,
+        ctx.accounts.authority.key,
+        ctx.accounts.pool_state.key,
+        ctx.accounts.token_0_vault.key,
+        ctx.accounts.token_1_vault.key,
+        ctx.accounts.vault_0_mint.key,
+        ctx.accounts.vault_1_mint.key,
+        ctx.accounts.lp_mint.key,
+        ctx.accounts.owner.key,
+        ctx.accounts.token_0_account.key,
+        ctx.accounts.token_1_account.key,
+        ctx.accounts.owner_lp_token.key,
+        lp_token_amount,
+        minimum_token_0_amount,
+        minimum_token_1_amount,
+    )?;
+    solana_program::program::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Creates and invokes a [raydium_contract_instructions::cpmm_instruction::swap_base_input] instruction.
+///
+/// # Arguments
+///
+/// See [raydium_contract_instructions::cpmm_instruction::SwapBaseInputInstruction].
+///
+/// * `amount_in` - Input amount to transfer, output is based on the exchange rate.
+/// * `minimum_amount_out` - Minimum amount of output token, prevents excessive slippage.
+pub fn swap_base_input<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, SwapBaseInput<'info>>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<()> {
+    let ix = cpmm_instruction::swap_base_input(
+        ctx.program.key,
+        ctx.accounts.authority.key,
+        ctx.accounts.amm_config.key,
+        ctx.accounts.pool_state.key,
+        ctx.accounts.input_vault.key,
+        ctx.accounts.output_vault.key,
+        ctx.accounts.input_token_program.key,
+        ctx.accounts.output_token_program.key,
+        ctx.accounts.input_token_mint.key,
+        ctx.accounts.output_token_mint.key,
+        ctx.accounts.observation_state.key,
+        ctx.accounts.input_token_account.key,
+        ctx.accounts.output_token_account.key,
+        ctx.accounts.payer.key,
+        amount_in,
+        minimum_amount_out,
+    )?;
+    solana_program::program::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Creates and invokes a [raydium_contract_instructions::cpmm_instruction::swap_base_output] instruction.
+///
+/// # Arguments
+///
+/// See [raydium_contract_instructions::cpmm_instruction::SwapBaseOutputInstruction].
+///
+/// * `max_amount_in` - Maximum input amount to transfer, prevents excessive slippage.
+/// * `amount_out` - Amount of output token to receive.
+pub fn swap_base_output<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, SwapBaseOutput<'info>>,
+    max_amount_in: u64,
+    amount_out: u64,
+) -> Result<()> {
+    let ix = cpmm_instruction::swap_base_output(
+        ctx.program.key,
+        ctx.accounts.authority.key,
+        ctx.accounts.amm_config.key,
+        ctx.accounts.pool_state.key,
+        ctx.accounts.input_vault.key,
+        ctx.accounts.output_vault.key,
+        ctx.accounts.input_token_program.key,
+        ctx.accounts.output_token_program.key,
+        ctx.accounts.input_token_mint.key,
+        ctx.accounts.output_token_mint.key,
+        ctx.accounts.observation_state.key,
+        ctx.accounts.input_token_account.key,
+        ctx.accounts.output_token_account.key,
+        ctx.accounts.payer.key,
+        max_amount_in,
+        amount_out,
+    )?;
+    solana_program::program::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+    )?;
+    Ok(())
+}
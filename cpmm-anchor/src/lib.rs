@@ -0,0 +1,34 @@
+//! Anchor-compatible SDK for the Raydium CPMM program.
+#![deny(rustdoc::all)]
+#![allow(rustdoc::missing_doc_code_examples)]
+#![allow(clippy::nonstandard_macro_braces)]
+
+mod accounts;
+mod instructions;
+
+pub use accounts::*;
+pub use instructions::*;
+
+use anchor_lang::prelude::*;
+/// Account receiving the pool creation fee
+pub mod create_pool_fee_address {
+    #[cfg(not(any(feature = "devnet")))]
+    anchor_lang::declare_id!("DNXgeM9EiiaAbaWvwjHj9fQQLAX5ZsfHyvmYUNRAdNC8");
+    #[cfg(feature = "devnet")]
+    anchor_lang::declare_id!("G11FKBRaAkHAKuLCgLM6K6NUc9rTjPAznRCjZifrTQe2");
+}
+
+#[cfg(not(any(feature = "devnet")))]
+declare_id!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+#[cfg(feature = "devnet")]
+declare_id!("CPMDWBwJDtYax9qW7AyRuVC19Cc4L4Vcy4n2BHAbHkCW");
+
+/// The CPMM program
+#[derive(Clone)]
+pub struct Cpmm;
+
+impl anchor_lang::Id for Cpmm {
+    fn id() -> Pubkey {
+        ID
+    }
+}
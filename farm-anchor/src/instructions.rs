@@ -1,6 +1,6 @@
 use crate::*;
-use anchor_lang::{prelude::*, solana_program};
-use raydium_contract_instructions::farm_instruction;
+use anchor_lang::{error::ErrorCode, prelude::*, solana_program};
+use raydium_contract_instructions::farm_instruction::{self, RewardBAccounts};
 
 /// Creates and invokes a [raydium_contract_instructions::farm_instruction::initialize] instruction.
 ///
@@ -12,7 +12,7 @@ pub fn initialize<'a, 'b, 'c, 'info>(
     ctx: CpiContext<'a, 'b, 'c, 'info, Initialize<'info>>,
     init_args: InitArgs,
 ) -> Result<()> {
-    let reward_vault_b = ctx.remaining_accounts.get(0);
+    let reward_vault_b = ctx.remaining_accounts.first();
     let admin_authority = ctx.remaining_accounts.get(1);
 
     let ix = farm_instruction::initialize(
@@ -48,8 +48,7 @@ pub fn deposit<'a, 'b, 'c, 'info>(
     ctx: CpiContext<'a, 'b, 'c, 'info, Deposit<'info>>,
     amount: u64,
 ) -> Result<()> {
-    let dest_reward_token_b = ctx.remaining_accounts.get(0);
-    let vault_reward_token_b = ctx.remaining_accounts.get(1);
+    let reward_b = reward_b_accounts(&ctx.remaining_accounts)?;
     let ix = farm_instruction::deposit(
         ctx.program.key,
         ctx.accounts.stake_pool.key,
@@ -60,8 +59,7 @@ pub fn deposit<'a, 'b, 'c, 'info>(
         ctx.accounts.vault_lp_token.key,
         ctx.accounts.dest_reward_token_a.key,
         ctx.accounts.vault_reward_token_a.key,
-        dest_reward_token_b.map(|info| info.key),
-        vault_reward_token_b.map(|info| info.key),
+        reward_b,
         ctx.accounts.spl_token_program.key,
         amount,
     )?;
@@ -83,7 +81,7 @@ pub fn deposit_v2<'a, 'b, 'c, 'info>(
     ctx: CpiContext<'a, 'b, 'c, 'info, DepositV2<'info>>,
     amount: u64,
 ) -> Result<()> {
-    let staker_info_v1 = ctx.remaining_accounts.get(0);
+    let staker_info_v1 = ctx.remaining_accounts.first();
     let ix = farm_instruction::deposit_v2(
         ctx.program.key,
         ctx.accounts.stake_pool.key,
@@ -118,8 +116,7 @@ pub fn withdraw<'a, 'b, 'c, 'info>(
     ctx: CpiContext<'a, 'b, 'c, 'info, Withdraw<'info>>,
     amount: u64,
 ) -> Result<()> {
-    let dest_reward_token_b = ctx.remaining_accounts.get(0);
-    let vault_reward_token_b = ctx.remaining_accounts.get(1);
+    let reward_b = reward_b_accounts(&ctx.remaining_accounts)?;
     let ix = farm_instruction::withdraw(
         ctx.program.key,
         ctx.accounts.stake_pool.key,
@@ -130,8 +127,7 @@ pub fn withdraw<'a, 'b, 'c, 'info>(
         ctx.accounts.vault_lp_token.key,
         ctx.accounts.dest_reward_token_a.key,
         ctx.accounts.vault_reward_token_a.key,
-        dest_reward_token_b.map(|info| info.key),
-        vault_reward_token_b.map(|info| info.key),
+        reward_b,
         ctx.accounts.spl_token_program.key,
         amount,
     )?;
@@ -153,8 +149,7 @@ pub fn withdraw_v2<'a, 'b, 'c, 'info>(
     ctx: CpiContext<'a, 'b, 'c, 'info, WithdrawV2<'info>>,
     amount: u64,
 ) -> Result<()> {
-    let dest_reward_token_b = ctx.remaining_accounts.get(0);
-    let vault_reward_token_b = ctx.remaining_accounts.get(1);
+    let reward_b = reward_b_accounts(&ctx.remaining_accounts)?;
     let ix = farm_instruction::withdraw_v2(
         ctx.program.key,
         ctx.accounts.stake_pool.key,
@@ -165,8 +160,7 @@ pub fn withdraw_v2<'a, 'b, 'c, 'info>(
         ctx.accounts.vault_lp_token.key,
         ctx.accounts.dest_reward_token_a.key,
         ctx.accounts.vault_reward_token_a.key,
-        dest_reward_token_b.map(|info| info.key),
-        vault_reward_token_b.map(|info| info.key),
+        reward_b,
         ctx.accounts.spl_token_program.key,
         amount,
     )?;
@@ -247,3 +241,16 @@ pub fn create_associated_account<'a, 'b, 'c, 'info>(
     )?;
     Ok(())
 }
+
+/// Reads the optional reward b accounts from the `remaining_accounts`: the user reward token b
+/// account followed by the pool reward token b vault.
+fn reward_b_accounts(remaining_accounts: &[AccountInfo]) -> Result<Option<RewardBAccounts>> {
+    match remaining_accounts {
+        [] => Ok(None),
+        [dest_reward_token, vault_reward_token, ..] => Ok(Some(RewardBAccounts {
+            dest_reward_token: *dest_reward_token.key,
+            vault_reward_token: *vault_reward_token.key,
+        })),
+        [_] => err!(ErrorCode::AccountNotEnoughKeys),
+    }
+}
@@ -0,0 +1,128 @@
+//! The reward b accounts of a deposit or withdraw are passed as the remaining
+//! accounts. Off-chain the CPI itself is a no-op stub, so only the account
+//! checks before it are exercised.
+
+use anchor_lang::{
+    error::ErrorCode,
+    prelude::*,
+    solana_program::{program_error::ProgramError, sysvar},
+};
+use anchor_spl::token::Token;
+use farm_anchor::*;
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+/// Account info living for the test.
+fn account_info(
+    key: Pubkey,
+    data: Vec<u8>,
+    is_signer: bool,
+    executable: bool,
+) -> &'static AccountInfo<'static> {
+    Box::leak(Box::new(AccountInfo::new(
+        Box::leak(Box::new(key)),
+        is_signer,
+        true,
+        Box::leak(Box::new(0)),
+        data.leak(),
+        Box::leak(Box::new(Pubkey::default())),
+        executable,
+        0,
+    )))
+}
+
+fn unchecked(n: u8) -> UncheckedAccount<'static> {
+    UncheckedAccount::try_from(account_info(key(n), vec![], false, false))
+}
+
+fn signer() -> Signer<'static> {
+    Signer::try_from(account_info(key(100), vec![], true, false)).unwrap()
+}
+
+fn clock() -> Sysvar<'static, Clock> {
+    // slot, epoch start timestamp, epoch, leader schedule epoch, unix timestamp
+    Sysvar::from_account_info(account_info(sysvar::clock::id(), vec![0; 40], false, false)).unwrap()
+}
+
+fn token_program() -> Program<'static, Token> {
+    Program::try_from(account_info(Token::id(), vec![], false, true)).unwrap()
+}
+
+fn program() -> AccountInfo<'static> {
+    account_info(farm_anchor::ID, vec![], false, true).clone()
+}
+
+fn remaining_accounts(count: u8) -> Vec<AccountInfo<'static>> {
+    (0..count)
+        .map(|n| account_info(key(10 + n), vec![], false, false).clone())
+        .collect()
+}
+
+fn deposit_ctx(remaining: u8) -> CpiContext<'static, 'static, 'static, 'static, Deposit<'static>> {
+    CpiContext::new(
+        program(),
+        Deposit {
+            stake_pool: unchecked(1),
+            pool_authority: unchecked(2),
+            staker_info: unchecked(3),
+            staker_owner: signer(),
+            src_lp_token: unchecked(5),
+            vault_lp_token: unchecked(6),
+            dest_reward_token_a: unchecked(7),
+            vault_reward_token_a: unchecked(8),
+            clock: clock(),
+            spl_token_program: token_program(),
+        },
+    )
+    .with_remaining_accounts(remaining_accounts(remaining))
+}
+
+fn withdraw_ctx(
+    remaining: u8,
+) -> CpiContext<'static, 'static, 'static, 'static, Withdraw<'static>> {
+    CpiContext::new(
+        program(),
+        Withdraw {
+            stake_pool: unchecked(1),
+            pool_authority: unchecked(2),
+            staker_info: unchecked(3),
+            staker_owner: signer(),
+            dest_lp_token: unchecked(5),
+            vault_lp_token: unchecked(6),
+            dest_reward_token_a: unchecked(7),
+            vault_reward_token_a: unchecked(8),
+            clock: clock(),
+            spl_token_program: token_program(),
+        },
+    )
+    .with_remaining_accounts(remaining_accounts(remaining))
+}
+
+fn not_enough_keys() -> std::result::Result<(), ProgramError> {
+    Err(Error::from(ErrorCode::AccountNotEnoughKeys).into())
+}
+
+#[test]
+fn deposit_reward_b() {
+    for remaining in [0, 2] {
+        assert_eq!(deposit(deposit_ctx(remaining), 1_000), Ok(()));
+    }
+    // a reward b user account without its vault
+    assert_eq!(
+        deposit(deposit_ctx(1), 1_000).map_err(ProgramError::from),
+        not_enough_keys()
+    );
+}
+
+#[test]
+fn withdraw_reward_b() {
+    for remaining in [0, 2] {
+        assert_eq!(withdraw(withdraw_ctx(remaining), 1_000), Ok(()));
+    }
+    assert_eq!(
+        withdraw(withdraw_ctx(1), 1_000).map_err(ProgramError::from),
+        not_enough_keys()
+    );
+}
@@ -0,0 +1,135 @@
+//! Account data reader of the AMM v4 pool account and the `client` feature
+//! decoders.
+
+#![cfg_attr(not(feature = "client"), allow(dead_code))]
+
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use std::convert::TryInto;
+
+/// Sequential reader over account data, following the on-chain field alignment.
+pub(crate) struct AccountReader<'a> {
+    input: &'a [u8],
+    offset: usize,
+    packed: bool,
+}
+
+impl<'a> AccountReader<'a> {
+    pub(crate) fn new(input: &'a [u8]) -> Self {
+        Self {
+            input,
+            offset: 0,
+            packed: false,
+        }
+    }
+
+    /// Reader over an anchor zero copy account, checking its discriminator.
+    pub(crate) fn new_packed(
+        input: &'a [u8],
+        discriminator: &[u8; 8],
+    ) -> Result<Self, ProgramError> {
+        let mut reader = Self {
+            input,
+            offset: 0,
+            packed: true,
+        };
+        if reader.take(8)? != discriminator {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(reader)
+    }
+
+    pub(crate) fn take(&mut self, len: usize) -> Result<&'a [u8], ProgramError> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let bytes = self
+            .input
+            .get(self.offset..end)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    pub(crate) fn align(&mut self, align: usize) {
+        if !self.packed {
+            self.offset += (align - self.offset % align) % align;
+        }
+    }
+
+    pub(crate) fn bytes<const N: usize>(&mut self) -> Result<[u8; N], ProgramError> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8, ProgramError> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub(crate) fn bool(&mut self) -> Result<bool, ProgramError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    pub(crate) fn u16(&mut self) -> Result<u16, ProgramError> {
+        self.align(2);
+        Ok(u16::from_le_bytes(self.bytes()?))
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32, ProgramError> {
+        self.align(4);
+        Ok(u32::from_le_bytes(self.bytes()?))
+    }
+
+    pub(crate) fn i32(&mut self) -> Result<i32, ProgramError> {
+        self.align(4);
+        Ok(i32::from_le_bytes(self.bytes()?))
+    }
+
+    pub(crate) fn i128(&mut self) -> Result<i128, ProgramError> {
+        self.align(8);
+        Ok(i128::from_le_bytes(self.bytes()?))
+    }
+
+    pub(crate) fn u64(&mut self) -> Result<u64, ProgramError> {
+        self.align(8);
+        let bytes = self.take(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub(crate) fn u128(&mut self) -> Result<u128, ProgramError> {
+        self.align(8);
+        let bytes = self.take(16)?;
+        Ok(u128::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub(crate) fn pubkey(&mut self) -> Result<Pubkey, ProgramError> {
+        let bytes = self.take(32)?;
+        Ok(Pubkey::new_from_array(bytes.try_into().unwrap()))
+    }
+
+    pub(crate) fn option_pubkey(&mut self) -> Result<Option<Pubkey>, ProgramError> {
+        let tag = self.take(1)?[0];
+        let key = self.pubkey()?;
+        match tag {
+            0 => Ok(None),
+            1 => Ok(Some(key)),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    pub(crate) fn array<T, const N: usize>(
+        &mut self,
+        mut read: impl FnMut(&mut Self) -> Result<T, ProgramError>,
+    ) -> Result<[T; N], ProgramError> {
+        let mut values = Vec::with_capacity(N);
+        for _ in 0..N {
+            values.push(read(self)?);
+        }
+        values
+            .try_into()
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+}
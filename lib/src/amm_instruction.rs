@@ -13,6 +13,10 @@ use std::mem::size_of;
 
 solana_program::declare_id!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8");
 
+/// Seed of the amm authority, shared with the stable program.
+pub const AUTHORITY_AMM: &[u8] = b"amm authority";
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct InitializeInstruction {
@@ -21,6 +25,7 @@ pub struct InitializeInstruction {
     pub open_time: u64,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct InitializeInstruction2 {
@@ -34,6 +39,7 @@ pub struct InitializeInstruction2 {
     pub init_coin_amount: u64,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct PreInitializeInstruction {
@@ -41,6 +47,7 @@ pub struct PreInitializeInstruction {
     pub nonce: u8,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct DepositInstruction {
@@ -51,6 +58,7 @@ pub struct DepositInstruction {
     pub base_side: u64,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct WithdrawInstruction {
@@ -59,6 +67,7 @@ pub struct WithdrawInstruction {
     pub amount: u64,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct SwapInstructionBaseIn {
@@ -68,6 +77,7 @@ pub struct SwapInstructionBaseIn {
     pub minimum_amount_out: u64,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct SwapInstructionBaseOut {
@@ -269,12 +279,12 @@ impl AmmInstruction {
                 })
             }
 
-            _ => return Err(ProgramError::InvalidInstructionData.into()),
+            _ => return Err(ProgramError::InvalidInstructionData),
         })
     }
 
     fn unpack_u8(input: &[u8]) -> Result<(u8, &[u8]), ProgramError> {
-        if input.len() >= 1 {
+        if !input.is_empty() {
             let (amount, rest) = input.split_at(1);
             let amount = amount
                 .get(..1)
@@ -283,7 +293,7 @@ impl AmmInstruction {
                 .ok_or(ProgramError::InvalidInstructionData)?;
             Ok((amount, rest))
         } else {
-            Err(ProgramError::InvalidInstructionData.into())
+            Err(ProgramError::InvalidInstructionData)
         }
     }
 
@@ -297,14 +307,14 @@ impl AmmInstruction {
                 .ok_or(ProgramError::InvalidInstructionData)?;
             Ok((amount, rest))
         } else {
-            Err(ProgramError::InvalidInstructionData.into())
+            Err(ProgramError::InvalidInstructionData)
         }
     }
 
     /// Packs a [AmmInstruction](enum.AmmInstruction.html) into a byte buffer.
     pub fn pack(&self) -> Result<Vec<u8>, ProgramError> {
         let mut buf = Vec::with_capacity(size_of::<Self>());
-        match &*self {
+        match self {
             Self::Initialize2(InitializeInstruction2 {
                 nonce,
                 open_time,
@@ -354,6 +364,12 @@ impl AmmInstruction {
     }
 }
 
+/// Creates the amm authority of `program_id` from the amm `nonce`.
+pub fn get_amm_authority(program_id: &Pubkey, nonce: u64) -> Result<Pubkey, ProgramError> {
+    Pubkey::create_program_address(&[AUTHORITY_AMM, &[nonce as u8]], program_id)
+        .map_err(|_| ProgramError::InvalidSeeds)
+}
+
 /// Creates an 'initialize2' instruction.
 pub fn initialize2(
     amm_program: &Pubkey,
@@ -544,7 +560,8 @@ pub fn withdraw(
     })
 }
 
-/// Creates a 'swap base in' instruction.
+/// Creates a 'swap base in' instruction. Fails with `InvalidArgument` on a zero
+/// `minimum_amount_out`, which would accept any swap output.
 pub fn swap_base_in(
     amm_program: &Pubkey,
     amm_pool: &Pubkey,
@@ -567,6 +584,9 @@ pub fn swap_base_in(
     amount_in: u64,
     minimum_amount_out: u64,
 ) -> Result<Instruction, ProgramError> {
+    if minimum_amount_out == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
     let data = AmmInstruction::SwapBaseIn(SwapInstructionBaseIn {
         amount_in,
         minimum_amount_out,
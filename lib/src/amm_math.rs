@@ -0,0 +1,101 @@
+//! AMM v4 swap math.
+//!
+//! The functions round the same way as the AMM program: the swap fee and the
+//! base out input are rounded up with the program `checked_ceil_div`, the
+//! base in output is rounded down.
+
+use solana_program::program_error::ProgramError;
+
+/// Ceil division of the AMM program, a quotient below 1 is rounded to the
+/// nearest integer instead.
+pub fn checked_ceil_div(numerator: u128, denominator: u128) -> Option<u128> {
+    let quotient = numerator.checked_div(denominator)?;
+    if quotient == 0 {
+        return if numerator.checked_mul(2)? >= denominator {
+            Some(1)
+        } else {
+            Some(0)
+        };
+    }
+    if numerator.checked_rem(denominator)? > 0 {
+        quotient.checked_add(1)
+    } else {
+        Some(quotient)
+    }
+}
+
+/// Swap fee charged on `amount_in`.
+pub fn swap_fee(
+    amount_in: u64,
+    swap_fee_numerator: u64,
+    swap_fee_denominator: u64,
+) -> Result<u64, ProgramError> {
+    (amount_in as u128)
+        .checked_mul(swap_fee_numerator as u128)
+        .and_then(|fee| checked_ceil_div(fee, swap_fee_denominator as u128))
+        .and_then(|fee| u64::try_from(fee).ok())
+        .ok_or(ProgramError::InvalidArgument)
+}
+
+/// Output amount of a swap of `amount_in`, the fee included, between the
+/// `reserve_in` and `reserve_out` pool reserves.
+pub fn swap_base_in_amount_out(
+    amount_in: u64,
+    reserve_in: u64,
+    reserve_out: u64,
+    swap_fee_numerator: u64,
+    swap_fee_denominator: u64,
+) -> Result<u64, ProgramError> {
+    let fee = swap_fee(amount_in, swap_fee_numerator, swap_fee_denominator)?;
+    let amount_in = (amount_in - fee.min(amount_in)) as u128;
+    // (reserve_in + amount_in) * (reserve_out - amount_out) = reserve_in * reserve_out
+    let denominator = (reserve_in as u128)
+        .checked_add(amount_in)
+        .filter(|denominator| *denominator > 0)
+        .ok_or(ProgramError::InvalidArgument)?;
+    let amount_out = (reserve_out as u128)
+        .checked_mul(amount_in)
+        .ok_or(ProgramError::InvalidArgument)?
+        / denominator;
+    Ok(amount_out as u64)
+}
+
+/// Input amount, the fee included, of a swap for `amount_out` between the
+/// `reserve_in` and `reserve_out` pool reserves.
+pub fn swap_base_out_amount_in(
+    amount_out: u64,
+    reserve_in: u64,
+    reserve_out: u64,
+    swap_fee_numerator: u64,
+    swap_fee_denominator: u64,
+) -> Result<u64, ProgramError> {
+    if amount_out >= reserve_out {
+        return Err(ProgramError::InsufficientFunds);
+    }
+    let amount_in = (reserve_in as u128)
+        .checked_mul(amount_out as u128)
+        .and_then(|amount| checked_ceil_div(amount, (reserve_out - amount_out) as u128))
+        .ok_or(ProgramError::InvalidArgument)?;
+    let amount_in = u64::try_from(amount_in).map_err(|_| ProgramError::InvalidArgument)?;
+    amount_in_with_fee(amount_in, swap_fee_numerator, swap_fee_denominator)
+}
+
+/// Input amount of a swap, the fee included, for `amount_in` to be left once
+/// the fee is charged.
+pub fn amount_in_with_fee(
+    amount_in: u64,
+    swap_fee_numerator: u64,
+    swap_fee_denominator: u64,
+) -> Result<u64, ProgramError> {
+    // amount_in_with_fee * (1 - fee rate) = amount_in
+    (amount_in as u128)
+        .checked_mul(swap_fee_denominator as u128)
+        .and_then(|amount| {
+            checked_ceil_div(
+                amount,
+                swap_fee_denominator.checked_sub(swap_fee_numerator)? as u128,
+            )
+        })
+        .and_then(|amount| u64::try_from(amount).ok())
+        .ok_or(ProgramError::InvalidArgument)
+}
@@ -1,7 +1,12 @@
 //! State transition types
 
-use solana_program::pubkey::Pubkey;
+use crate::{
+    account_reader::AccountReader,
+    market_stats::{MarketState, OpenOrders},
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
 
+#[cfg_attr(feature = "client", derive(Debug))]
 #[repr(u64)]
 pub enum AmmStatus {
     Uninitialized = 0u64,
@@ -18,6 +23,32 @@ pub enum AmmStatus {
     WaitingTrade = 7u64,
 }
 
+impl AmmStatus {
+    /// Converts the `AmmInfo.status` value into an [AmmStatus](enum.AmmStatus.html).
+    pub fn from_u64(status: u64) -> Option<Self> {
+        match status {
+            0 => Some(AmmStatus::Uninitialized),
+            1 => Some(AmmStatus::Initialized),
+            2 => Some(AmmStatus::Disabled),
+            3 => Some(AmmStatus::WithdrawOnly),
+            4 => Some(AmmStatus::LiquidityOnly),
+            5 => Some(AmmStatus::OrderBookOnly),
+            6 => Some(AmmStatus::SwapOnly),
+            7 => Some(AmmStatus::WaitingTrade),
+            _ => None,
+        }
+    }
+
+    /// Whether the pool keeps orders on the market, so its open orders funds count as liquidity.
+    pub fn orderbook_permission(&self) -> bool {
+        matches!(
+            self,
+            AmmStatus::Initialized | AmmStatus::OrderBookOnly | AmmStatus::WaitingTrade
+        )
+    }
+}
+
+#[cfg_attr(feature = "client", derive(Debug))]
 #[repr(u64)]
 pub enum AmmState {
     InvlidState = 0u64,
@@ -29,6 +60,7 @@ pub enum AmmState {
     PurgeOrderState = 6u64,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "client", derive(Debug))]
 #[repr(C)]
 #[derive(Clone, Copy, Default, PartialEq)]
@@ -107,40 +139,54 @@ pub struct AmmInfo {
     pub orderbook_to_init_time: u64,
 
     /// swap coin in amount
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub swap_coin_in_amount: u128,
     /// swap pc out amount
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub swap_pc_out_amount: u128,
     /// charge pc as swap fee while swap pc to coin
     pub swap_acc_pc_fee: u64,
 
     /// swap pc in amount
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub swap_pc_in_amount: u128,
     /// swap coin out amount
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub swap_coin_out_amount: u128,
     /// charge coin as swap fee while swap coin to pc
     pub swap_acc_coin_fee: u64,
 
     /// Coin vault
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub coin_vault: Pubkey,
     /// Pc vault
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub pc_vault: Pubkey,
     /// Coin vault mint
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub coin_vault_mint: Pubkey,
     /// Pc vault mint
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub pc_vault_mint: Pubkey,
     /// lp mint
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub lp_mint: Pubkey,
     /// open_orders key
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub open_orders: Pubkey,
     /// market key
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub market: Pubkey,
     /// market program key
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub market_program: Pubkey,
     /// target_orders key
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub target_orders: Pubkey,
     /// padding
     pub padding1: [u64; 8],
     /// amm owner key
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub amm_owner: Pubkey,
     /// pool lp amount
     pub lp_amount: u64,
@@ -149,3 +195,100 @@ pub struct AmmInfo {
     /// padding
     pub padding2: [u64; 2],
 }
+
+impl AmmInfo {
+    /// Length of the amm account data.
+    pub const LEN: usize = 752;
+
+    /// Decodes the AMM v4 pool account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let mut r = AccountReader::new(input);
+        Ok(Self {
+            status: r.u64()?,
+            nonce: r.u64()?,
+            order_num: r.u64()?,
+            depth: r.u64()?,
+            coin_decimals: r.u64()?,
+            pc_decimals: r.u64()?,
+            state: r.u64()?,
+            reset_flag: r.u64()?,
+            min_size: r.u64()?,
+            vol_max_cut_ratio: r.u64()?,
+            amount_wave: r.u64()?,
+            coin_lot_size: r.u64()?,
+            pc_lot_size: r.u64()?,
+            min_price_multiplier: r.u64()?,
+            max_price_multiplier: r.u64()?,
+            sys_decimal_value: r.u64()?,
+            min_separate_numerator: r.u64()?,
+            min_separate_denominator: r.u64()?,
+            trade_fee_numerator: r.u64()?,
+            trade_fee_denominator: r.u64()?,
+            pnl_numerator: r.u64()?,
+            pnl_denominator: r.u64()?,
+            swap_fee_numerator: r.u64()?,
+            swap_fee_denominator: r.u64()?,
+            need_take_pnl_coin: r.u64()?,
+            need_take_pnl_pc: r.u64()?,
+            total_pnl_pc: r.u64()?,
+            total_pnl_coin: r.u64()?,
+            pool_open_time: r.u64()?,
+            padding: r.array(AccountReader::u64)?,
+            orderbook_to_init_time: r.u64()?,
+            swap_coin_in_amount: r.u128()?,
+            swap_pc_out_amount: r.u128()?,
+            swap_acc_pc_fee: r.u64()?,
+            swap_pc_in_amount: r.u128()?,
+            swap_coin_out_amount: r.u128()?,
+            swap_acc_coin_fee: r.u64()?,
+            coin_vault: r.pubkey()?,
+            pc_vault: r.pubkey()?,
+            coin_vault_mint: r.pubkey()?,
+            pc_vault_mint: r.pubkey()?,
+            lp_mint: r.pubkey()?,
+            open_orders: r.pubkey()?,
+            market: r.pubkey()?,
+            market_program: r.pubkey()?,
+            target_orders: r.pubkey()?,
+            padding1: r.array(AccountReader::u64)?,
+            amm_owner: r.pubkey()?,
+            lp_amount: r.u64()?,
+            client_order_id: r.u64()?,
+            padding2: r.array(AccountReader::u64)?,
+        })
+    }
+
+    /// Checks the amm coin and pc lot sizes against its market.
+    pub fn check_market_lot_sizes(&self, market: &MarketState) -> Result<(), ProgramError> {
+        market.check_lot_sizes(self.coin_lot_size, self.pc_lot_size)
+    }
+
+    /// Calculates the pool (coin, pc) reserves the program swaps against.
+    ///
+    /// The open orders funds are added while the pool status allows orderbook
+    /// trading, and the pnl not yet taken is subtracted.
+    pub fn effective_reserves(
+        &self,
+        coin_vault_amount: u64,
+        pc_vault_amount: u64,
+        open_orders: &OpenOrders,
+    ) -> Result<(u64, u64), ProgramError> {
+        let orderbook_permission = AmmStatus::from_u64(self.status)
+            .map(|status| status.orderbook_permission())
+            .unwrap_or(false);
+        let (open_orders_coin, open_orders_pc) = if orderbook_permission {
+            (open_orders.native_coin_total, open_orders.native_pc_total)
+        } else {
+            (0, 0)
+        };
+        let coin_amount = coin_vault_amount
+            .checked_add(open_orders_coin)
+            .and_then(|amount| amount.checked_sub(self.need_take_pnl_coin))
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let pc_amount = pc_vault_amount
+            .checked_add(open_orders_pc)
+            .and_then(|amount| amount.checked_sub(self.need_take_pnl_pc))
+            .ok_or(ProgramError::InvalidAccountData)?;
+        Ok((coin_amount, pc_amount))
+    }
+}
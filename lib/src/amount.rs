@@ -0,0 +1,216 @@
+//! Token amounts and prices with decimals.
+//!
+//! The programs take raw amounts in atoms, the smallest unit of a mint. A
+//! [TokenAmount](struct.TokenAmount.html) keeps the mint decimals along with
+//! the atoms, a [UiAmount](struct.UiAmount.html) is the decimal amount shown
+//! to users, and a [PoolPrice](struct.PoolPrice.html) normalizes the pc per
+//! coin price of a pool by the decimals of both mints.
+
+use solana_program::program_error::ProgramError;
+use std::{convert::TryFrom, fmt};
+
+/// Raw amount of a mint, in atoms.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TokenAmount {
+    /// amount in atoms
+    pub amount: u64,
+    /// decimals of the mint
+    pub decimals: u8,
+}
+
+impl TokenAmount {
+    pub fn new(amount: u64, decimals: u8) -> Self {
+        Self { amount, decimals }
+    }
+
+    /// Converts a decimal amount, rounded to the nearest atom.
+    pub fn from_ui_amount(ui_amount: f64, decimals: u8) -> Result<Self, ProgramError> {
+        let amount = (ui_amount * 10f64.powi(decimals as i32)).round();
+        if !(0.0..=u64::MAX as f64).contains(&amount) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Self::new(amount as u64, decimals))
+    }
+
+    /// Parses a decimal amount string like `"12.5"` exactly, failing if it has
+    /// more fractional digits than `decimals`.
+    pub fn parse_ui(ui_amount: &str, decimals: u8) -> Result<Self, ProgramError> {
+        let (integer, fraction) = ui_amount.split_once('.').unwrap_or((ui_amount, ""));
+        if (integer.is_empty() && fraction.is_empty())
+            || fraction.len() > decimals as usize
+            || !integer
+                .bytes()
+                .chain(fraction.bytes())
+                .all(|digit| digit.is_ascii_digit())
+        {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let digits = format!(
+            "{}{:0<width$}",
+            integer,
+            fraction,
+            width = decimals as usize
+        );
+        let amount = digits
+            .parse::<u64>()
+            .map_err(|_| ProgramError::InvalidArgument)?;
+        Ok(Self::new(amount, decimals))
+    }
+
+    /// Decimal amount, rounded to the nearest `f64`.
+    pub fn ui_amount(&self) -> f64 {
+        self.amount as f64 / 10f64.powi(self.decimals as i32)
+    }
+
+    /// Exact decimal amount string, without trailing zeros.
+    pub fn to_ui_string(&self) -> String {
+        let decimals = self.decimals as usize;
+        let digits = format!("{:0>width$}", self.amount, width = decimals + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - decimals);
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            integer.to_string()
+        } else {
+            format!("{}.{}", integer, fraction)
+        }
+    }
+}
+
+impl fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_ui_string())
+    }
+}
+
+impl From<TokenAmount> for u64 {
+    fn from(amount: TokenAmount) -> Self {
+        amount.amount
+    }
+}
+
+/// Decimal amount of a mint, as shown to users.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct UiAmount {
+    /// decimal amount
+    pub amount: f64,
+    /// decimals of the mint
+    pub decimals: u8,
+}
+
+impl UiAmount {
+    pub fn new(amount: f64, decimals: u8) -> Self {
+        Self { amount, decimals }
+    }
+
+    /// Amount in atoms, rounded to the nearest atom.
+    pub fn to_token_amount(&self) -> Result<TokenAmount, ProgramError> {
+        TokenAmount::from_ui_amount(self.amount, self.decimals)
+    }
+}
+
+impl From<TokenAmount> for UiAmount {
+    fn from(amount: TokenAmount) -> Self {
+        Self::new(amount.ui_amount(), amount.decimals)
+    }
+}
+
+impl TryFrom<UiAmount> for TokenAmount {
+    type Error = ProgramError;
+
+    fn try_from(amount: UiAmount) -> Result<Self, Self::Error> {
+        amount.to_token_amount()
+    }
+}
+
+/// Amount accepted by the quote and builder APIs of the pools.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Amount {
+    /// raw amount in atoms of the mint
+    Atoms(u64),
+    /// raw amount with decimals, checked against the mint
+    Token(TokenAmount),
+    /// decimal amount, checked against the mint
+    Ui(UiAmount),
+}
+
+impl Amount {
+    /// Amount in atoms of a mint with `decimals`.
+    pub fn to_atoms(&self, decimals: u8) -> Result<u64, ProgramError> {
+        match self {
+            Self::Atoms(amount) => Ok(*amount),
+            Self::Token(amount) if amount.decimals == decimals => Ok(amount.amount),
+            Self::Ui(amount) if amount.decimals == decimals => Ok(amount.to_token_amount()?.amount),
+            _ => Err(ProgramError::InvalidArgument),
+        }
+    }
+}
+
+impl From<u64> for Amount {
+    fn from(amount: u64) -> Self {
+        Self::Atoms(amount)
+    }
+}
+
+impl From<TokenAmount> for Amount {
+    fn from(amount: TokenAmount) -> Self {
+        Self::Token(amount)
+    }
+}
+
+impl From<UiAmount> for Amount {
+    fn from(amount: UiAmount) -> Self {
+        Self::Ui(amount)
+    }
+}
+
+/// Price of the coin in pc of a pool.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PoolPrice {
+    /// pc atoms per coin atom
+    pub raw: f64,
+    pub coin_decimals: u8,
+    pub pc_decimals: u8,
+}
+
+impl PoolPrice {
+    /// Price of a pc amount per coin amount, `None` for a zero coin amount.
+    pub fn from_amounts(coin_amount: TokenAmount, pc_amount: TokenAmount) -> Option<Self> {
+        (coin_amount.amount > 0).then(|| Self {
+            raw: pc_amount.amount as f64 / coin_amount.amount as f64,
+            coin_decimals: coin_amount.decimals,
+            pc_decimals: pc_amount.decimals,
+        })
+    }
+
+    /// Spot price of a constant product pool with the coin and pc reserves.
+    pub fn from_reserves(
+        coin_reserve: u64,
+        pc_reserve: u64,
+        coin_decimals: u8,
+        pc_decimals: u8,
+    ) -> Option<Self> {
+        Self::from_amounts(
+            TokenAmount::new(coin_reserve, coin_decimals),
+            TokenAmount::new(pc_reserve, pc_decimals),
+        )
+    }
+
+    /// Decimal pc amount per decimal coin amount.
+    pub fn ui_price(&self) -> f64 {
+        self.raw * 10f64.powi(self.coin_decimals as i32 - self.pc_decimals as i32)
+    }
+
+    /// Decimal coin amount per decimal pc amount, `None` for a zero price.
+    pub fn inverse_ui_price(&self) -> Option<f64> {
+        let ui_price = self.ui_price();
+        (ui_price > 0.0).then(|| 1.0 / ui_price)
+    }
+
+    /// Pc value of a coin amount at the price, rounded down.
+    pub fn pc_value(&self, coin_amount: u64) -> TokenAmount {
+        TokenAmount::new((coin_amount as f64 * self.raw) as u64, self.pc_decimals)
+    }
+}
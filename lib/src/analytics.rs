@@ -0,0 +1,170 @@
+//! Fee and pnl analytics from pool account snapshots.
+//!
+//! The AMM v4 and stable pool accounts keep cumulative swap amounts, swap
+//! fees and protocol pnl. Diffing two snapshots of a pool account gives its
+//! activity between them: volume, fee revenue, the pnl accrued to the
+//! protocol and the fee yield left to the lp.
+
+use crate::{amm_stats, amount::PoolPrice, stable_stats};
+use solana_program::program_error::ProgramError;
+
+/// Seconds of a 365 days year, the period of the APRs.
+pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 3600;
+
+/// Cumulative counters of a pool account.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PoolCounters {
+    /// coin swapped in, from coin to pc
+    pub swap_coin_in_amount: u128,
+    /// pc swapped out, from coin to pc
+    pub swap_pc_out_amount: u128,
+    /// pc swapped in, from pc to coin
+    pub swap_pc_in_amount: u128,
+    /// coin swapped out, from pc to coin
+    pub swap_coin_out_amount: u128,
+    /// coin charged as swap fee, from coin to pc
+    pub swap_coin_fee: u64,
+    /// pc charged as swap fee, from pc to coin
+    pub swap_pc_fee: u64,
+    /// coin pnl of the protocol, counted when the program computes it, before
+    /// it is taken
+    pub total_pnl_coin: u64,
+    /// pc pnl of the protocol, counted when the program computes it, before it
+    /// is taken
+    pub total_pnl_pc: u64,
+}
+
+impl From<&amm_stats::AmmInfo> for PoolCounters {
+    fn from(amm: &amm_stats::AmmInfo) -> Self {
+        Self {
+            swap_coin_in_amount: amm.swap_coin_in_amount,
+            swap_pc_out_amount: amm.swap_pc_out_amount,
+            swap_pc_in_amount: amm.swap_pc_in_amount,
+            swap_coin_out_amount: amm.swap_coin_out_amount,
+            swap_coin_fee: amm.swap_acc_coin_fee,
+            swap_pc_fee: amm.swap_acc_pc_fee,
+            total_pnl_coin: amm.total_pnl_coin,
+            total_pnl_pc: amm.total_pnl_pc,
+        }
+    }
+}
+
+impl From<&stable_stats::OutPutData> for PoolCounters {
+    fn from(out_put: &stable_stats::OutPutData) -> Self {
+        Self {
+            swap_coin_in_amount: out_put.swap_coin_in_amount,
+            swap_pc_out_amount: out_put.swap_pc_out_amount,
+            swap_pc_in_amount: out_put.swap_pc_in_amount,
+            swap_coin_out_amount: out_put.swap_coin_out_amount,
+            swap_coin_fee: out_put.swap_coin_fee,
+            swap_pc_fee: out_put.swap_pc_fee,
+            total_pnl_coin: out_put.total_pnl_coin,
+            total_pnl_pc: out_put.total_pnl_pc,
+        }
+    }
+}
+
+impl From<&stable_stats::AmmInfo> for PoolCounters {
+    fn from(amm: &stable_stats::AmmInfo) -> Self {
+        Self::from(&amm.out_put)
+    }
+}
+
+/// Counters of a pool account at a time.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PoolSnapshot {
+    /// unix timestamp of the snapshot, in seconds
+    pub timestamp: i64,
+    pub counters: PoolCounters,
+}
+
+impl PoolSnapshot {
+    pub fn new(timestamp: i64, counters: impl Into<PoolCounters>) -> Self {
+        Self {
+            timestamp,
+            counters: counters.into(),
+        }
+    }
+}
+
+/// Activity of a pool between two snapshots.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PoolActivity {
+    /// seconds between the snapshots
+    pub elapsed_seconds: u64,
+    /// coin swapped in and out
+    pub coin_volume: u128,
+    /// pc swapped in and out, the volume in pc without a price
+    pub pc_volume: u128,
+    /// coin charged as swap fee
+    pub coin_fee: u64,
+    /// pc charged as swap fee
+    pub pc_fee: u64,
+    /// coin pnl accrued to the protocol
+    pub coin_pnl: u64,
+    /// pc pnl accrued to the protocol
+    pub pc_pnl: u64,
+}
+
+impl PoolActivity {
+    /// Diffs the `start` and `end` snapshots of a pool. Fails with
+    /// `InvalidArgument` if `end` is before `start` or a counter went down,
+    /// snapshots of another pool or out of order.
+    pub fn between(start: &PoolSnapshot, end: &PoolSnapshot) -> Result<Self, ProgramError> {
+        let elapsed_seconds = end
+            .timestamp
+            .checked_sub(start.timestamp)
+            .and_then(|elapsed| u64::try_from(elapsed).ok())
+            .ok_or(ProgramError::InvalidArgument)?;
+        let (start, end) = (&start.counters, &end.counters);
+        let diff = |start: u128, end: u128| end.checked_sub(start);
+        let diff_u64 = |start: u64, end: u64| end.checked_sub(start);
+        let coin_volume = diff(start.swap_coin_in_amount, end.swap_coin_in_amount)
+            .zip(diff(start.swap_coin_out_amount, end.swap_coin_out_amount))
+            .and_then(|(swap_in, swap_out)| swap_in.checked_add(swap_out));
+        let pc_volume = diff(start.swap_pc_in_amount, end.swap_pc_in_amount)
+            .zip(diff(start.swap_pc_out_amount, end.swap_pc_out_amount))
+            .and_then(|(swap_in, swap_out)| swap_in.checked_add(swap_out));
+        Ok(Self {
+            elapsed_seconds,
+            coin_volume: coin_volume.ok_or(ProgramError::InvalidArgument)?,
+            pc_volume: pc_volume.ok_or(ProgramError::InvalidArgument)?,
+            coin_fee: diff_u64(start.swap_coin_fee, end.swap_coin_fee)
+                .ok_or(ProgramError::InvalidArgument)?,
+            pc_fee: diff_u64(start.swap_pc_fee, end.swap_pc_fee)
+                .ok_or(ProgramError::InvalidArgument)?,
+            coin_pnl: diff_u64(start.total_pnl_coin, end.total_pnl_coin)
+                .ok_or(ProgramError::InvalidArgument)?,
+            pc_pnl: diff_u64(start.total_pnl_pc, end.total_pnl_pc)
+                .ok_or(ProgramError::InvalidArgument)?,
+        })
+    }
+
+    /// Swap fee revenue in pc atoms, the coin fee valued at `price`.
+    pub fn pc_fee_revenue(&self, price: &PoolPrice) -> f64 {
+        self.coin_fee as f64 * price.raw + self.pc_fee as f64
+    }
+
+    /// Pnl accrued to the protocol in pc atoms, the coin pnl valued at
+    /// `price`.
+    pub fn pc_protocol_pnl(&self, price: &PoolPrice) -> f64 {
+        self.coin_pnl as f64 * price.raw + self.pc_pnl as f64
+    }
+
+    /// Fees left to the lp in pc atoms, the swap fees without the protocol
+    /// pnl taken from them, none if the pnl outgrows them.
+    pub fn pc_lp_fees(&self, price: &PoolPrice) -> f64 {
+        (self.pc_fee_revenue(price) - self.pc_protocol_pnl(price)).max(0.0)
+    }
+
+    /// Implied lp fee APR of a pool with the coin and pc reserves, the lp
+    /// fees over the pool value extrapolated to a year. `None` for an empty
+    /// pool or snapshots at the same time.
+    pub fn lp_fee_apr(&self, coin_reserve: u64, pc_reserve: u64, price: &PoolPrice) -> Option<f64> {
+        let pool_value = coin_reserve as f64 * price.raw + pc_reserve as f64;
+        (pool_value > 0.0 && self.elapsed_seconds > 0).then(|| {
+            self.pc_lp_fees(price) / pool_value * SECONDS_PER_YEAR as f64
+                / self.elapsed_seconds as f64
+        })
+    }
+}
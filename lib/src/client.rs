@@ -0,0 +1,647 @@
+//! Off-chain helpers of the `client` feature.
+//!
+//! The state types are read by the programs with the BPF `repr(C)` layout,
+//! where `u128` is 8 bytes aligned. Host targets align `u128` on 16 bytes, so
+//! account data is decoded field by field instead of being cast. The CLMM and
+//! CPMM accounts are packed and prefixed with their anchor discriminator.
+
+use crate::{
+    account_reader::AccountReader, amm_stats, clmm_stats, cpmm_stats, farm_stats, farm_v6_stats,
+    stable_stats, staking_stats,
+};
+use solana_program::program_error::ProgramError;
+use std::fmt;
+
+impl stable_stats::AmmInfo {
+    /// Decodes the stable swap pool account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let mut r = AccountReader::new(input);
+        Ok(Self {
+            account_type: r.u64()?,
+            status: r.u64()?,
+            nonce: r.u64()?,
+            order_num: r.u64()?,
+            depth: r.u64()?,
+            coin_decimals: r.u64()?,
+            pc_decimals: r.u64()?,
+            state: r.u64()?,
+            reset_flag: r.u64()?,
+            min_size: r.u64()?,
+            vol_max_cut_ratio: r.u64()?,
+            amount_wave: r.u64()?,
+            coin_lot_size: r.u64()?,
+            pc_lot_size: r.u64()?,
+            min_price_multiplier: r.u64()?,
+            max_price_multiplier: r.u64()?,
+            sys_decimal_value: r.u64()?,
+            abort_trade_factor: r.u64()?,
+            price_tick_multiplier: r.u64()?,
+            price_tick: r.u64()?,
+            fees: stable_stats::Fees {
+                min_separate_numerator: r.u64()?,
+                min_separate_denominator: r.u64()?,
+                trade_fee_numerator: r.u64()?,
+                trade_fee_denominator: r.u64()?,
+                pnl_numerator: r.u64()?,
+                pnl_denominator: r.u64()?,
+                swap_fee_numerator: r.u64()?,
+                swap_fee_denominator: r.u64()?,
+            },
+            out_put: stable_stats::OutPutData {
+                need_take_pnl_coin: r.u64()?,
+                need_take_pnl_pc: r.u64()?,
+                total_pnl_pc: r.u64()?,
+                total_pnl_coin: r.u64()?,
+                pool_open_time: r.u64()?,
+                punish_pc_amount: r.u64()?,
+                punish_coin_amount: r.u64()?,
+                orderbook_to_init_time: r.u64()?,
+                swap_coin_in_amount: r.u128()?,
+                swap_pc_out_amount: r.u128()?,
+                swap_pc_in_amount: r.u128()?,
+                swap_coin_out_amount: r.u128()?,
+                swap_pc_fee: r.u64()?,
+                swap_coin_fee: r.u64()?,
+            },
+            coin_vault: r.pubkey()?,
+            pc_vault: r.pubkey()?,
+            coin_mint: r.pubkey()?,
+            pc_mint: r.pubkey()?,
+            lp_mint: r.pubkey()?,
+            model_data_key: r.pubkey()?,
+            open_orders: r.pubkey()?,
+            serum_market: r.pubkey()?,
+            serum_program: r.pubkey()?,
+            target_orders: r.pubkey()?,
+            amm_admin: r.pubkey()?,
+            padding: r.array(AccountReader::u64)?,
+        })
+    }
+}
+
+impl stable_stats::TargetOrder {
+    fn read(r: &mut AccountReader) -> Result<Self, ProgramError> {
+        Ok(Self {
+            plan_price: r.u128()?,
+            plan_vol: r.u128()?,
+            place_price: r.u64()?,
+            place_vol: r.u64()?,
+        })
+    }
+}
+
+impl stable_stats::TargetOrders {
+    /// Decodes the stable swap target orders account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let mut r = AccountReader::new(input);
+        Ok(Self {
+            account_type: r.u64()?,
+            status: r.u64()?,
+            buy_orders: r.array(stable_stats::TargetOrder::read)?,
+            sell_orders: r.array(stable_stats::TargetOrder::read)?,
+            last_order_numerator: r.u64()?,
+            last_order_denominator: r.u64()?,
+            plan_orders_cur: r.u64()?,
+            place_orders_cur: r.u64()?,
+            valid_buy_order_num: r.u64()?,
+            valid_sell_order_num: r.u64()?,
+            stable_mid_price: r.u128()?,
+            padding0: r.array(AccountReader::u128)?,
+            padding: r.array(AccountReader::u64)?,
+        })
+    }
+}
+
+impl stable_stats::Order {
+    fn read(r: &mut AccountReader) -> Result<Self, ProgramError> {
+        Ok(Self {
+            price: r.u64()?,
+            qty: r.u64()?,
+            id: r.u128()?,
+            slot: r.u64()?,
+        })
+    }
+}
+
+impl stable_stats::MyOrders {
+    /// Decodes the stable swap orders account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let mut r = AccountReader::new(input);
+        Ok(Self {
+            buy_len: r.u64()?,
+            sell_len: r.u64()?,
+            buy_orders: r.array(stable_stats::Order::read)?,
+            sell_orders: r.array(stable_stats::Order::read)?,
+        })
+    }
+}
+
+impl farm_stats::FarmPool {
+    /// Decodes the farm pool account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let mut r = AccountReader::new(input);
+        Ok(Self {
+            state: r.u64()?,
+            nonce: r.u64()?,
+            lp_vault: r.pubkey()?,
+            reward_vault_a: r.pubkey()?,
+            reward_total_a: r.u64()?,
+            acc_ray_per_share_a: r.u128()?,
+            reward_per_slot_a: r.u64()?,
+            reward_vault_b: r.option_pubkey()?,
+            reward_total_b: r.u64()?,
+            acc_ray_per_share_b: r.u128()?,
+            reward_per_slot_b: r.u64()?,
+            last_pool_update_slot: r.u64()?,
+            owner: r.pubkey()?,
+        })
+    }
+}
+
+impl farm_stats::FarmerInfo {
+    /// Decodes the farmer account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let mut r = AccountReader::new(input);
+        Ok(Self {
+            state: r.u64()?,
+            farm_pool: r.pubkey()?,
+            farmer: r.pubkey()?,
+            deposit_balance: r.u64()?,
+            reward_debt_a: r.u64()?,
+            reward_debt_b: r.u64()?,
+        })
+    }
+}
+
+impl farm_stats::FarmerInfoV2 {
+    /// Decodes the farmer v2 account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let mut r = AccountReader::new(input);
+        Ok(Self {
+            state: r.u64()?,
+            farm_pool: r.pubkey()?,
+            farmer: r.pubkey()?,
+            deposit_balance: r.u64()?,
+            reward_debt_a: r.u128()?,
+            reward_debt_b: r.u128()?,
+            account_type: r.u64()?,
+            pending: r.array(AccountReader::u64)?,
+        })
+    }
+}
+
+impl farm_v6_stats::RewardInfo {
+    fn read(r: &mut AccountReader) -> Result<Self, ProgramError> {
+        Ok(Self {
+            reward_state: r.u64()?,
+            reward_open_time: r.u64()?,
+            reward_end_time: r.u64()?,
+            reward_last_update_time: r.u64()?,
+            total_reward: r.u64()?,
+            total_reward_emissioned: r.u64()?,
+            reward_claimed: r.u64()?,
+            reward_per_second: r.u64()?,
+            acc_reward_per_share: r.u128()?,
+            reward_vault: r.pubkey()?,
+            reward_mint: r.pubkey()?,
+            reward_sender: r.pubkey()?,
+            reward_type: r.u64()?,
+            padding: r.array(AccountReader::u64)?,
+        })
+    }
+}
+
+impl farm_v6_stats::FarmState {
+    /// Decodes the farm v6 account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let mut r = AccountReader::new(input);
+        Ok(Self {
+            padding0: r.u64()?,
+            state: r.u64()?,
+            nonce: r.u64()?,
+            valid_reward_token_num: r.u64()?,
+            reward_multiplier: r.u128()?,
+            reward_period_max: r.u64()?,
+            reward_period_min: r.u64()?,
+            reward_period_extend: r.u64()?,
+            lp_mint: r.pubkey()?,
+            lp_vault: r.pubkey()?,
+            reward_infos: r.array(farm_v6_stats::RewardInfo::read)?,
+            creator: r.pubkey()?,
+            padding1: r.pubkey()?,
+            padding: r.array(AccountReader::u64)?,
+        })
+    }
+}
+
+impl farm_v6_stats::FarmerLedger {
+    /// Decodes the farm v6 ledger account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let mut r = AccountReader::new(input);
+        Ok(Self {
+            padding0: r.u64()?,
+            state: r.u64()?,
+            farm: r.pubkey()?,
+            owner: r.pubkey()?,
+            deposited: r.u64()?,
+            reward_debts: r.array(AccountReader::u128)?,
+            vote_locked_balance: r.u64()?,
+            padding: r.array(AccountReader::u64)?,
+        })
+    }
+}
+
+impl clmm_stats::AmmConfig {
+    /// Decodes the CLMM amm config account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let mut r = AccountReader::new_packed(input, &clmm_stats::AMM_CONFIG_DISCRIMINATOR)?;
+        Ok(Self {
+            bump: r.u8()?,
+            index: r.u16()?,
+            owner: r.pubkey()?,
+            protocol_fee_rate: r.u32()?,
+            trade_fee_rate: r.u32()?,
+            tick_spacing: r.u16()?,
+            fund_fee_rate: r.u32()?,
+            padding_u32: r.u32()?,
+            fund_owner: r.pubkey()?,
+            padding: r.array(AccountReader::u64)?,
+        })
+    }
+}
+
+impl clmm_stats::RewardInfo {
+    fn read(r: &mut AccountReader) -> Result<Self, ProgramError> {
+        Ok(Self {
+            reward_state: r.u8()?,
+            open_time: r.u64()?,
+            end_time: r.u64()?,
+            last_update_time: r.u64()?,
+            emissions_per_second_x64: r.u128()?,
+            reward_total_emissioned: r.u64()?,
+            reward_claimed: r.u64()?,
+            token_mint: r.pubkey()?,
+            token_vault: r.pubkey()?,
+            authority: r.pubkey()?,
+            reward_growth_global_x64: r.u128()?,
+        })
+    }
+}
+
+impl clmm_stats::PoolState {
+    /// Decodes the CLMM pool account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let mut r = AccountReader::new_packed(input, &clmm_stats::POOL_STATE_DISCRIMINATOR)?;
+        Ok(Self {
+            bump: r.bytes()?,
+            amm_config: r.pubkey()?,
+            owner: r.pubkey()?,
+            token_mint_0: r.pubkey()?,
+            token_mint_1: r.pubkey()?,
+            token_vault_0: r.pubkey()?,
+            token_vault_1: r.pubkey()?,
+            observation_key: r.pubkey()?,
+            mint_decimals_0: r.u8()?,
+            mint_decimals_1: r.u8()?,
+            tick_spacing: r.u16()?,
+            liquidity: r.u128()?,
+            sqrt_price_x64: r.u128()?,
+            tick_current: r.i32()?,
+            padding3: r.u16()?,
+            padding4: r.u16()?,
+            fee_growth_global_0_x64: r.u128()?,
+            fee_growth_global_1_x64: r.u128()?,
+            protocol_fees_token_0: r.u64()?,
+            protocol_fees_token_1: r.u64()?,
+            swap_in_amount_token_0: r.u128()?,
+            swap_out_amount_token_1: r.u128()?,
+            swap_in_amount_token_1: r.u128()?,
+            swap_out_amount_token_0: r.u128()?,
+            status: r.u8()?,
+            padding: r.bytes()?,
+            reward_infos: r.array(clmm_stats::RewardInfo::read)?,
+            tick_array_bitmap: r.array(AccountReader::u64)?,
+            total_fees_token_0: r.u64()?,
+            total_fees_claimed_token_0: r.u64()?,
+            total_fees_token_1: r.u64()?,
+            total_fees_claimed_token_1: r.u64()?,
+            fund_fees_token_0: r.u64()?,
+            fund_fees_token_1: r.u64()?,
+            open_time: r.u64()?,
+            recent_epoch: r.u64()?,
+            padding1: r.array(AccountReader::u64)?,
+            padding2: r.array(AccountReader::u64)?,
+        })
+    }
+}
+
+impl clmm_stats::PositionRewardInfo {
+    fn read(r: &mut AccountReader) -> Result<Self, ProgramError> {
+        Ok(Self {
+            growth_inside_last_x64: r.u128()?,
+            reward_amount_owed: r.u64()?,
+        })
+    }
+}
+
+impl clmm_stats::PersonalPositionState {
+    /// Decodes the CLMM personal position account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let mut r =
+            AccountReader::new_packed(input, &clmm_stats::PERSONAL_POSITION_STATE_DISCRIMINATOR)?;
+        Ok(Self {
+            bump: r.u8()?,
+            nft_mint: r.pubkey()?,
+            pool_id: r.pubkey()?,
+            tick_lower_index: r.i32()?,
+            tick_upper_index: r.i32()?,
+            liquidity: r.u128()?,
+            fee_growth_inside_0_last_x64: r.u128()?,
+            fee_growth_inside_1_last_x64: r.u128()?,
+            token_fees_owed_0: r.u64()?,
+            token_fees_owed_1: r.u64()?,
+            reward_infos: r.array(clmm_stats::PositionRewardInfo::read)?,
+            recent_epoch: r.u64()?,
+            padding: r.array(AccountReader::u64)?,
+        })
+    }
+}
+
+impl clmm_stats::TickState {
+    fn read(r: &mut AccountReader) -> Result<Self, ProgramError> {
+        Ok(Self {
+            tick: r.i32()?,
+            liquidity_net: r.i128()?,
+            liquidity_gross: r.u128()?,
+            fee_growth_outside_0_x64: r.u128()?,
+            fee_growth_outside_1_x64: r.u128()?,
+            reward_growths_outside_x64: r.array(AccountReader::u128)?,
+            padding: r.array(AccountReader::u32)?,
+        })
+    }
+}
+
+impl clmm_stats::TickArrayState {
+    /// Decodes the CLMM tick array account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let mut r = AccountReader::new_packed(input, &clmm_stats::TICK_ARRAY_STATE_DISCRIMINATOR)?;
+        Ok(Self {
+            pool_id: r.pubkey()?,
+            start_tick_index: r.i32()?,
+            ticks: r.array(clmm_stats::TickState::read)?,
+            initialized_tick_count: r.u8()?,
+            recent_epoch: r.u64()?,
+            padding: r.bytes()?,
+        })
+    }
+}
+
+impl clmm_stats::TickArrayBitmapExtension {
+    /// Decodes the CLMM tick array bitmap extension account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let mut r = AccountReader::new_packed(
+            input,
+            &clmm_stats::TICK_ARRAY_BITMAP_EXTENSION_DISCRIMINATOR,
+        )?;
+        Ok(Self {
+            pool_id: r.pubkey()?,
+            positive_tick_array_bitmap: r.array(|r| r.array(AccountReader::u64))?,
+            negative_tick_array_bitmap: r.array(|r| r.array(AccountReader::u64))?,
+        })
+    }
+}
+
+impl cpmm_stats::AmmConfig {
+    /// Decodes the CPMM amm config account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let mut r = AccountReader::new_packed(input, &cpmm_stats::AMM_CONFIG_DISCRIMINATOR)?;
+        Ok(Self {
+            bump: r.u8()?,
+            disable_create_pool: r.bool()?,
+            index: r.u16()?,
+            trade_fee_rate: r.u64()?,
+            protocol_fee_rate: r.u64()?,
+            fund_fee_rate: r.u64()?,
+            create_pool_fee: r.u64()?,
+            protocol_owner: r.pubkey()?,
+            fund_owner: r.pubkey()?,
+            padding: r.array(AccountReader::u64)?,
+        })
+    }
+}
+
+impl cpmm_stats::PoolState {
+    /// Decodes the CPMM pool account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let mut r = AccountReader::new_packed(input, &cpmm_stats::POOL_STATE_DISCRIMINATOR)?;
+        Ok(Self {
+            amm_config: r.pubkey()?,
+            pool_creator: r.pubkey()?,
+            token_0_vault: r.pubkey()?,
+            token_1_vault: r.pubkey()?,
+            lp_mint: r.pubkey()?,
+            token_0_mint: r.pubkey()?,
+            token_1_mint: r.pubkey()?,
+            token_0_program: r.pubkey()?,
+            token_1_program: r.pubkey()?,
+            observation_key: r.pubkey()?,
+            auth_bump: r.u8()?,
+            status: r.u8()?,
+            lp_mint_decimals: r.u8()?,
+            mint_0_decimals: r.u8()?,
+            mint_1_decimals: r.u8()?,
+            lp_supply: r.u64()?,
+            protocol_fees_token_0: r.u64()?,
+            protocol_fees_token_1: r.u64()?,
+            fund_fees_token_0: r.u64()?,
+            fund_fees_token_1: r.u64()?,
+            open_time: r.u64()?,
+            recent_epoch: r.u64()?,
+            padding: r.array(AccountReader::u64)?,
+        })
+    }
+}
+
+impl cpmm_stats::Observation {
+    fn read(r: &mut AccountReader) -> Result<Self, ProgramError> {
+        Ok(Self {
+            block_timestamp: r.u64()?,
+            cumulative_token_0_price_x32: r.u128()?,
+            cumulative_token_1_price_x32: r.u128()?,
+        })
+    }
+}
+
+impl cpmm_stats::ObservationState {
+    /// Decodes the CPMM observation account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let mut r = AccountReader::new_packed(input, &cpmm_stats::OBSERVATION_STATE_DISCRIMINATOR)?;
+        Ok(Self {
+            initialized: r.bool()?,
+            observation_index: r.u16()?,
+            pool_id: r.pubkey()?,
+            observations: r.array(cpmm_stats::Observation::read)?,
+            padding: r.array(AccountReader::u64)?,
+        })
+    }
+}
+
+impl staking_stats::StakePool {
+    /// Decodes the stake pool account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let mut r = AccountReader::new(input);
+        Ok(Self {
+            state: r.u64()?,
+            nonce: r.u64()?,
+            lp_vault: r.pubkey()?,
+            reward_vault: r.pubkey()?,
+            owner: r.pubkey()?,
+            padding: r.array(AccountReader::u64)?,
+            reward_total: r.u64()?,
+            acc_ray_per_share: r.u128()?,
+            last_pool_update_slot: r.u64()?,
+            reward_per_slot: r.u64()?,
+        })
+    }
+}
+
+impl staking_stats::StakerInfo {
+    /// Decodes the staker account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let mut r = AccountReader::new(input);
+        Ok(Self {
+            state: r.u64()?,
+            stake_pool: r.pubkey()?,
+            staker_owner: r.pubkey()?,
+            deposit_balance: r.u64()?,
+            reward_debt: r.u64()?,
+        })
+    }
+}
+
+impl staking_stats::StakerInfoV2 {
+    /// Decodes the staker v2 account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let mut r = AccountReader::new(input);
+        Ok(Self {
+            state: r.u64()?,
+            stake_pool: r.pubkey()?,
+            staker_owner: r.pubkey()?,
+            deposit_balance: r.u64()?,
+            reward_debt: r.u128()?,
+            account_type: r.u64()?,
+            padding: r.array(AccountReader::u64)?,
+        })
+    }
+}
+
+/// Writes `numerator / denominator` as a percentage.
+struct FeeRate(u64, u64);
+
+impl fmt::Display for FeeRate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.1 == 0 {
+            return write!(f, "{}/0", self.0);
+        }
+        write!(f, "{}%", self.0 as f64 * 100f64 / self.1 as f64)
+    }
+}
+
+impl fmt::Display for amm_stats::AmmStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl fmt::Display for stable_stats::AmmStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Pool summary, one field per line.
+impl fmt::Display for amm_stats::AmmInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match amm_stats::AmmStatus::from_u64(self.status) {
+            Some(status) => writeln!(f, "status: {}", status)?,
+            None => writeln!(f, "status: unknown ({})", self.status)?,
+        }
+        writeln!(
+            f,
+            "coin: mint {} vault {} decimals {}",
+            self.coin_vault_mint, self.coin_vault, self.coin_decimals
+        )?;
+        writeln!(
+            f,
+            "pc: mint {} vault {} decimals {}",
+            self.pc_vault_mint, self.pc_vault, self.pc_decimals
+        )?;
+        writeln!(f, "lp: mint {} supply {}", self.lp_mint, self.lp_amount)?;
+        writeln!(
+            f,
+            "market: {} program {} open orders {}",
+            self.market, self.market_program, self.open_orders
+        )?;
+        writeln!(f, "target orders: {}", self.target_orders)?;
+        writeln!(
+            f,
+            "fees: trade {} swap {} pnl {}",
+            FeeRate(self.trade_fee_numerator, self.trade_fee_denominator),
+            FeeRate(self.swap_fee_numerator, self.swap_fee_denominator),
+            FeeRate(self.pnl_numerator, self.pnl_denominator)
+        )?;
+        writeln!(
+            f,
+            "need take pnl: coin {} pc {}",
+            self.need_take_pnl_coin, self.need_take_pnl_pc
+        )?;
+        write!(f, "pool open time: {}", self.pool_open_time)
+    }
+}
+
+/// Pool summary, one field per line.
+impl fmt::Display for stable_stats::AmmInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match stable_stats::AmmStatus::from_u64(self.status) {
+            Some(status) => writeln!(f, "status: {}", status)?,
+            None => writeln!(f, "status: unknown ({})", self.status)?,
+        }
+        writeln!(
+            f,
+            "coin: mint {} vault {} decimals {}",
+            self.coin_mint, self.coin_vault, self.coin_decimals
+        )?;
+        writeln!(
+            f,
+            "pc: mint {} vault {} decimals {}",
+            self.pc_mint, self.pc_vault, self.pc_decimals
+        )?;
+        writeln!(f, "lp: mint {}", self.lp_mint)?;
+        writeln!(
+            f,
+            "market: {} program {} open orders {}",
+            self.serum_market, self.serum_program, self.open_orders
+        )?;
+        writeln!(
+            f,
+            "model data: {} target orders: {}",
+            self.model_data_key, self.target_orders
+        )?;
+        writeln!(
+            f,
+            "fees: trade {} swap {} pnl {}",
+            FeeRate(
+                self.fees.trade_fee_numerator,
+                self.fees.trade_fee_denominator
+            ),
+            FeeRate(self.fees.swap_fee_numerator, self.fees.swap_fee_denominator),
+            FeeRate(self.fees.pnl_numerator, self.fees.pnl_denominator)
+        )?;
+        writeln!(
+            f,
+            "need take pnl: coin {} pc {}",
+            self.out_put.need_take_pnl_coin, self.out_put.need_take_pnl_pc
+        )?;
+        write!(f, "pool open time: {}", self.out_put.pool_open_time)
+    }
+}
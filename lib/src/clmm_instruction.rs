@@ -0,0 +1,998 @@
+//! Instruction types
+
+#![allow(clippy::too_many_arguments)]
+
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar,
+};
+use std::convert::TryInto;
+
+solana_program::declare_id!("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK");
+
+pub const TOKEN_2022_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+pub const MEMO_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+pub const METADATA_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+pub const POOL_SEED: &[u8] = b"pool";
+pub const POOL_VAULT_SEED: &[u8] = b"pool_vault";
+pub const OBSERVATION_SEED: &[u8] = b"observation";
+pub const POSITION_SEED: &[u8] = b"position";
+pub const TICK_ARRAY_SEED: &[u8] = b"tick_array";
+pub const POOL_TICK_ARRAY_BITMAP_SEED: &[u8] = b"pool_tick_array_bitmap_extension";
+pub const METADATA_SEED: &[u8] = b"metadata";
+
+/// Anchor discriminators, the first 8 bytes of `sha256("global:<instruction name>")`.
+const OPEN_POSITION_V2: [u8; 8] = [77, 184, 74, 214, 112, 86, 241, 199];
+const INCREASE_LIQUIDITY_V2: [u8; 8] = [133, 29, 89, 223, 69, 238, 176, 10];
+const DECREASE_LIQUIDITY_V2: [u8; 8] = [58, 127, 188, 62, 79, 82, 196, 96];
+const CLOSE_POSITION: [u8; 8] = [123, 134, 81, 0, 49, 68, 98, 98];
+const SWAP_V2: [u8; 8] = [43, 4, 237, 11, 26, 201, 30, 98];
+const SWAP_ROUTER_BASE_IN: [u8; 8] = [69, 125, 115, 218, 245, 186, 242, 196];
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct OpenPositionV2Instruction {
+    /// lower tick of the position
+    pub tick_lower_index: i32,
+    /// upper tick of the position
+    pub tick_upper_index: i32,
+    /// start index of the tick array holding the lower tick
+    pub tick_array_lower_start_index: i32,
+    /// start index of the tick array holding the upper tick
+    pub tick_array_upper_start_index: i32,
+    /// liquidity to add, 0 to derive it from the base amount
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub liquidity: u128,
+    /// max token 0 amount to deposit
+    pub amount_0_max: u64,
+    /// max token 1 amount to deposit
+    pub amount_1_max: u64,
+    /// create the metaplex metadata of the position nft
+    pub with_metadata: bool,
+    /// with a 0 liquidity, derive it from token 0 (true) or token 1 (false)
+    pub base_flag: Option<bool>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct IncreaseLiquidityV2Instruction {
+    /// liquidity to add, 0 to derive it from the base amount
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub liquidity: u128,
+    /// max token 0 amount to deposit
+    pub amount_0_max: u64,
+    /// max token 1 amount to deposit
+    pub amount_1_max: u64,
+    /// with a 0 liquidity, derive it from token 0 (true) or token 1 (false)
+    pub base_flag: Option<bool>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DecreaseLiquidityV2Instruction {
+    /// liquidity to remove
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub liquidity: u128,
+    /// min token 0 amount to receive
+    pub amount_0_min: u64,
+    /// min token 1 amount to receive
+    pub amount_1_min: u64,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SwapV2Instruction {
+    /// amount in if `is_base_input`, amount out otherwise
+    pub amount: u64,
+    /// min amount out if `is_base_input`, max amount in otherwise
+    pub other_amount_threshold: u64,
+    /// sqrt price limit in Q64.64, 0 for no limit
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub sqrt_price_limit_x64: u128,
+    /// `amount` is the input amount
+    pub is_base_input: bool,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SwapRouterBaseInInstruction {
+    /// amount of the first pool input token
+    pub amount_in: u64,
+    /// min amount of the last pool output token
+    pub amount_out_minimum: u64,
+}
+
+/// Instructions supported by the CLMM program.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClmmInstruction {
+    ///   Opens a position, minting the position nft.
+    ///
+    ///   0. `[writable, signer]` payer Account
+    ///   1. `[]` position nft owner Account
+    ///   2. `[writable, signer]` position nft mint Account to create
+    ///   3. `[writable]` position nft token Account, the owner's associated token account
+    ///   4. `[writable]` position nft metadata Account
+    ///   5. `[writable]` pool state Account
+    ///   6. `[writable]` protocol position Account
+    ///   7. `[writable]` lower tick array Account
+    ///   8. `[writable]` upper tick array Account
+    ///   9. `[writable]` personal position Account
+    ///   10. `[writable]` user token 0 Account
+    ///   11. `[writable]` user token 1 Account
+    ///   12. `[writable]` pool token 0 vault
+    ///   13. `[writable]` pool token 1 vault
+    ///   14. `[]` Rent program id
+    ///   15. `[]` Sys program id
+    ///   16. `[]` Spl Token program id
+    ///   17. `[]` Associated Token program id
+    ///   18. `[]` Metadata program id
+    ///   19. `[]` Token 2022 program id
+    ///   20. `[]` token 0 mint Account
+    ///   21. `[]` token 1 mint Account
+    ///   22. `[writable]` (optional) tick array bitmap extension Account
+    OpenPositionV2(OpenPositionV2Instruction),
+
+    ///   Adds liquidity to a position.
+    ///
+    ///   0. `[signer]` position nft owner Account
+    ///   1. `[]` position nft token Account
+    ///   2. `[writable]` pool state Account
+    ///   3. `[writable]` protocol position Account
+    ///   4. `[writable]` personal position Account
+    ///   5. `[writable]` lower tick array Account
+    ///   6. `[writable]` upper tick array Account
+    ///   7. `[writable]` user token 0 Account
+    ///   8. `[writable]` user token 1 Account
+    ///   9. `[writable]` pool token 0 vault
+    ///   10. `[writable]` pool token 1 vault
+    ///   11. `[]` Spl Token program id
+    ///   12. `[]` Token 2022 program id
+    ///   13. `[]` token 0 mint Account
+    ///   14. `[]` token 1 mint Account
+    ///   15. `[writable]` (optional) tick array bitmap extension Account
+    IncreaseLiquidityV2(IncreaseLiquidityV2Instruction),
+
+    ///   Removes liquidity from a position, and collects its fees and rewards.
+    ///
+    ///   0. `[signer]` position nft owner Account
+    ///   1. `[]` position nft token Account
+    ///   2. `[writable]` personal position Account
+    ///   3. `[writable]` pool state Account
+    ///   4. `[writable]` protocol position Account
+    ///   5. `[writable]` pool token 0 vault
+    ///   6. `[writable]` pool token 1 vault
+    ///   7. `[writable]` lower tick array Account
+    ///   8. `[writable]` upper tick array Account
+    ///   9. `[writable]` user token 0 Account
+    ///   10. `[writable]` user token 1 Account
+    ///   11. `[]` Spl Token program id
+    ///   12. `[]` Token 2022 program id
+    ///   13. `[]` Memo program id
+    ///   14. `[]` token 0 mint Account
+    ///   15. `[]` token 1 mint Account
+    ///   16. `[writable]` (optional) tick array bitmap extension Account
+    ///   17. `[writable]` pool reward vault, repeated with 18 and 19 for each reward
+    ///   18. `[writable]` user reward token Account
+    ///   19. `[]` reward mint Account
+    DecreaseLiquidityV2(DecreaseLiquidityV2Instruction),
+
+    ///   Closes an empty position, burning the position nft.
+    ///
+    ///   0. `[writable, signer]` position nft owner Account
+    ///   1. `[writable]` position nft mint Account
+    ///   2. `[writable]` position nft token Account
+    ///   3. `[writable]` personal position Account
+    ///   4. `[]` Sys program id
+    ///   5. `[]` Spl Token program id
+    ClosePosition,
+
+    ///   Swaps through a pool, crossing the tick arrays passed in order.
+    ///
+    ///   0. `[signer]` user wallet Account
+    ///   1. `[]` amm config Account
+    ///   2. `[writable]` pool state Account
+    ///   3. `[writable]` user input token Account
+    ///   4. `[writable]` user output token Account
+    ///   5. `[writable]` pool input vault
+    ///   6. `[writable]` pool output vault
+    ///   7. `[writable]` observation state Account
+    ///   8. `[]` Spl Token program id
+    ///   9. `[]` Token 2022 program id
+    ///   10. `[]` Memo program id
+    ///   11. `[]` input mint Account
+    ///   12. `[]` output mint Account
+    ///   13. `[]` (optional) tick array bitmap extension Account
+    ///   14. `[writable]` tick array Account, repeated for each tick array crossed
+    SwapV2(SwapV2Instruction),
+
+    ///   Swaps an exact input amount through a chain of pools, each pool
+    ///   output being the next pool input.
+    ///
+    ///   0. `[signer]` user wallet Account
+    ///   1. `[writable]` user input token Account of the first pool
+    ///   2. `[writable]` input mint Account of the first pool
+    ///   3. `[]` Spl Token program id
+    ///   4. `[]` Token 2022 program id
+    ///   5. `[]` Memo program id
+    ///
+    ///   Then for each pool, in swap order:
+    ///
+    ///   0. `[]` amm config Account
+    ///   1. `[writable]` pool state Account
+    ///   2. `[writable]` user output token Account
+    ///   3. `[writable]` pool input vault
+    ///   4. `[writable]` pool output vault
+    ///   5. `[]` output mint Account
+    ///   6. `[writable]` observation state Account
+    ///   7. `[]` (optional) tick array bitmap extension Account
+    ///   8. `[writable]` tick array Account, repeated for each tick array crossed
+    SwapRouterBaseIn(SwapRouterBaseInInstruction),
+}
+
+impl ClmmInstruction {
+    /// Unpacks a byte buffer into a [ClmmInstruction](enum.ClmmInstruction.html).
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        if input.len() < 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (discriminator, rest) = input.split_at(8);
+        Ok(match discriminator.try_into().unwrap() {
+            OPEN_POSITION_V2 => {
+                let (tick_lower_index, rest) = Self::unpack_i32(rest)?;
+                let (tick_upper_index, rest) = Self::unpack_i32(rest)?;
+                let (tick_array_lower_start_index, rest) = Self::unpack_i32(rest)?;
+                let (tick_array_upper_start_index, rest) = Self::unpack_i32(rest)?;
+                let (liquidity, rest) = Self::unpack_u128(rest)?;
+                let (amount_0_max, rest) = Self::unpack_u64(rest)?;
+                let (amount_1_max, rest) = Self::unpack_u64(rest)?;
+                let (with_metadata, rest) = Self::unpack_bool(rest)?;
+                let (base_flag, _rest) = Self::unpack_option_bool(rest)?;
+                Self::OpenPositionV2(OpenPositionV2Instruction {
+                    tick_lower_index,
+                    tick_upper_index,
+                    tick_array_lower_start_index,
+                    tick_array_upper_start_index,
+                    liquidity,
+                    amount_0_max,
+                    amount_1_max,
+                    with_metadata,
+                    base_flag,
+                })
+            }
+            INCREASE_LIQUIDITY_V2 => {
+                let (liquidity, rest) = Self::unpack_u128(rest)?;
+                let (amount_0_max, rest) = Self::unpack_u64(rest)?;
+                let (amount_1_max, rest) = Self::unpack_u64(rest)?;
+                let (base_flag, _rest) = Self::unpack_option_bool(rest)?;
+                Self::IncreaseLiquidityV2(IncreaseLiquidityV2Instruction {
+                    liquidity,
+                    amount_0_max,
+                    amount_1_max,
+                    base_flag,
+                })
+            }
+            DECREASE_LIQUIDITY_V2 => {
+                let (liquidity, rest) = Self::unpack_u128(rest)?;
+                let (amount_0_min, rest) = Self::unpack_u64(rest)?;
+                let (amount_1_min, _rest) = Self::unpack_u64(rest)?;
+                Self::DecreaseLiquidityV2(DecreaseLiquidityV2Instruction {
+                    liquidity,
+                    amount_0_min,
+                    amount_1_min,
+                })
+            }
+            CLOSE_POSITION => Self::ClosePosition,
+            SWAP_V2 => {
+                let (amount, rest) = Self::unpack_u64(rest)?;
+                let (other_amount_threshold, rest) = Self::unpack_u64(rest)?;
+                let (sqrt_price_limit_x64, rest) = Self::unpack_u128(rest)?;
+                let (is_base_input, _rest) = Self::unpack_bool(rest)?;
+                Self::SwapV2(SwapV2Instruction {
+                    amount,
+                    other_amount_threshold,
+                    sqrt_price_limit_x64,
+                    is_base_input,
+                })
+            }
+            SWAP_ROUTER_BASE_IN => {
+                let (amount_in, rest) = Self::unpack_u64(rest)?;
+                let (amount_out_minimum, _rest) = Self::unpack_u64(rest)?;
+                Self::SwapRouterBaseIn(SwapRouterBaseInInstruction {
+                    amount_in,
+                    amount_out_minimum,
+                })
+            }
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+
+    fn unpack_bytes<const N: usize>(input: &[u8]) -> Result<([u8; N], &[u8]), ProgramError> {
+        if input.len() >= N {
+            let (bytes, rest) = input.split_at(N);
+            Ok((bytes.try_into().unwrap(), rest))
+        } else {
+            Err(ProgramError::InvalidInstructionData)
+        }
+    }
+
+    fn unpack_i32(input: &[u8]) -> Result<(i32, &[u8]), ProgramError> {
+        let (bytes, rest) = Self::unpack_bytes(input)?;
+        Ok((i32::from_le_bytes(bytes), rest))
+    }
+
+    fn unpack_u64(input: &[u8]) -> Result<(u64, &[u8]), ProgramError> {
+        let (bytes, rest) = Self::unpack_bytes(input)?;
+        Ok((u64::from_le_bytes(bytes), rest))
+    }
+
+    fn unpack_u128(input: &[u8]) -> Result<(u128, &[u8]), ProgramError> {
+        let (bytes, rest) = Self::unpack_bytes(input)?;
+        Ok((u128::from_le_bytes(bytes), rest))
+    }
+
+    fn unpack_bool(input: &[u8]) -> Result<(bool, &[u8]), ProgramError> {
+        let ([value], rest) = Self::unpack_bytes(input)?;
+        match value {
+            0 => Ok((false, rest)),
+            1 => Ok((true, rest)),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+
+    fn unpack_option_bool(input: &[u8]) -> Result<(Option<bool>, &[u8]), ProgramError> {
+        let (is_some, rest) = Self::unpack_bool(input)?;
+        if is_some {
+            let (value, rest) = Self::unpack_bool(rest)?;
+            Ok((Some(value), rest))
+        } else {
+            Ok((None, rest))
+        }
+    }
+
+    /// Packs a [ClmmInstruction](enum.ClmmInstruction.html) into a byte buffer.
+    pub fn pack(&self) -> Result<Vec<u8>, ProgramError> {
+        let mut buf = Vec::with_capacity(64);
+        match self {
+            Self::OpenPositionV2(OpenPositionV2Instruction {
+                tick_lower_index,
+                tick_upper_index,
+                tick_array_lower_start_index,
+                tick_array_upper_start_index,
+                liquidity,
+                amount_0_max,
+                amount_1_max,
+                with_metadata,
+                base_flag,
+            }) => {
+                buf.extend_from_slice(&OPEN_POSITION_V2);
+                buf.extend_from_slice(&tick_lower_index.to_le_bytes());
+                buf.extend_from_slice(&tick_upper_index.to_le_bytes());
+                buf.extend_from_slice(&tick_array_lower_start_index.to_le_bytes());
+                buf.extend_from_slice(&tick_array_upper_start_index.to_le_bytes());
+                buf.extend_from_slice(&liquidity.to_le_bytes());
+                buf.extend_from_slice(&amount_0_max.to_le_bytes());
+                buf.extend_from_slice(&amount_1_max.to_le_bytes());
+                buf.push(*with_metadata as u8);
+                Self::pack_option_bool(*base_flag, &mut buf);
+            }
+            Self::IncreaseLiquidityV2(IncreaseLiquidityV2Instruction {
+                liquidity,
+                amount_0_max,
+                amount_1_max,
+                base_flag,
+            }) => {
+                buf.extend_from_slice(&INCREASE_LIQUIDITY_V2);
+                buf.extend_from_slice(&liquidity.to_le_bytes());
+                buf.extend_from_slice(&amount_0_max.to_le_bytes());
+                buf.extend_from_slice(&amount_1_max.to_le_bytes());
+                Self::pack_option_bool(*base_flag, &mut buf);
+            }
+            Self::DecreaseLiquidityV2(DecreaseLiquidityV2Instruction {
+                liquidity,
+                amount_0_min,
+                amount_1_min,
+            }) => {
+                buf.extend_from_slice(&DECREASE_LIQUIDITY_V2);
+                buf.extend_from_slice(&liquidity.to_le_bytes());
+                buf.extend_from_slice(&amount_0_min.to_le_bytes());
+                buf.extend_from_slice(&amount_1_min.to_le_bytes());
+            }
+            Self::ClosePosition => {
+                buf.extend_from_slice(&CLOSE_POSITION);
+            }
+            Self::SwapV2(SwapV2Instruction {
+                amount,
+                other_amount_threshold,
+                sqrt_price_limit_x64,
+                is_base_input,
+            }) => {
+                buf.extend_from_slice(&SWAP_V2);
+                buf.extend_from_slice(&amount.to_le_bytes());
+                buf.extend_from_slice(&other_amount_threshold.to_le_bytes());
+                buf.extend_from_slice(&sqrt_price_limit_x64.to_le_bytes());
+                buf.push(*is_base_input as u8);
+            }
+            Self::SwapRouterBaseIn(SwapRouterBaseInInstruction {
+                amount_in,
+                amount_out_minimum,
+            }) => {
+                buf.extend_from_slice(&SWAP_ROUTER_BASE_IN);
+                buf.extend_from_slice(&amount_in.to_le_bytes());
+                buf.extend_from_slice(&amount_out_minimum.to_le_bytes());
+            }
+        }
+        Ok(buf)
+    }
+
+    fn pack_option_bool(value: Option<bool>, buf: &mut Vec<u8>) {
+        match value {
+            Some(value) => {
+                buf.push(1);
+                buf.push(value as u8);
+            }
+            None => buf.push(0),
+        }
+    }
+}
+
+/// Accounts of a pool reward collected by a position.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RewardAccounts {
+    /// pool reward vault
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub reward_vault: Pubkey,
+    /// user reward token account receiving the reward
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub user_reward_token: Pubkey,
+    /// reward mint
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub reward_mint: Pubkey,
+}
+
+/// Accounts of a pool swapped through by a 'swap router base in'.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SwapRouterHop<'a> {
+    pub amm_config: Pubkey,
+    pub pool_state: Pubkey,
+    /// user token account receiving the pool output, the next pool input
+    pub user_output_token: Pubkey,
+    pub input_vault: Pubkey,
+    pub output_vault: Pubkey,
+    pub output_vault_mint: Pubkey,
+    pub observation_state: Pubkey,
+    pub tick_array_bitmap_extension: Option<Pubkey>,
+    /// tick arrays in the swap direction, starting with the array holding the
+    /// current tick
+    pub tick_arrays: &'a [Pubkey],
+}
+
+/// Finds the pool state of an amm config and a mint pair, `mint_0` < `mint_1`.
+pub fn get_pool_state(
+    program_id: &Pubkey,
+    amm_config: &Pubkey,
+    mint_0: &Pubkey,
+    mint_1: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            POOL_SEED,
+            amm_config.as_ref(),
+            mint_0.as_ref(),
+            mint_1.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Finds the pool vault of a mint.
+pub fn get_pool_vault(program_id: &Pubkey, pool_state: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[POOL_VAULT_SEED, pool_state.as_ref(), mint.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+/// Finds the pool observation state.
+pub fn get_observation_state(program_id: &Pubkey, pool_state: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[OBSERVATION_SEED, pool_state.as_ref()], program_id).0
+}
+
+/// Finds the personal position of a position nft.
+pub fn get_personal_position(program_id: &Pubkey, position_nft_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[POSITION_SEED, position_nft_mint.as_ref()], program_id).0
+}
+
+/// Finds the protocol position of a tick range.
+pub fn get_protocol_position(
+    program_id: &Pubkey,
+    pool_state: &Pubkey,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            POSITION_SEED,
+            pool_state.as_ref(),
+            &tick_lower_index.to_be_bytes(),
+            &tick_upper_index.to_be_bytes(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Finds the tick array starting at `start_index`.
+pub fn get_tick_array(program_id: &Pubkey, pool_state: &Pubkey, start_index: i32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            TICK_ARRAY_SEED,
+            pool_state.as_ref(),
+            &start_index.to_be_bytes(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Finds the pool tick array bitmap extension.
+pub fn get_tick_array_bitmap_extension(program_id: &Pubkey, pool_state: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[POOL_TICK_ARRAY_BITMAP_SEED, pool_state.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+/// Finds the metaplex metadata of a position nft.
+pub fn get_position_nft_metadata(position_nft_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            METADATA_SEED,
+            METADATA_PROGRAM_ID.as_ref(),
+            position_nft_mint.as_ref(),
+        ],
+        &METADATA_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Creates an 'open position v2' instruction.
+pub fn open_position_v2(
+    clmm_program: &Pubkey,
+    payer: &Pubkey,
+    position_nft_owner: &Pubkey,
+    position_nft_mint: &Pubkey,
+    position_nft_account: &Pubkey,
+    metadata_account: &Pubkey,
+    pool_state: &Pubkey,
+    protocol_position: &Pubkey,
+    tick_array_lower: &Pubkey,
+    tick_array_upper: &Pubkey,
+    personal_position: &Pubkey,
+    user_token_0: &Pubkey,
+    user_token_1: &Pubkey,
+    token_vault_0: &Pubkey,
+    token_vault_1: &Pubkey,
+    vault_0_mint: &Pubkey,
+    vault_1_mint: &Pubkey,
+    tick_array_bitmap_extension: Option<&Pubkey>,
+
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    tick_array_lower_start_index: i32,
+    tick_array_upper_start_index: i32,
+    liquidity: u128,
+    amount_0_max: u64,
+    amount_1_max: u64,
+    with_metadata: bool,
+    base_flag: Option<bool>,
+) -> Result<Instruction, ProgramError> {
+    let data = ClmmInstruction::OpenPositionV2(OpenPositionV2Instruction {
+        tick_lower_index,
+        tick_upper_index,
+        tick_array_lower_start_index,
+        tick_array_upper_start_index,
+        liquidity,
+        amount_0_max,
+        amount_1_max,
+        with_metadata,
+        base_flag,
+    })
+    .pack()?;
+
+    let mut accounts = vec![
+        // user
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(*position_nft_owner, false),
+        AccountMeta::new(*position_nft_mint, true),
+        AccountMeta::new(*position_nft_account, false),
+        AccountMeta::new(*metadata_account, false),
+        // pool
+        AccountMeta::new(*pool_state, false),
+        AccountMeta::new(*protocol_position, false),
+        AccountMeta::new(*tick_array_lower, false),
+        AccountMeta::new(*tick_array_upper, false),
+        AccountMeta::new(*personal_position, false),
+        // user
+        AccountMeta::new(*user_token_0, false),
+        AccountMeta::new(*user_token_1, false),
+        // pool
+        AccountMeta::new(*token_vault_0, false),
+        AccountMeta::new(*token_vault_1, false),
+        // programs
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(METADATA_PROGRAM_ID, false),
+        AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        // mints
+        AccountMeta::new_readonly(*vault_0_mint, false),
+        AccountMeta::new_readonly(*vault_1_mint, false),
+    ];
+
+    if let Some(tick_array_bitmap_extension) = tick_array_bitmap_extension {
+        accounts.push(AccountMeta::new(*tick_array_bitmap_extension, false));
+    }
+
+    Ok(Instruction {
+        program_id: *clmm_program,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'increase liquidity v2' instruction.
+pub fn increase_liquidity_v2(
+    clmm_program: &Pubkey,
+    position_nft_owner: &Pubkey,
+    position_nft_account: &Pubkey,
+    pool_state: &Pubkey,
+    protocol_position: &Pubkey,
+    personal_position: &Pubkey,
+    tick_array_lower: &Pubkey,
+    tick_array_upper: &Pubkey,
+    user_token_0: &Pubkey,
+    user_token_1: &Pubkey,
+    token_vault_0: &Pubkey,
+    token_vault_1: &Pubkey,
+    vault_0_mint: &Pubkey,
+    vault_1_mint: &Pubkey,
+    tick_array_bitmap_extension: Option<&Pubkey>,
+
+    liquidity: u128,
+    amount_0_max: u64,
+    amount_1_max: u64,
+    base_flag: Option<bool>,
+) -> Result<Instruction, ProgramError> {
+    let data = ClmmInstruction::IncreaseLiquidityV2(IncreaseLiquidityV2Instruction {
+        liquidity,
+        amount_0_max,
+        amount_1_max,
+        base_flag,
+    })
+    .pack()?;
+
+    let mut accounts = vec![
+        // user
+        AccountMeta::new_readonly(*position_nft_owner, true),
+        AccountMeta::new_readonly(*position_nft_account, false),
+        // pool
+        AccountMeta::new(*pool_state, false),
+        AccountMeta::new(*protocol_position, false),
+        AccountMeta::new(*personal_position, false),
+        AccountMeta::new(*tick_array_lower, false),
+        AccountMeta::new(*tick_array_upper, false),
+        // user
+        AccountMeta::new(*user_token_0, false),
+        AccountMeta::new(*user_token_1, false),
+        // pool
+        AccountMeta::new(*token_vault_0, false),
+        AccountMeta::new(*token_vault_1, false),
+        // programs
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        // mints
+        AccountMeta::new_readonly(*vault_0_mint, false),
+        AccountMeta::new_readonly(*vault_1_mint, false),
+    ];
+
+    if let Some(tick_array_bitmap_extension) = tick_array_bitmap_extension {
+        accounts.push(AccountMeta::new(*tick_array_bitmap_extension, false));
+    }
+
+    Ok(Instruction {
+        program_id: *clmm_program,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'decrease liquidity v2' instruction.
+pub fn decrease_liquidity_v2(
+    clmm_program: &Pubkey,
+    position_nft_owner: &Pubkey,
+    position_nft_account: &Pubkey,
+    personal_position: &Pubkey,
+    pool_state: &Pubkey,
+    protocol_position: &Pubkey,
+    token_vault_0: &Pubkey,
+    token_vault_1: &Pubkey,
+    tick_array_lower: &Pubkey,
+    tick_array_upper: &Pubkey,
+    user_token_0: &Pubkey,
+    user_token_1: &Pubkey,
+    vault_0_mint: &Pubkey,
+    vault_1_mint: &Pubkey,
+    tick_array_bitmap_extension: Option<&Pubkey>,
+    rewards: &[RewardAccounts],
+
+    liquidity: u128,
+    amount_0_min: u64,
+    amount_1_min: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = ClmmInstruction::DecreaseLiquidityV2(DecreaseLiquidityV2Instruction {
+        liquidity,
+        amount_0_min,
+        amount_1_min,
+    })
+    .pack()?;
+
+    let mut accounts = vec![
+        // user
+        AccountMeta::new_readonly(*position_nft_owner, true),
+        AccountMeta::new_readonly(*position_nft_account, false),
+        // pool
+        AccountMeta::new(*personal_position, false),
+        AccountMeta::new(*pool_state, false),
+        AccountMeta::new(*protocol_position, false),
+        AccountMeta::new(*token_vault_0, false),
+        AccountMeta::new(*token_vault_1, false),
+        AccountMeta::new(*tick_array_lower, false),
+        AccountMeta::new(*tick_array_upper, false),
+        // user
+        AccountMeta::new(*user_token_0, false),
+        AccountMeta::new(*user_token_1, false),
+        // programs
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        AccountMeta::new_readonly(MEMO_PROGRAM_ID, false),
+        // mints
+        AccountMeta::new_readonly(*vault_0_mint, false),
+        AccountMeta::new_readonly(*vault_1_mint, false),
+    ];
+
+    if let Some(tick_array_bitmap_extension) = tick_array_bitmap_extension {
+        accounts.push(AccountMeta::new(*tick_array_bitmap_extension, false));
+    }
+    for reward in rewards {
+        accounts.push(AccountMeta::new(reward.reward_vault, false));
+        accounts.push(AccountMeta::new(reward.user_reward_token, false));
+        accounts.push(AccountMeta::new_readonly(reward.reward_mint, false));
+    }
+
+    Ok(Instruction {
+        program_id: *clmm_program,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'collect rewards' instruction, a 'decrease liquidity v2' of 0
+/// liquidity which collects the position fees and the given rewards.
+pub fn collect_rewards(
+    clmm_program: &Pubkey,
+    position_nft_owner: &Pubkey,
+    position_nft_account: &Pubkey,
+    personal_position: &Pubkey,
+    pool_state: &Pubkey,
+    protocol_position: &Pubkey,
+    token_vault_0: &Pubkey,
+    token_vault_1: &Pubkey,
+    tick_array_lower: &Pubkey,
+    tick_array_upper: &Pubkey,
+    user_token_0: &Pubkey,
+    user_token_1: &Pubkey,
+    vault_0_mint: &Pubkey,
+    vault_1_mint: &Pubkey,
+    tick_array_bitmap_extension: Option<&Pubkey>,
+    rewards: &[RewardAccounts],
+) -> Result<Instruction, ProgramError> {
+    decrease_liquidity_v2(
+        clmm_program,
+        position_nft_owner,
+        position_nft_account,
+        personal_position,
+        pool_state,
+        protocol_position,
+        token_vault_0,
+        token_vault_1,
+        tick_array_lower,
+        tick_array_upper,
+        user_token_0,
+        user_token_1,
+        vault_0_mint,
+        vault_1_mint,
+        tick_array_bitmap_extension,
+        rewards,
+        0,
+        0,
+        0,
+    )
+}
+
+/// Creates a 'close position' instruction.
+pub fn close_position(
+    clmm_program: &Pubkey,
+    position_nft_owner: &Pubkey,
+    position_nft_mint: &Pubkey,
+    position_nft_account: &Pubkey,
+    personal_position: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = ClmmInstruction::ClosePosition.pack()?;
+
+    let accounts = vec![
+        // user
+        AccountMeta::new(*position_nft_owner, true),
+        AccountMeta::new(*position_nft_mint, false),
+        AccountMeta::new(*position_nft_account, false),
+        AccountMeta::new(*personal_position, false),
+        // programs
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    Ok(Instruction {
+        program_id: *clmm_program,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'swap v2' instruction. The tick arrays are passed in the swap
+/// direction, starting with the array holding the current tick.
+pub fn swap_v2(
+    clmm_program: &Pubkey,
+    amm_config: &Pubkey,
+    pool_state: &Pubkey,
+    input_vault: &Pubkey,
+    output_vault: &Pubkey,
+    observation_state: &Pubkey,
+    input_vault_mint: &Pubkey,
+    output_vault_mint: &Pubkey,
+    tick_array_bitmap_extension: Option<&Pubkey>,
+    tick_arrays: &[Pubkey],
+    user_input_token: &Pubkey,
+    user_output_token: &Pubkey,
+    user_owner: &Pubkey,
+
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit_x64: u128,
+    is_base_input: bool,
+) -> Result<Instruction, ProgramError> {
+    if tick_arrays.is_empty() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let data = ClmmInstruction::SwapV2(SwapV2Instruction {
+        amount,
+        other_amount_threshold,
+        sqrt_price_limit_x64,
+        is_base_input,
+    })
+    .pack()?;
+
+    let mut accounts = vec![
+        // user
+        AccountMeta::new_readonly(*user_owner, true),
+        // pool
+        AccountMeta::new_readonly(*amm_config, false),
+        AccountMeta::new(*pool_state, false),
+        // user
+        AccountMeta::new(*user_input_token, false),
+        AccountMeta::new(*user_output_token, false),
+        // pool
+        AccountMeta::new(*input_vault, false),
+        AccountMeta::new(*output_vault, false),
+        AccountMeta::new(*observation_state, false),
+        // programs
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        AccountMeta::new_readonly(MEMO_PROGRAM_ID, false),
+        // mints
+        AccountMeta::new_readonly(*input_vault_mint, false),
+        AccountMeta::new_readonly(*output_vault_mint, false),
+    ];
+
+    if let Some(tick_array_bitmap_extension) = tick_array_bitmap_extension {
+        accounts.push(AccountMeta::new_readonly(
+            *tick_array_bitmap_extension,
+            false,
+        ));
+    }
+    for tick_array in tick_arrays {
+        accounts.push(AccountMeta::new(*tick_array, false));
+    }
+
+    Ok(Instruction {
+        program_id: *clmm_program,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'swap router base in' instruction swapping `amount_in` through
+/// `hops` in order. Only the last pool output is checked against
+/// `amount_out_minimum`.
+pub fn swap_router_base_in(
+    clmm_program: &Pubkey,
+    input_vault_mint: &Pubkey,
+    hops: &[SwapRouterHop],
+    user_input_token: &Pubkey,
+    user_owner: &Pubkey,
+
+    amount_in: u64,
+    amount_out_minimum: u64,
+) -> Result<Instruction, ProgramError> {
+    if hops.is_empty() || hops.iter().any(|hop| hop.tick_arrays.is_empty()) {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let data = ClmmInstruction::SwapRouterBaseIn(SwapRouterBaseInInstruction {
+        amount_in,
+        amount_out_minimum,
+    })
+    .pack()?;
+
+    let mut accounts = vec![
+        // user
+        AccountMeta::new_readonly(*user_owner, true),
+        AccountMeta::new(*user_input_token, false),
+        AccountMeta::new(*input_vault_mint, false),
+        // programs
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        AccountMeta::new_readonly(MEMO_PROGRAM_ID, false),
+    ];
+
+    for hop in hops {
+        accounts.extend([
+            AccountMeta::new_readonly(hop.amm_config, false),
+            AccountMeta::new(hop.pool_state, false),
+            AccountMeta::new(hop.user_output_token, false),
+            AccountMeta::new(hop.input_vault, false),
+            AccountMeta::new(hop.output_vault, false),
+            AccountMeta::new_readonly(hop.output_vault_mint, false),
+            AccountMeta::new(hop.observation_state, false),
+        ]);
+        if let Some(tick_array_bitmap_extension) = hop.tick_array_bitmap_extension {
+            accounts.push(AccountMeta::new_readonly(
+                tick_array_bitmap_extension,
+                false,
+            ));
+        }
+        for tick_array in hop.tick_arrays {
+            accounts.push(AccountMeta::new(*tick_array, false));
+        }
+    }
+
+    Ok(Instruction {
+        program_id: *clmm_program,
+        accounts,
+        data,
+    })
+}
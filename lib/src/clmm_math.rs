@@ -0,0 +1,890 @@
+//! Concentrated liquidity math and swap simulation.
+//!
+//! Prices are square roots in Q64.64 of the token 0 price in token 1, and
+//! the functions round the same way as the CLMM program so a simulated swap
+//! matches the on-chain result to the unit.
+
+#![allow(clippy::too_many_arguments)]
+
+use crate::{
+    clmm_instruction,
+    clmm_stats::{
+        AmmConfig, PoolState, TickArrayBitmapExtension, TickArrayState, TickState,
+        EXTENSION_TICKARRAY_BITMAP_SIZE, TICK_ARRAY_SIZE,
+    },
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use std::cmp::Ordering;
+
+pub const MIN_TICK: i32 = -443636;
+pub const MAX_TICK: i32 = -MIN_TICK;
+/// Sqrt price of `MIN_TICK`.
+pub const MIN_SQRT_PRICE_X64: u128 = 4295048016;
+/// Sqrt price of `MAX_TICK`.
+pub const MAX_SQRT_PRICE_X64: u128 = 79226673521066979257578248091;
+/// Denominator of the amm config fee rates.
+pub const FEE_RATE_DENOMINATOR_VALUE: u32 = 1_000_000;
+/// Number of tick arrays tracked by the pool `tick_array_bitmap`, half on each side of tick 0.
+pub const TICK_ARRAY_BITMAP_SIZE: i32 = 512;
+
+/// 1 / sqrt(1.0001)^(2^i) in Q64.64, for i in 0..19.
+const SQRT_RATIOS: [u128; 19] = [
+    0xfffcb933bd6fb800,
+    0xfff97272373d4000,
+    0xfff2e50f5f657000,
+    0xffe5caca7e10f000,
+    0xffcb9843d60f7000,
+    0xff973b41fa98e800,
+    0xff2ea16466c9b000,
+    0xfe5dee046a9a3800,
+    0xfcbe86c7900bb000,
+    0xf987a7253ac65800,
+    0xf3392b0822bb6000,
+    0xe7159475a2caf000,
+    0xd097f3bdfd2f2000,
+    0xa9f746462d9f8000,
+    0x70d869a156f31c00,
+    0x31be135f97ed3200,
+    0x9aa508b5b85a500,
+    0x5d6af8dedc582c,
+    0x2216e584f5fa,
+];
+
+/// Unsigned 512 bits integer, wide enough for every intermediate product.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct U512([u64; 8]);
+
+impl U512 {
+    const ZERO: Self = Self([0; 8]);
+
+    fn from_u128(value: u128) -> Self {
+        let mut limbs = [0; 8];
+        limbs[0] = value as u64;
+        limbs[1] = (value >> 64) as u64;
+        Self(limbs)
+    }
+
+    fn to_u128(self) -> Option<u128> {
+        if self.0[2..].iter().any(|limb| *limb != 0) {
+            return None;
+        }
+        Some(u128::from(self.0[0]) | u128::from(self.0[1]) << 64)
+    }
+
+    fn to_u64(self) -> Option<u64> {
+        self.to_u128().and_then(|value| value.try_into().ok())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.iter().all(|limb| *limb == 0)
+    }
+
+    fn bit(&self, index: usize) -> bool {
+        self.0[index / 64] >> (index % 64) & 1 == 1
+    }
+
+    fn bits(&self) -> usize {
+        match self.0.iter().rposition(|limb| *limb != 0) {
+            Some(i) => i * 64 + 64 - self.0[i].leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    fn shl1(self) -> Self {
+        let mut limbs = [0; 8];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            *limb = self.0[i] << 1 | if i > 0 { self.0[i - 1] >> 63 } else { 0 };
+        }
+        Self(limbs)
+    }
+
+    fn shl64(self) -> Self {
+        let mut limbs = [0; 8];
+        limbs[1..].copy_from_slice(&self.0[..7]);
+        Self(limbs)
+    }
+
+    fn add(self, other: Self) -> Self {
+        let mut limbs = [0; 8];
+        let mut carry = false;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let (sum, c1) = self.0[i].overflowing_add(other.0[i]);
+            let (sum, c2) = sum.overflowing_add(carry as u64);
+            *limb = sum;
+            carry = c1 || c2;
+        }
+        Self(limbs)
+    }
+
+    /// Wrapping subtraction, callers check `self >= other`.
+    fn sub(self, other: Self) -> Self {
+        let mut limbs = [0; 8];
+        let mut borrow = false;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let (diff, b1) = self.0[i].overflowing_sub(other.0[i]);
+            let (diff, b2) = diff.overflowing_sub(borrow as u64);
+            *limb = diff;
+            borrow = b1 || b2;
+        }
+        Self(limbs)
+    }
+
+    /// Truncating multiplication, the operands stay below 256 bits.
+    fn mul(self, other: Self) -> Self {
+        let mut limbs = [0; 8];
+        for i in 0..8 {
+            let mut carry = 0u128;
+            for j in 0..8 - i {
+                let cur = u128::from(limbs[i + j])
+                    + u128::from(self.0[i]) * u128::from(other.0[j])
+                    + carry;
+                limbs[i + j] = cur as u64;
+                carry = cur >> 64;
+            }
+        }
+        Self(limbs)
+    }
+
+    fn div_rem(self, divisor: Self) -> Result<(Self, Self), ProgramError> {
+        if divisor.is_zero() {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let mut quotient = Self::ZERO;
+        let mut remainder = Self::ZERO;
+        for i in (0..self.bits()).rev() {
+            remainder = remainder.shl1();
+            remainder.0[0] |= self.bit(i) as u64;
+            if remainder >= divisor {
+                remainder = remainder.sub(divisor);
+                quotient.0[i / 64] |= 1 << (i % 64);
+            }
+        }
+        Ok((quotient, remainder))
+    }
+
+    fn div_floor(self, divisor: Self) -> Result<Self, ProgramError> {
+        Ok(self.div_rem(divisor)?.0)
+    }
+
+    fn div_ceil(self, divisor: Self) -> Result<Self, ProgramError> {
+        let (quotient, remainder) = self.div_rem(divisor)?;
+        Ok(if remainder.is_zero() {
+            quotient
+        } else {
+            quotient.add(Self::from_u128(1))
+        })
+    }
+}
+
+impl Ord for U512 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl PartialOrd for U512 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Returns the sqrt price of a tick.
+pub fn get_sqrt_price_at_tick(tick: i32) -> Result<u128, ProgramError> {
+    let abs_tick = tick.unsigned_abs();
+    if abs_tick > MAX_TICK as u32 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let mut ratio = if abs_tick & 1 != 0 {
+        SQRT_RATIOS[0]
+    } else {
+        1u128 << 64
+    };
+    for (i, sqrt_ratio) in SQRT_RATIOS.iter().enumerate().skip(1) {
+        if abs_tick & (1 << i) != 0 {
+            ratio = (ratio * sqrt_ratio) >> 64;
+        }
+    }
+    if tick > 0 {
+        ratio = u128::MAX / ratio;
+    }
+    Ok(ratio)
+}
+
+/// Returns the greatest tick whose sqrt price is lower or equal to `sqrt_price_x64`.
+pub fn get_tick_at_sqrt_price(sqrt_price_x64: u128) -> Result<i32, ProgramError> {
+    if !(MIN_SQRT_PRICE_X64..MAX_SQRT_PRICE_X64).contains(&sqrt_price_x64) {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let (mut low, mut high) = (MIN_TICK, MAX_TICK);
+    while low < high {
+        let mid = low + (high - low + 1) / 2;
+        if get_sqrt_price_at_tick(mid)? <= sqrt_price_x64 {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    Ok(low)
+}
+
+/// Converts a sqrt price to the ui price of token 0 in token 1.
+pub fn sqrt_price_x64_to_price(sqrt_price_x64: u128, decimals_0: u8, decimals_1: u8) -> f64 {
+    let sqrt_price = sqrt_price_x64 as f64 / (1u128 << 64) as f64;
+    sqrt_price * sqrt_price * 10f64.powi(i32::from(decimals_0) - i32::from(decimals_1))
+}
+
+/// Converts the ui price of token 0 in token 1 to a sqrt price.
+pub fn price_to_sqrt_price_x64(price: f64, decimals_0: u8, decimals_1: u8) -> u128 {
+    let price = price * 10f64.powi(i32::from(decimals_1) - i32::from(decimals_0));
+    (price.sqrt() * (1u128 << 64) as f64) as u128
+}
+
+/// Returns the start index of the tick array holding `tick`.
+pub fn get_tick_array_start_index(tick: i32, tick_spacing: u16) -> i32 {
+    let ticks_in_array = TICK_ARRAY_SIZE as i32 * i32::from(tick_spacing);
+    tick.div_euclid(ticks_in_array) * ticks_in_array
+}
+
+/// Returns the token 0 amount between two sqrt prices for a liquidity.
+pub fn get_delta_amount_0(
+    sqrt_price_a_x64: u128,
+    sqrt_price_b_x64: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<u64, ProgramError> {
+    let (low, high) = sorted(sqrt_price_a_x64, sqrt_price_b_x64);
+    if low == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let numerator_1 = U512::from_u128(liquidity).shl64();
+    let numerator_2 = U512::from_u128(high - low);
+    let product = numerator_1.mul(numerator_2);
+    let amount = if round_up {
+        product
+            .div_ceil(U512::from_u128(high))?
+            .div_ceil(U512::from_u128(low))?
+    } else {
+        product
+            .div_floor(U512::from_u128(high))?
+            .div_floor(U512::from_u128(low))?
+    };
+    amount.to_u64().ok_or(ProgramError::InvalidArgument)
+}
+
+/// Returns the token 1 amount between two sqrt prices for a liquidity.
+pub fn get_delta_amount_1(
+    sqrt_price_a_x64: u128,
+    sqrt_price_b_x64: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<u64, ProgramError> {
+    let (low, high) = sorted(sqrt_price_a_x64, sqrt_price_b_x64);
+    let product = U512::from_u128(liquidity).mul(U512::from_u128(high - low));
+    let q64 = U512::from_u128(1 << 64);
+    let amount = if round_up {
+        product.div_ceil(q64)?
+    } else {
+        product.div_floor(q64)?
+    };
+    amount.to_u64().ok_or(ProgramError::InvalidArgument)
+}
+
+/// Returns the liquidity of a token 0 amount between two sqrt prices.
+pub fn get_liquidity_from_amount_0(
+    sqrt_price_a_x64: u128,
+    sqrt_price_b_x64: u128,
+    amount_0: u64,
+) -> Result<u128, ProgramError> {
+    let (low, high) = sorted(sqrt_price_a_x64, sqrt_price_b_x64);
+    let intermediate = U512::from_u128(low)
+        .mul(U512::from_u128(high))
+        .div_floor(U512::from_u128(1 << 64))?;
+    U512::from_u128(amount_0.into())
+        .mul(intermediate)
+        .div_floor(U512::from_u128(high - low))?
+        .to_u128()
+        .ok_or(ProgramError::InvalidArgument)
+}
+
+/// Returns the liquidity of a token 1 amount between two sqrt prices.
+pub fn get_liquidity_from_amount_1(
+    sqrt_price_a_x64: u128,
+    sqrt_price_b_x64: u128,
+    amount_1: u64,
+) -> Result<u128, ProgramError> {
+    let (low, high) = sorted(sqrt_price_a_x64, sqrt_price_b_x64);
+    U512::from_u128(amount_1.into())
+        .shl64()
+        .div_floor(U512::from_u128(high - low))?
+        .to_u128()
+        .ok_or(ProgramError::InvalidArgument)
+}
+
+/// Returns the max liquidity of a position between two sqrt prices funded
+/// with the given token amounts.
+pub fn get_liquidity_from_amounts(
+    sqrt_price_current_x64: u128,
+    sqrt_price_a_x64: u128,
+    sqrt_price_b_x64: u128,
+    amount_0: u64,
+    amount_1: u64,
+) -> Result<u128, ProgramError> {
+    let (low, high) = sorted(sqrt_price_a_x64, sqrt_price_b_x64);
+    if sqrt_price_current_x64 <= low {
+        get_liquidity_from_amount_0(low, high, amount_0)
+    } else if sqrt_price_current_x64 < high {
+        Ok(std::cmp::min(
+            get_liquidity_from_amount_0(sqrt_price_current_x64, high, amount_0)?,
+            get_liquidity_from_amount_1(low, sqrt_price_current_x64, amount_1)?,
+        ))
+    } else {
+        get_liquidity_from_amount_1(low, high, amount_1)
+    }
+}
+
+/// Returns the token amounts of a position liquidity between two sqrt prices.
+pub fn get_amounts_from_liquidity(
+    sqrt_price_current_x64: u128,
+    sqrt_price_a_x64: u128,
+    sqrt_price_b_x64: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<(u64, u64), ProgramError> {
+    let (low, high) = sorted(sqrt_price_a_x64, sqrt_price_b_x64);
+    if sqrt_price_current_x64 < low {
+        Ok((get_delta_amount_0(low, high, liquidity, round_up)?, 0))
+    } else if sqrt_price_current_x64 < high {
+        Ok((
+            get_delta_amount_0(sqrt_price_current_x64, high, liquidity, round_up)?,
+            get_delta_amount_1(low, sqrt_price_current_x64, liquidity, round_up)?,
+        ))
+    } else {
+        Ok((0, get_delta_amount_1(low, high, liquidity, round_up)?))
+    }
+}
+
+fn sorted(a: u128, b: u128) -> (u128, u128) {
+    if a > b {
+        (b, a)
+    } else {
+        (a, b)
+    }
+}
+
+fn get_next_sqrt_price_from_amount_0_rounding_up(
+    sqrt_price_x64: u128,
+    liquidity: u128,
+    amount: u64,
+    add: bool,
+) -> Result<u128, ProgramError> {
+    if amount == 0 {
+        return Ok(sqrt_price_x64);
+    }
+    let numerator_1 = U512::from_u128(liquidity).shl64();
+    let product = U512::from_u128(amount.into()).mul(U512::from_u128(sqrt_price_x64));
+    let denominator = if add {
+        numerator_1.add(product)
+    } else {
+        if numerator_1 <= product {
+            return Err(ProgramError::InvalidArgument);
+        }
+        numerator_1.sub(product)
+    };
+    numerator_1
+        .mul(U512::from_u128(sqrt_price_x64))
+        .div_ceil(denominator)?
+        .to_u128()
+        .ok_or(ProgramError::InvalidArgument)
+}
+
+fn get_next_sqrt_price_from_amount_1_rounding_down(
+    sqrt_price_x64: u128,
+    liquidity: u128,
+    amount: u64,
+    add: bool,
+) -> Result<u128, ProgramError> {
+    let numerator = U512::from_u128(amount.into()).shl64();
+    let liquidity = U512::from_u128(liquidity);
+    if add {
+        let quotient = numerator
+            .div_floor(liquidity)?
+            .to_u128()
+            .ok_or(ProgramError::InvalidArgument)?;
+        sqrt_price_x64
+            .checked_add(quotient)
+            .ok_or(ProgramError::InvalidArgument)
+    } else {
+        let quotient = numerator
+            .div_ceil(liquidity)?
+            .to_u128()
+            .ok_or(ProgramError::InvalidArgument)?;
+        if sqrt_price_x64 <= quotient {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(sqrt_price_x64 - quotient)
+    }
+}
+
+fn get_next_sqrt_price_from_input(
+    sqrt_price_x64: u128,
+    liquidity: u128,
+    amount_in: u64,
+    zero_for_one: bool,
+) -> Result<u128, ProgramError> {
+    if zero_for_one {
+        get_next_sqrt_price_from_amount_0_rounding_up(sqrt_price_x64, liquidity, amount_in, true)
+    } else {
+        get_next_sqrt_price_from_amount_1_rounding_down(sqrt_price_x64, liquidity, amount_in, true)
+    }
+}
+
+fn get_next_sqrt_price_from_output(
+    sqrt_price_x64: u128,
+    liquidity: u128,
+    amount_out: u64,
+    zero_for_one: bool,
+) -> Result<u128, ProgramError> {
+    if zero_for_one {
+        get_next_sqrt_price_from_amount_1_rounding_down(
+            sqrt_price_x64,
+            liquidity,
+            amount_out,
+            false,
+        )
+    } else {
+        get_next_sqrt_price_from_amount_0_rounding_up(sqrt_price_x64, liquidity, amount_out, false)
+    }
+}
+
+/// Result of a swap within a single liquidity range.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SwapStep {
+    /// sqrt price after the step
+    pub sqrt_price_next_x64: u128,
+    /// input amount, without the fee
+    pub amount_in: u64,
+    /// output amount
+    pub amount_out: u64,
+    /// fee paid on the input amount
+    pub fee_amount: u64,
+}
+
+/// Swaps `amount_remaining` from `sqrt_price_current_x64` towards
+/// `sqrt_price_target_x64`, with a constant liquidity.
+pub fn compute_swap_step(
+    sqrt_price_current_x64: u128,
+    sqrt_price_target_x64: u128,
+    liquidity: u128,
+    amount_remaining: u64,
+    fee_rate: u32,
+    is_base_input: bool,
+    zero_for_one: bool,
+) -> Result<SwapStep, ProgramError> {
+    if fee_rate >= FEE_RATE_DENOMINATOR_VALUE {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let mut step = SwapStep::default();
+    if is_base_input {
+        let amount_remaining_less_fee =
+            (u128::from(amount_remaining) * u128::from(FEE_RATE_DENOMINATOR_VALUE - fee_rate)
+                / u128::from(FEE_RATE_DENOMINATOR_VALUE)) as u64;
+        let amount_in = if zero_for_one {
+            get_delta_amount_0(
+                sqrt_price_target_x64,
+                sqrt_price_current_x64,
+                liquidity,
+                true,
+            )
+        } else {
+            get_delta_amount_1(
+                sqrt_price_current_x64,
+                sqrt_price_target_x64,
+                liquidity,
+                true,
+            )
+        };
+        step.sqrt_price_next_x64 = match amount_in {
+            Ok(amount_in) if amount_remaining_less_fee >= amount_in => {
+                step.amount_in = amount_in;
+                sqrt_price_target_x64
+            }
+            _ => get_next_sqrt_price_from_input(
+                sqrt_price_current_x64,
+                liquidity,
+                amount_remaining_less_fee,
+                zero_for_one,
+            )?,
+        };
+    } else {
+        let amount_out = if zero_for_one {
+            get_delta_amount_1(
+                sqrt_price_target_x64,
+                sqrt_price_current_x64,
+                liquidity,
+                false,
+            )
+        } else {
+            get_delta_amount_0(
+                sqrt_price_current_x64,
+                sqrt_price_target_x64,
+                liquidity,
+                false,
+            )
+        };
+        step.sqrt_price_next_x64 = match amount_out {
+            Ok(amount_out) if amount_remaining >= amount_out => {
+                step.amount_out = amount_out;
+                sqrt_price_target_x64
+            }
+            _ => get_next_sqrt_price_from_output(
+                sqrt_price_current_x64,
+                liquidity,
+                amount_remaining,
+                zero_for_one,
+            )?,
+        };
+    }
+
+    let max = sqrt_price_target_x64 == step.sqrt_price_next_x64;
+    if zero_for_one {
+        if !max || !is_base_input {
+            step.amount_in = get_delta_amount_0(
+                step.sqrt_price_next_x64,
+                sqrt_price_current_x64,
+                liquidity,
+                true,
+            )?;
+        }
+        if !max || is_base_input {
+            step.amount_out = get_delta_amount_1(
+                step.sqrt_price_next_x64,
+                sqrt_price_current_x64,
+                liquidity,
+                false,
+            )?;
+        }
+    } else {
+        if !max || !is_base_input {
+            step.amount_in = get_delta_amount_1(
+                sqrt_price_current_x64,
+                step.sqrt_price_next_x64,
+                liquidity,
+                true,
+            )?;
+        }
+        if !max || is_base_input {
+            step.amount_out = get_delta_amount_0(
+                sqrt_price_current_x64,
+                step.sqrt_price_next_x64,
+                liquidity,
+                false,
+            )?;
+        }
+    }
+
+    if !is_base_input && step.amount_out > amount_remaining {
+        step.amount_out = amount_remaining;
+    }
+    step.fee_amount = if is_base_input && step.sqrt_price_next_x64 != sqrt_price_target_x64 {
+        // the remaining amount is the fee, even if the price could not move
+        amount_remaining - step.amount_in
+    } else {
+        let numerator = u128::from(step.amount_in) * u128::from(fee_rate);
+        let denominator = u128::from(FEE_RATE_DENOMINATOR_VALUE - fee_rate);
+        let fee = numerator / denominator + u128::from(numerator % denominator != 0);
+        fee.try_into().map_err(|_| ProgramError::InvalidArgument)?
+    };
+    Ok(step)
+}
+
+/// Result of a simulated swap.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SwapSimulation {
+    /// input amount, fee included
+    pub amount_in: u64,
+    /// output amount
+    pub amount_out: u64,
+    /// trade fee paid on the input amount
+    pub fee_amount: u64,
+    /// pool sqrt price after the swap
+    pub sqrt_price_x64: u128,
+    /// pool tick after the swap
+    pub tick_current: i32,
+    /// pool liquidity after the swap
+    pub liquidity: u128,
+    /// start index of the tick arrays to pass, in order
+    pub tick_array_start_indexes: Vec<i32>,
+    /// tick array accounts to pass, in order
+    pub tick_arrays: Vec<Pubkey>,
+    /// the tick array bitmap extension was read and must be passed
+    pub uses_bitmap_extension: bool,
+}
+
+/// Initialized tick arrays of a pool, from the pool bitmap and its extension.
+struct TickArrayBitmap<'a> {
+    pool: &'a PoolState,
+    extension: Option<&'a TickArrayBitmapExtension>,
+    ticks_in_array: i32,
+    uses_extension: bool,
+}
+
+impl<'a> TickArrayBitmap<'a> {
+    fn is_initialized(&mut self, start_index: i32) -> Result<bool, ProgramError> {
+        let index = start_index / self.ticks_in_array;
+        if (-TICK_ARRAY_BITMAP_SIZE..TICK_ARRAY_BITMAP_SIZE).contains(&index) {
+            let bit = (index + TICK_ARRAY_BITMAP_SIZE) as usize;
+            return Ok(self.pool.tick_array_bitmap[bit / 64] >> (bit % 64) & 1 == 1);
+        }
+        let extension = self.extension.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        self.uses_extension = true;
+        let size = TICK_ARRAY_BITMAP_SIZE as usize;
+        let (bitmaps, offset, bit) = if index >= 0 {
+            let k = (index - TICK_ARRAY_BITMAP_SIZE) as usize;
+            (&extension.positive_tick_array_bitmap, k / size, k % size)
+        } else {
+            let k = (-index - TICK_ARRAY_BITMAP_SIZE - 1) as usize;
+            (
+                &extension.negative_tick_array_bitmap,
+                k / size,
+                size - 1 - k % size,
+            )
+        };
+        if offset >= EXTENSION_TICKARRAY_BITMAP_SIZE {
+            return Ok(false);
+        }
+        Ok(bitmaps[offset][bit / 64] >> (bit % 64) & 1 == 1)
+    }
+
+    fn next_initialized(
+        &mut self,
+        start_index: i32,
+        zero_for_one: bool,
+    ) -> Result<Option<i32>, ProgramError> {
+        let spacing = (self.ticks_in_array / TICK_ARRAY_SIZE as i32) as u16;
+        let min = get_tick_array_start_index(MIN_TICK, spacing);
+        let max = get_tick_array_start_index(MAX_TICK, spacing);
+        let step = if zero_for_one {
+            -self.ticks_in_array
+        } else {
+            self.ticks_in_array
+        };
+        let mut start_index = start_index + step;
+        while (min..=max).contains(&start_index) {
+            if self.is_initialized(start_index)? {
+                return Ok(Some(start_index));
+            }
+            start_index += step;
+        }
+        Ok(None)
+    }
+}
+
+/// Next initialized tick of the tick array from `tick_current`, the current
+/// tick included when swapping token 0 for token 1.
+fn next_initialized_tick(
+    tick_array: &TickArrayState,
+    tick_current: i32,
+    tick_spacing: u16,
+    zero_for_one: bool,
+) -> Option<&TickState> {
+    if get_tick_array_start_index(tick_current, tick_spacing) != tick_array.start_tick_index {
+        return None;
+    }
+    let offset = ((tick_current - tick_array.start_tick_index) / i32::from(tick_spacing)) as usize;
+    if zero_for_one {
+        tick_array.ticks[..=offset]
+            .iter()
+            .rev()
+            .find(|tick| tick.liquidity_gross != 0)
+    } else {
+        tick_array.ticks[offset + 1..]
+            .iter()
+            .find(|tick| tick.liquidity_gross != 0)
+    }
+}
+
+fn first_initialized_tick(tick_array: &TickArrayState, zero_for_one: bool) -> Option<&TickState> {
+    if zero_for_one {
+        tick_array
+            .ticks
+            .iter()
+            .rev()
+            .find(|tick| tick.liquidity_gross != 0)
+    } else {
+        tick_array
+            .ticks
+            .iter()
+            .find(|tick| tick.liquidity_gross != 0)
+    }
+}
+
+/// Simulates a swap through the pool `pool_id`, walking its decoded tick arrays.
+///
+/// `amount` is the input amount if `is_base_input`, the output amount otherwise.
+/// A `sqrt_price_limit_x64` of 0 means no limit. `tick_arrays` holds the
+/// decoded tick arrays around the current tick, in any order; the error is
+/// `NotEnoughAccountKeys` when the swap needs one which is not given, or the
+/// bitmap extension.
+pub fn simulate_swap(
+    pool_id: &Pubkey,
+    pool: &PoolState,
+    amm_config: &AmmConfig,
+    tick_arrays: &[TickArrayState],
+    bitmap_extension: Option<&TickArrayBitmapExtension>,
+    amount: u64,
+    is_base_input: bool,
+    zero_for_one: bool,
+    sqrt_price_limit_x64: u128,
+) -> Result<SwapSimulation, ProgramError> {
+    if amount == 0 || pool.tick_spacing == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let sqrt_price_limit_x64 = match sqrt_price_limit_x64 {
+        0 if zero_for_one => MIN_SQRT_PRICE_X64 + 1,
+        0 => MAX_SQRT_PRICE_X64 - 1,
+        limit => limit,
+    };
+    let valid_limit = if zero_for_one {
+        sqrt_price_limit_x64 < pool.sqrt_price_x64 && sqrt_price_limit_x64 > MIN_SQRT_PRICE_X64
+    } else {
+        sqrt_price_limit_x64 > pool.sqrt_price_x64 && sqrt_price_limit_x64 < MAX_SQRT_PRICE_X64
+    };
+    if !valid_limit {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let tick_spacing = pool.tick_spacing;
+    let mut bitmap = TickArrayBitmap {
+        pool,
+        extension: bitmap_extension,
+        ticks_in_array: TICK_ARRAY_SIZE as i32 * i32::from(tick_spacing),
+        uses_extension: false,
+    };
+    let find_tick_array = |start_index: i32| -> Result<&TickArrayState, ProgramError> {
+        let tick_array = tick_arrays
+            .iter()
+            .find(|tick_array| tick_array.start_tick_index == start_index)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        if tick_array.pool_id != *pool_id {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(tick_array)
+    };
+
+    let current_start_index = get_tick_array_start_index(pool.tick_current, tick_spacing);
+    let (mut is_match_pool_current_tick_array, mut current_start_index) =
+        if bitmap.is_initialized(current_start_index)? {
+            (true, current_start_index)
+        } else {
+            let start_index = bitmap
+                .next_initialized(current_start_index, zero_for_one)?
+                .ok_or(ProgramError::InsufficientFunds)?;
+            (false, start_index)
+        };
+    let mut tick_array = find_tick_array(current_start_index)?;
+    let mut tick_array_start_indexes = vec![current_start_index];
+
+    let mut amount_remaining = amount;
+    let mut amount_calculated = 0u64;
+    let mut fee_amount = 0u64;
+    let mut sqrt_price_x64 = pool.sqrt_price_x64;
+    let mut tick_current = pool.tick_current;
+    let mut liquidity = pool.liquidity;
+
+    while amount_remaining != 0 && sqrt_price_x64 != sqrt_price_limit_x64 {
+        let mut next_tick =
+            next_initialized_tick(tick_array, tick_current, tick_spacing, zero_for_one);
+        if next_tick.is_none() && !is_match_pool_current_tick_array {
+            is_match_pool_current_tick_array = true;
+            next_tick = first_initialized_tick(tick_array, zero_for_one);
+        }
+        let next_tick = match next_tick {
+            Some(tick) => *tick,
+            None => {
+                current_start_index = bitmap
+                    .next_initialized(current_start_index, zero_for_one)?
+                    .ok_or(ProgramError::InsufficientFunds)?;
+                tick_array = find_tick_array(current_start_index)?;
+                tick_array_start_indexes.push(current_start_index);
+                *first_initialized_tick(tick_array, zero_for_one)
+                    .ok_or(ProgramError::InvalidAccountData)?
+            }
+        };
+
+        let tick_next = next_tick.tick.clamp(MIN_TICK, MAX_TICK);
+        let sqrt_price_next_x64 = get_sqrt_price_at_tick(tick_next)?;
+        let sqrt_price_target_x64 = if (zero_for_one && sqrt_price_next_x64 < sqrt_price_limit_x64)
+            || (!zero_for_one && sqrt_price_next_x64 > sqrt_price_limit_x64)
+        {
+            sqrt_price_limit_x64
+        } else {
+            sqrt_price_next_x64
+        };
+        let sqrt_price_start_x64 = sqrt_price_x64;
+        let step = compute_swap_step(
+            sqrt_price_start_x64,
+            sqrt_price_target_x64,
+            liquidity,
+            amount_remaining,
+            amm_config.trade_fee_rate,
+            is_base_input,
+            zero_for_one,
+        )?;
+        sqrt_price_x64 = step.sqrt_price_next_x64;
+
+        let (amount_specified, amount_other) = if is_base_input {
+            (step.amount_in + step.fee_amount, step.amount_out)
+        } else {
+            (step.amount_out, step.amount_in + step.fee_amount)
+        };
+        amount_remaining = amount_remaining
+            .checked_sub(amount_specified)
+            .ok_or(ProgramError::InvalidArgument)?;
+        amount_calculated = amount_calculated
+            .checked_add(amount_other)
+            .ok_or(ProgramError::InvalidArgument)?;
+        fee_amount += step.fee_amount;
+
+        if sqrt_price_x64 == sqrt_price_next_x64 {
+            if next_tick.liquidity_gross != 0 {
+                let liquidity_net = if zero_for_one {
+                    -next_tick.liquidity_net
+                } else {
+                    next_tick.liquidity_net
+                };
+                liquidity = liquidity
+                    .checked_add_signed(liquidity_net)
+                    .ok_or(ProgramError::InvalidAccountData)?;
+            }
+            tick_current = if zero_for_one {
+                tick_next - 1
+            } else {
+                tick_next
+            };
+        } else if sqrt_price_x64 != sqrt_price_start_x64 {
+            tick_current = get_tick_at_sqrt_price(sqrt_price_x64)?;
+        }
+    }
+
+    let (amount_in, amount_out) = if is_base_input {
+        (amount - amount_remaining, amount_calculated)
+    } else {
+        (amount_calculated, amount - amount_remaining)
+    };
+    let program_id = clmm_instruction::id();
+    let tick_arrays = tick_array_start_indexes
+        .iter()
+        .map(|start_index| clmm_instruction::get_tick_array(&program_id, pool_id, *start_index))
+        .collect();
+    Ok(SwapSimulation {
+        amount_in,
+        amount_out,
+        fee_amount,
+        sqrt_price_x64,
+        tick_current,
+        liquidity,
+        tick_array_start_indexes,
+        tick_arrays,
+        uses_bitmap_extension: bitmap.uses_extension,
+    })
+}
@@ -0,0 +1,289 @@
+//! State transition types
+//!
+//! The CLMM accounts are anchor zero copy accounts: the data starts with an
+//! 8 bytes discriminator followed by the packed fields of the struct.
+
+use solana_program::pubkey::Pubkey;
+
+/// Number of rewards of a pool.
+pub const REWARD_NUM: usize = 3;
+/// Number of ticks of a tick array.
+pub const TICK_ARRAY_SIZE: usize = 60;
+/// Number of bitmaps of the tick array bitmap extension, on each side.
+pub const EXTENSION_TICKARRAY_BITMAP_SIZE: usize = 14;
+
+pub const AMM_CONFIG_DISCRIMINATOR: [u8; 8] = [218, 244, 33, 104, 203, 203, 43, 111];
+pub const POOL_STATE_DISCRIMINATOR: [u8; 8] = [247, 237, 227, 245, 215, 195, 222, 70];
+pub const PERSONAL_POSITION_STATE_DISCRIMINATOR: [u8; 8] = [70, 111, 150, 126, 230, 15, 25, 117];
+pub const TICK_ARRAY_STATE_DISCRIMINATOR: [u8; 8] = [192, 155, 85, 205, 49, 249, 129, 42];
+pub const TICK_ARRAY_BITMAP_EXTENSION_DISCRIMINATOR: [u8; 8] =
+    [60, 150, 36, 219, 97, 128, 139, 153];
+
+#[cfg_attr(feature = "client", derive(Debug))]
+#[repr(u8)]
+pub enum PoolStatusBitIndex {
+    OpenPositionOrIncreaseLiquidity = 0u8,
+    DecreaseLiquidity = 1u8,
+    CollectFee = 2u8,
+    CollectReward = 3u8,
+    Swap = 4u8,
+}
+
+#[cfg_attr(feature = "client", derive(Debug))]
+#[repr(u8)]
+pub enum RewardState {
+    Uninitialized = 0u8,
+    Initialized = 1u8,
+    Opening = 2u8,
+    Ended = 3u8,
+}
+
+/// Fee tier of the pools.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AmmConfig {
+    /// bump to identify PDA
+    pub bump: u8,
+    /// config index
+    pub index: u16,
+    /// config admin
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub owner: Pubkey,
+    /// protocol share of the trade fee, in hundredths of a bip
+    pub protocol_fee_rate: u32,
+    /// trade fee, in hundredths of a bip
+    pub trade_fee_rate: u32,
+    /// tick spacing of the pools
+    pub tick_spacing: u16,
+    /// fund share of the trade fee, in hundredths of a bip
+    pub fund_fee_rate: u32,
+    pub padding_u32: u32,
+    /// fund fee owner
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub fund_owner: Pubkey,
+    pub padding: [u64; 3],
+}
+
+/// Reward of a pool.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RewardInfo {
+    /// reward state
+    pub reward_state: u8,
+    /// utc timestamps of the reward start
+    pub open_time: u64,
+    /// utc timestamps of the reward end
+    pub end_time: u64,
+    /// utc timestamps of the last reward update
+    pub last_update_time: u64,
+    /// reward per second in Q64.64
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub emissions_per_second_x64: u128,
+    /// reward amount emitted
+    pub reward_total_emissioned: u64,
+    /// reward amount claimed by the positions
+    pub reward_claimed: u64,
+    /// reward mint
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub token_mint: Pubkey,
+    /// reward vault
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub token_vault: Pubkey,
+    /// reward admin
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub authority: Pubkey,
+    /// reward growth per unit of liquidity in Q64.64
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub reward_growth_global_x64: u128,
+}
+
+/// Concentrated liquidity pool.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PoolState {
+    /// bump to identify PDA
+    pub bump: [u8; 1],
+    /// fee tier of the pool
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub amm_config: Pubkey,
+    /// pool creator
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub owner: Pubkey,
+    /// token 0 mint, lower than token 1 mint
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub token_mint_0: Pubkey,
+    /// token 1 mint
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub token_mint_1: Pubkey,
+    /// token 0 vault
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub token_vault_0: Pubkey,
+    /// token 1 vault
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub token_vault_1: Pubkey,
+    /// observation state
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub observation_key: Pubkey,
+    /// token 0 decimals
+    pub mint_decimals_0: u8,
+    /// token 1 decimals
+    pub mint_decimals_1: u8,
+    /// tick spacing of the pool
+    pub tick_spacing: u16,
+    /// liquidity in range
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub liquidity: u128,
+    /// sqrt of the token 0 price in token 1, in Q64.64
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub sqrt_price_x64: u128,
+    /// current tick
+    pub tick_current: i32,
+    pub padding3: u16,
+    pub padding4: u16,
+    /// fee growth per unit of liquidity in Q64.64
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub fee_growth_global_0_x64: u128,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub fee_growth_global_1_x64: u128,
+    /// protocol fees to collect
+    pub protocol_fees_token_0: u64,
+    pub protocol_fees_token_1: u64,
+    /// swap volumes
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub swap_in_amount_token_0: u128,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub swap_out_amount_token_1: u128,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub swap_in_amount_token_1: u128,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub swap_out_amount_token_0: u128,
+    /// disabled operations, see [PoolStatusBitIndex](enum.PoolStatusBitIndex.html)
+    pub status: u8,
+    pub padding: [u8; 7],
+    /// rewards
+    pub reward_infos: [RewardInfo; REWARD_NUM],
+    /// initialized tick arrays around tick 0
+    pub tick_array_bitmap: [u64; 16],
+    /// fees
+    pub total_fees_token_0: u64,
+    pub total_fees_claimed_token_0: u64,
+    pub total_fees_token_1: u64,
+    pub total_fees_claimed_token_1: u64,
+    pub fund_fees_token_0: u64,
+    pub fund_fees_token_1: u64,
+    /// utc timestamps for pool open
+    pub open_time: u64,
+    /// epoch of the last update
+    pub recent_epoch: u64,
+    pub padding1: [u64; 24],
+    pub padding2: [u64; 32],
+}
+
+/// Reward of a position.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PositionRewardInfo {
+    /// reward growth inside the position range at the last update, in Q64.64
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub growth_inside_last_x64: u128,
+    /// reward amount to collect
+    pub reward_amount_owed: u64,
+}
+
+/// Position of a position nft holder.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PersonalPositionState {
+    /// bump to identify PDA
+    pub bump: u8,
+    /// position nft mint
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub nft_mint: Pubkey,
+    /// pool state
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub pool_id: Pubkey,
+    /// lower tick of the position
+    pub tick_lower_index: i32,
+    /// upper tick of the position
+    pub tick_upper_index: i32,
+    /// position liquidity
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub liquidity: u128,
+    /// fee growth inside the position range at the last update, in Q64.64
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub fee_growth_inside_0_last_x64: u128,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub fee_growth_inside_1_last_x64: u128,
+    /// fees to collect
+    pub token_fees_owed_0: u64,
+    pub token_fees_owed_1: u64,
+    /// rewards
+    pub reward_infos: [PositionRewardInfo; REWARD_NUM],
+    /// epoch of the last update
+    pub recent_epoch: u64,
+    pub padding: [u64; 7],
+}
+
+/// Tick of a tick array.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TickState {
+    /// tick index
+    pub tick: i32,
+    /// liquidity added when the price crosses the tick upwards
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub liquidity_net: i128,
+    /// liquidity referencing the tick, 0 when uninitialized
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub liquidity_gross: u128,
+    /// fee growth on the other side of the tick, in Q64.64
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub fee_growth_outside_0_x64: u128,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub fee_growth_outside_1_x64: u128,
+    /// reward growths on the other side of the tick, in Q64.64
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string_array"))]
+    pub reward_growths_outside_x64: [u128; REWARD_NUM],
+    pub padding: [u32; 13],
+}
+
+/// Array of `TICK_ARRAY_SIZE` ticks spaced by the pool tick spacing.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(Debug))]
+#[derive(Clone, Copy)]
+pub struct TickArrayState {
+    /// pool state
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub pool_id: Pubkey,
+    /// index of the first tick
+    pub start_tick_index: i32,
+    /// ticks
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::array"))]
+    pub ticks: [TickState; TICK_ARRAY_SIZE],
+    /// number of initialized ticks
+    pub initialized_tick_count: u8,
+    /// epoch of the last update
+    pub recent_epoch: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::array"))]
+    pub padding: [u8; 107],
+}
+
+/// Initialized tick arrays beyond the pool `tick_array_bitmap`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TickArrayBitmapExtension {
+    /// pool state
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub pool_id: Pubkey,
+    /// bitmaps of the positive tick arrays
+    pub positive_tick_array_bitmap: [[u64; 8]; EXTENSION_TICKARRAY_BITMAP_SIZE],
+    /// bitmaps of the negative tick arrays
+    pub negative_tick_array_bitmap: [[u64; 8]; EXTENSION_TICKARRAY_BITMAP_SIZE],
+}
@@ -0,0 +1,644 @@
+//! Instruction types
+
+#![allow(clippy::too_many_arguments)]
+
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar,
+};
+use std::convert::TryInto;
+
+solana_program::declare_id!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+
+pub const TOKEN_2022_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+pub const MEMO_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+pub const AUTH_SEED: &[u8] = b"vault_and_lp_mint_auth_seed";
+pub const AMM_CONFIG_SEED: &[u8] = b"amm_config";
+pub const POOL_SEED: &[u8] = b"pool";
+pub const POOL_LP_MINT_SEED: &[u8] = b"pool_lp_mint";
+pub const POOL_VAULT_SEED: &[u8] = b"pool_vault";
+pub const OBSERVATION_SEED: &[u8] = b"observation";
+
+/// Anchor discriminators, the first 8 bytes of `sha256("global:<instruction name>")`.
+const INITIALIZE: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+const DEPOSIT: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+const WITHDRAW: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+const SWAP_BASE_INPUT: [u8; 8] = [143, 190, 90, 218, 196, 30, 51, 222];
+const SWAP_BASE_OUTPUT: [u8; 8] = [55, 217, 98, 86, 163, 74, 180, 173];
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct InitializeInstruction {
+    /// init token 0 amount
+    pub init_amount_0: u64,
+    /// init token 1 amount
+    pub init_amount_1: u64,
+    /// utc timestamps for pool open
+    pub open_time: u64,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DepositInstruction {
+    /// lp amount to mint
+    pub lp_token_amount: u64,
+    /// max token 0 amount to deposit
+    pub maximum_token_0_amount: u64,
+    /// max token 1 amount to deposit
+    pub maximum_token_1_amount: u64,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WithdrawInstruction {
+    /// lp amount to burn
+    pub lp_token_amount: u64,
+    /// min token 0 amount to receive
+    pub minimum_token_0_amount: u64,
+    /// min token 1 amount to receive
+    pub minimum_token_1_amount: u64,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SwapBaseInputInstruction {
+    /// input amount
+    pub amount_in: u64,
+    /// min output amount, the swap fails below it
+    pub minimum_amount_out: u64,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SwapBaseOutputInstruction {
+    /// max input amount, the swap fails above it
+    pub max_amount_in: u64,
+    /// output amount
+    pub amount_out: u64,
+}
+
+/// Instructions supported by the CPMM program.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum CpmmInstruction {
+    ///   Initializes a new pool.
+    ///
+    ///   0. `[writable, signer]` creator Account
+    ///   1. `[]` amm config Account
+    ///   2. `[]` $authority derived from `find_program_address(&[AUTH_SEED])`
+    ///   3. `[writable]` New pool state Account to create.
+    ///   4. `[]` token 0 mint Account
+    ///   5. `[]` token 1 mint Account
+    ///   6. `[writable]` lp mint Account to create
+    ///   7. `[writable]` creator token 0 Account
+    ///   8. `[writable]` creator token 1 Account
+    ///   9. `[writable]` creator lp token Account to create
+    ///   10. `[writable]` pool token 0 vault to create
+    ///   11. `[writable]` pool token 1 vault to create
+    ///   12. `[writable]` create pool fee Account
+    ///   13. `[writable]` observation state Account to create
+    ///   14. `[]` Spl Token program id
+    ///   15. `[]` token 0 program id
+    ///   16. `[]` token 1 program id
+    ///   17. `[]` Associated Token program id
+    ///   18. `[]` Sys program id
+    ///   19. `[]` Rent program id
+    Initialize(InitializeInstruction),
+
+    ///   Deposits tokens into the pool for lp.
+    ///
+    ///   0. `[signer]` user wallet Account
+    ///   1. `[]` $authority
+    ///   2. `[writable]` pool state Account
+    ///   3. `[writable]` user lp token Account
+    ///   4. `[writable]` user token 0 Account
+    ///   5. `[writable]` user token 1 Account
+    ///   6. `[writable]` pool token 0 vault
+    ///   7. `[writable]` pool token 1 vault
+    ///   8. `[]` Spl Token program id
+    ///   9. `[]` Token 2022 program id
+    ///   10. `[]` token 0 mint Account
+    ///   11. `[]` token 1 mint Account
+    ///   12. `[writable]` lp mint Account
+    Deposit(DepositInstruction),
+
+    ///   Withdraws tokens from the pool for lp.
+    ///
+    ///   0. `[signer]` user wallet Account
+    ///   1. `[]` $authority
+    ///   2. `[writable]` pool state Account
+    ///   3. `[writable]` user lp token Account
+    ///   4. `[writable]` user token 0 Account
+    ///   5. `[writable]` user token 1 Account
+    ///   6. `[writable]` pool token 0 vault
+    ///   7. `[writable]` pool token 1 vault
+    ///   8. `[]` Spl Token program id
+    ///   9. `[]` Token 2022 program id
+    ///   10. `[]` token 0 mint Account
+    ///   11. `[]` token 1 mint Account
+    ///   12. `[writable]` lp mint Account
+    ///   13. `[]` Memo program id
+    Withdraw(WithdrawInstruction),
+
+    ///   Swaps an exact input amount.
+    ///
+    ///   0. `[signer]` user wallet Account
+    ///   1. `[]` $authority
+    ///   2. `[]` amm config Account
+    ///   3. `[writable]` pool state Account
+    ///   4. `[writable]` user input token Account
+    ///   5. `[writable]` user output token Account
+    ///   6. `[writable]` pool input vault
+    ///   7. `[writable]` pool output vault
+    ///   8. `[]` input token program id
+    ///   9. `[]` output token program id
+    ///   10. `[]` input mint Account
+    ///   11. `[]` output mint Account
+    ///   12. `[writable]` observation state Account
+    SwapBaseInput(SwapBaseInputInstruction),
+
+    ///   Swaps for an exact output amount.
+    ///
+    ///   Accounts as `SwapBaseInput`.
+    SwapBaseOutput(SwapBaseOutputInstruction),
+}
+
+impl CpmmInstruction {
+    /// Unpacks a byte buffer into a [CpmmInstruction](enum.CpmmInstruction.html).
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        if input.len() < 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (discriminator, rest) = input.split_at(8);
+        let (first, rest) = Self::unpack_u64(rest)?;
+        let (second, rest) = Self::unpack_u64(rest)?;
+        Ok(match discriminator.try_into().unwrap() {
+            INITIALIZE => {
+                let (open_time, _rest) = Self::unpack_u64(rest)?;
+                Self::Initialize(InitializeInstruction {
+                    init_amount_0: first,
+                    init_amount_1: second,
+                    open_time,
+                })
+            }
+            DEPOSIT => {
+                let (maximum_token_1_amount, _rest) = Self::unpack_u64(rest)?;
+                Self::Deposit(DepositInstruction {
+                    lp_token_amount: first,
+                    maximum_token_0_amount: second,
+                    maximum_token_1_amount,
+                })
+            }
+            WITHDRAW => {
+                let (minimum_token_1_amount, _rest) = Self::unpack_u64(rest)?;
+                Self::Withdraw(WithdrawInstruction {
+                    lp_token_amount: first,
+                    minimum_token_0_amount: second,
+                    minimum_token_1_amount,
+                })
+            }
+            SWAP_BASE_INPUT => Self::SwapBaseInput(SwapBaseInputInstruction {
+                amount_in: first,
+                minimum_amount_out: second,
+            }),
+            SWAP_BASE_OUTPUT => Self::SwapBaseOutput(SwapBaseOutputInstruction {
+                max_amount_in: first,
+                amount_out: second,
+            }),
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+
+    fn unpack_u64(input: &[u8]) -> Result<(u64, &[u8]), ProgramError> {
+        if input.len() >= 8 {
+            let (amount, rest) = input.split_at(8);
+            let amount = amount
+                .try_into()
+                .map(u64::from_le_bytes)
+                .map_err(|_| ProgramError::InvalidInstructionData)?;
+            Ok((amount, rest))
+        } else {
+            Err(ProgramError::InvalidInstructionData)
+        }
+    }
+
+    /// Packs a [CpmmInstruction](enum.CpmmInstruction.html) into a byte buffer.
+    pub fn pack(&self) -> Result<Vec<u8>, ProgramError> {
+        let (discriminator, args): ([u8; 8], &[u64]) = match self {
+            Self::Initialize(InitializeInstruction {
+                init_amount_0,
+                init_amount_1,
+                open_time,
+            }) => (INITIALIZE, &[*init_amount_0, *init_amount_1, *open_time]),
+            Self::Deposit(DepositInstruction {
+                lp_token_amount,
+                maximum_token_0_amount,
+                maximum_token_1_amount,
+            }) => (
+                DEPOSIT,
+                &[
+                    *lp_token_amount,
+                    *maximum_token_0_amount,
+                    *maximum_token_1_amount,
+                ],
+            ),
+            Self::Withdraw(WithdrawInstruction {
+                lp_token_amount,
+                minimum_token_0_amount,
+                minimum_token_1_amount,
+            }) => (
+                WITHDRAW,
+                &[
+                    *lp_token_amount,
+                    *minimum_token_0_amount,
+                    *minimum_token_1_amount,
+                ],
+            ),
+            Self::SwapBaseInput(SwapBaseInputInstruction {
+                amount_in,
+                minimum_amount_out,
+            }) => (SWAP_BASE_INPUT, &[*amount_in, *minimum_amount_out]),
+            Self::SwapBaseOutput(SwapBaseOutputInstruction {
+                max_amount_in,
+                amount_out,
+            }) => (SWAP_BASE_OUTPUT, &[*max_amount_in, *amount_out]),
+        };
+        let mut buf = Vec::with_capacity(8 + 8 * args.len());
+        buf.extend_from_slice(&discriminator);
+        for arg in args {
+            buf.extend_from_slice(&arg.to_le_bytes());
+        }
+        Ok(buf)
+    }
+}
+
+/// Finds the authority of the pool vaults and lp mints.
+pub fn get_authority(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AUTH_SEED], program_id)
+}
+
+/// Finds the amm config of a fee tier index.
+pub fn get_amm_config(program_id: &Pubkey, index: u16) -> Pubkey {
+    Pubkey::find_program_address(&[AMM_CONFIG_SEED, &index.to_be_bytes()], program_id).0
+}
+
+/// Finds the pool state of an amm config and a mint pair, `token_0_mint` < `token_1_mint`.
+pub fn get_pool_state(
+    program_id: &Pubkey,
+    amm_config: &Pubkey,
+    token_0_mint: &Pubkey,
+    token_1_mint: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            POOL_SEED,
+            amm_config.as_ref(),
+            token_0_mint.as_ref(),
+            token_1_mint.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Finds the pool lp mint.
+pub fn get_lp_mint(program_id: &Pubkey, pool_state: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[POOL_LP_MINT_SEED, pool_state.as_ref()], program_id).0
+}
+
+/// Finds the pool vault of a mint.
+pub fn get_pool_vault(program_id: &Pubkey, pool_state: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[POOL_VAULT_SEED, pool_state.as_ref(), mint.as_ref()],
+        program_id,
+    )
+    .0
+}
+
+/// Finds the pool observation state.
+pub fn get_observation_state(program_id: &Pubkey, pool_state: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[OBSERVATION_SEED, pool_state.as_ref()], program_id).0
+}
+
+/// Creates an 'initialize' instruction.
+pub fn initialize(
+    cpmm_program: &Pubkey,
+    amm_config: &Pubkey,
+    authority: &Pubkey,
+    pool_state: &Pubkey,
+    token_0_mint: &Pubkey,
+    token_1_mint: &Pubkey,
+    lp_mint: &Pubkey,
+    token_0_vault: &Pubkey,
+    token_1_vault: &Pubkey,
+    create_pool_fee: &Pubkey,
+    observation_state: &Pubkey,
+    token_0_program: &Pubkey,
+    token_1_program: &Pubkey,
+    creator: &Pubkey,
+    creator_token_0: &Pubkey,
+    creator_token_1: &Pubkey,
+    creator_lp_token: &Pubkey,
+
+    init_amount_0: u64,
+    init_amount_1: u64,
+    open_time: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = CpmmInstruction::Initialize(InitializeInstruction {
+        init_amount_0,
+        init_amount_1,
+        open_time,
+    })
+    .pack()?;
+
+    let accounts = vec![
+        // user
+        AccountMeta::new(*creator, true),
+        // pool
+        AccountMeta::new_readonly(*amm_config, false),
+        AccountMeta::new_readonly(*authority, false),
+        AccountMeta::new(*pool_state, false),
+        AccountMeta::new_readonly(*token_0_mint, false),
+        AccountMeta::new_readonly(*token_1_mint, false),
+        AccountMeta::new(*lp_mint, false),
+        // user
+        AccountMeta::new(*creator_token_0, false),
+        AccountMeta::new(*creator_token_1, false),
+        AccountMeta::new(*creator_lp_token, false),
+        // pool
+        AccountMeta::new(*token_0_vault, false),
+        AccountMeta::new(*token_1_vault, false),
+        AccountMeta::new(*create_pool_fee, false),
+        AccountMeta::new(*observation_state, false),
+        // programs
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(*token_0_program, false),
+        AccountMeta::new_readonly(*token_1_program, false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    Ok(Instruction {
+        program_id: *cpmm_program,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'deposit' instruction.
+pub fn deposit(
+    cpmm_program: &Pubkey,
+    authority: &Pubkey,
+    pool_state: &Pubkey,
+    token_0_vault: &Pubkey,
+    token_1_vault: &Pubkey,
+    vault_0_mint: &Pubkey,
+    vault_1_mint: &Pubkey,
+    lp_mint: &Pubkey,
+    user_owner: &Pubkey,
+    user_token_0: &Pubkey,
+    user_token_1: &Pubkey,
+    user_lp_token: &Pubkey,
+
+    lp_token_amount: u64,
+    maximum_token_0_amount: u64,
+    maximum_token_1_amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = CpmmInstruction::Deposit(DepositInstruction {
+        lp_token_amount,
+        maximum_token_0_amount,
+        maximum_token_1_amount,
+    })
+    .pack()?;
+
+    let accounts = vec![
+        // user
+        AccountMeta::new_readonly(*user_owner, true),
+        // pool
+        AccountMeta::new_readonly(*authority, false),
+        AccountMeta::new(*pool_state, false),
+        // user
+        AccountMeta::new(*user_lp_token, false),
+        AccountMeta::new(*user_token_0, false),
+        AccountMeta::new(*user_token_1, false),
+        // pool
+        AccountMeta::new(*token_0_vault, false),
+        AccountMeta::new(*token_1_vault, false),
+        // programs
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        // mints
+        AccountMeta::new_readonly(*vault_0_mint, false),
+        AccountMeta::new_readonly(*vault_1_mint, false),
+        AccountMeta::new(*lp_mint, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *cpmm_program,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'withdraw' instruction.
+pub fn withdraw(
+    cpmm_program: &Pubkey,
+    authority: &Pubkey,
+    pool_state: &Pubkey,
+    token_0_vault: &Pubkey,
+    token_1_vault: &Pubkey,
+    vault_0_mint: &Pubkey,
+    vault_1_mint: &Pubkey,
+    lp_mint: &Pubkey,
+    user_owner: &Pubkey,
+    user_token_0: &Pubkey,
+    user_token_1: &Pubkey,
+    user_lp_token: &Pubkey,
+
+    lp_token_amount: u64,
+    minimum_token_0_amount: u64,
+    minimum_token_1_amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = CpmmInstruction::Withdraw(WithdrawInstruction {
+        lp_token_amount,
+        minimum_token_0_amount,
+        minimum_token_1_amount,
+    })
+    .pack()?;
+
+    let accounts = vec![
+        // user
+        AccountMeta::new_readonly(*user_owner, true),
+        // pool
+        AccountMeta::new_readonly(*authority, false),
+        AccountMeta::new(*pool_state, false),
+        // user
+        AccountMeta::new(*user_lp_token, false),
+        AccountMeta::new(*user_token_0, false),
+        AccountMeta::new(*user_token_1, false),
+        // pool
+        AccountMeta::new(*token_0_vault, false),
+        AccountMeta::new(*token_1_vault, false),
+        // programs
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        // mints
+        AccountMeta::new_readonly(*vault_0_mint, false),
+        AccountMeta::new_readonly(*vault_1_mint, false),
+        AccountMeta::new(*lp_mint, false),
+        AccountMeta::new_readonly(MEMO_PROGRAM_ID, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *cpmm_program,
+        accounts,
+        data,
+    })
+}
+
+fn swap_accounts(
+    authority: &Pubkey,
+    amm_config: &Pubkey,
+    pool_state: &Pubkey,
+    input_vault: &Pubkey,
+    output_vault: &Pubkey,
+    input_token_program: &Pubkey,
+    output_token_program: &Pubkey,
+    input_token_mint: &Pubkey,
+    output_token_mint: &Pubkey,
+    observation_state: &Pubkey,
+    user_input_token: &Pubkey,
+    user_output_token: &Pubkey,
+    user_owner: &Pubkey,
+) -> Vec<AccountMeta> {
+    vec![
+        // user
+        AccountMeta::new_readonly(*user_owner, true),
+        // pool
+        AccountMeta::new_readonly(*authority, false),
+        AccountMeta::new_readonly(*amm_config, false),
+        AccountMeta::new(*pool_state, false),
+        // user
+        AccountMeta::new(*user_input_token, false),
+        AccountMeta::new(*user_output_token, false),
+        // pool
+        AccountMeta::new(*input_vault, false),
+        AccountMeta::new(*output_vault, false),
+        // programs
+        AccountMeta::new_readonly(*input_token_program, false),
+        AccountMeta::new_readonly(*output_token_program, false),
+        // mints
+        AccountMeta::new_readonly(*input_token_mint, false),
+        AccountMeta::new_readonly(*output_token_mint, false),
+        AccountMeta::new(*observation_state, false),
+    ]
+}
+
+/// Creates a 'swap base input' instruction.
+pub fn swap_base_input(
+    cpmm_program: &Pubkey,
+    authority: &Pubkey,
+    amm_config: &Pubkey,
+    pool_state: &Pubkey,
+    input_vault: &Pubkey,
+    output_vault: &Pubkey,
+    input_token_program: &Pubkey,
+    output_token_program: &Pubkey,
+    input_token_mint: &Pubkey,
+    output_token_mint: &Pubkey,
+    observation_state: &Pubkey,
+    user_input_token: &Pubkey,
+    user_output_token: &Pubkey,
+    user_owner: &Pubkey,
+
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = CpmmInstruction::SwapBaseInput(SwapBaseInputInstruction {
+        amount_in,
+        minimum_amount_out,
+    })
+    .pack()?;
+
+    let accounts = swap_accounts(
+        authority,
+        amm_config,
+        pool_state,
+        input_vault,
+        output_vault,
+        input_token_program,
+        output_token_program,
+        input_token_mint,
+        output_token_mint,
+        observation_state,
+        user_input_token,
+        user_output_token,
+        user_owner,
+    );
+
+    Ok(Instruction {
+        program_id: *cpmm_program,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'swap base output' instruction.
+pub fn swap_base_output(
+    cpmm_program: &Pubkey,
+    authority: &Pubkey,
+    amm_config: &Pubkey,
+    pool_state: &Pubkey,
+    input_vault: &Pubkey,
+    output_vault: &Pubkey,
+    input_token_program: &Pubkey,
+    output_token_program: &Pubkey,
+    input_token_mint: &Pubkey,
+    output_token_mint: &Pubkey,
+    observation_state: &Pubkey,
+    user_input_token: &Pubkey,
+    user_output_token: &Pubkey,
+    user_owner: &Pubkey,
+
+    max_amount_in: u64,
+    amount_out: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = CpmmInstruction::SwapBaseOutput(SwapBaseOutputInstruction {
+        max_amount_in,
+        amount_out,
+    })
+    .pack()?;
+
+    let accounts = swap_accounts(
+        authority,
+        amm_config,
+        pool_state,
+        input_vault,
+        output_vault,
+        input_token_program,
+        output_token_program,
+        input_token_mint,
+        output_token_mint,
+        observation_state,
+        user_input_token,
+        user_output_token,
+        user_owner,
+    );
+
+    Ok(Instruction {
+        program_id: *cpmm_program,
+        accounts,
+        data,
+    })
+}
@@ -0,0 +1,139 @@
+//! State transition types
+//!
+//! The CPMM accounts are anchor accounts: the data starts with an 8 bytes
+//! discriminator followed by the packed fields of the struct.
+
+use solana_program::pubkey::Pubkey;
+
+/// Number of observations of the observation state.
+pub const OBSERVATION_NUM: usize = 100;
+
+pub const AMM_CONFIG_DISCRIMINATOR: [u8; 8] = [218, 244, 33, 104, 203, 203, 43, 111];
+pub const POOL_STATE_DISCRIMINATOR: [u8; 8] = [247, 237, 227, 245, 215, 195, 222, 70];
+pub const OBSERVATION_STATE_DISCRIMINATOR: [u8; 8] = [122, 174, 197, 53, 129, 9, 165, 132];
+
+#[cfg_attr(feature = "client", derive(Debug))]
+#[repr(u8)]
+pub enum PoolStatusBitIndex {
+    Deposit = 0u8,
+    Withdraw = 1u8,
+    Swap = 2u8,
+}
+
+/// Fee tier of the pools.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AmmConfig {
+    /// bump to identify PDA
+    pub bump: u8,
+    /// pool creation disabled
+    pub disable_create_pool: bool,
+    /// config index
+    pub index: u16,
+    /// trade fee, in hundredths of a bip
+    pub trade_fee_rate: u64,
+    /// protocol share of the trade fee, in hundredths of a bip
+    pub protocol_fee_rate: u64,
+    /// fund share of the trade fee, in hundredths of a bip
+    pub fund_fee_rate: u64,
+    /// fee in lamports to create a pool
+    pub create_pool_fee: u64,
+    /// protocol fee owner
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub protocol_owner: Pubkey,
+    /// fund fee owner
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub fund_owner: Pubkey,
+    pub padding: [u64; 16],
+}
+
+/// Constant product pool.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PoolState {
+    /// fee tier of the pool
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub amm_config: Pubkey,
+    /// pool creator
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub pool_creator: Pubkey,
+    /// token 0 vault
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub token_0_vault: Pubkey,
+    /// token 1 vault
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub token_1_vault: Pubkey,
+    /// lp mint
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub lp_mint: Pubkey,
+    /// token 0 mint, lower than token 1 mint
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub token_0_mint: Pubkey,
+    /// token 1 mint
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub token_1_mint: Pubkey,
+    /// token program of token 0
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub token_0_program: Pubkey,
+    /// token program of token 1
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub token_1_program: Pubkey,
+    /// observation state
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub observation_key: Pubkey,
+    /// bump of the authority
+    pub auth_bump: u8,
+    /// disabled operations, see [PoolStatusBitIndex](enum.PoolStatusBitIndex.html)
+    pub status: u8,
+    pub lp_mint_decimals: u8,
+    pub mint_0_decimals: u8,
+    pub mint_1_decimals: u8,
+    /// lp supply
+    pub lp_supply: u64,
+    /// fees to collect, in the vaults but not in the reserves
+    pub protocol_fees_token_0: u64,
+    pub protocol_fees_token_1: u64,
+    pub fund_fees_token_0: u64,
+    pub fund_fees_token_1: u64,
+    /// utc timestamps for pool open
+    pub open_time: u64,
+    /// epoch of the last update
+    pub recent_epoch: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::array"))]
+    pub padding: [u64; 31],
+}
+
+/// Price observation.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Observation {
+    /// utc timestamps of the observation
+    pub block_timestamp: u64,
+    /// cumulative token 0 price in Q32.32
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub cumulative_token_0_price_x32: u128,
+    /// cumulative token 1 price in Q32.32
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub cumulative_token_1_price_x32: u128,
+}
+
+/// Ring buffer of the pool price observations.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(Debug))]
+#[derive(Clone, Copy)]
+pub struct ObservationState {
+    /// initialized
+    pub initialized: bool,
+    /// index of the last observation
+    pub observation_index: u16,
+    /// pool state
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub pool_id: Pubkey,
+    /// observations
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::array"))]
+    pub observations: [Observation; OBSERVATION_NUM],
+    pub padding: [u64; 4],
+}
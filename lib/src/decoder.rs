@@ -0,0 +1,431 @@
+//! Typed view of the compiled instructions sent to the Raydium programs.
+
+use crate::{
+    amm_instruction, farm_instruction, farm_instruction::FarmPoolInstruction, stable_instruction,
+    staking_instruction, staking_instruction::StakePoolInstruction,
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+/// Instruction data of one of the Raydium programs.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RaydiumInstructionData {
+    /// AMM v4 program instruction.
+    Amm(amm_instruction::AmmInstruction),
+    /// Stable swap program instruction.
+    StableAmm(stable_instruction::AmmInstruction),
+    /// Farm program instruction.
+    Farm(FarmPoolInstruction),
+    /// Staking program instruction.
+    Staking(StakePoolInstruction),
+}
+
+/// Account of an instruction with the role documented by its program.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LabelledAccount {
+    /// role of the account, e.g. "user source token Account"
+    pub role: &'static str,
+    pub pubkey: Pubkey,
+}
+
+/// Decoded Raydium instruction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RaydiumInstruction {
+    pub program_id: Pubkey,
+    pub data: RaydiumInstructionData,
+    /// accounts in the instruction order
+    pub accounts: Vec<LabelledAccount>,
+}
+
+/// Role given to accounts past the documented ones.
+pub const UNKNOWN_ROLE: &str = "unknown Account";
+
+const AMM_INITIALIZE2: &[&str] = &[
+    "Spl Token program id",
+    "Associated Token program id",
+    "Sys program id",
+    "Rent program id",
+    "AMM Account",
+    "AMM authority",
+    "AMM open orders Account",
+    "AMM lp mint Account",
+    "AMM coin mint Account",
+    "AMM pc mint Account",
+    "AMM coin vault Account",
+    "AMM pc vault Account",
+    "AMM target orders Account",
+    "AMM config Account",
+    "AMM create pool fee destination Account",
+    "Market program id",
+    "Market Account",
+    "User wallet Account",
+    "User token coin Account",
+    "User token pc Account",
+    "User destination lp token ATA Account",
+];
+
+const AMM_DEPOSIT: &[&str] = &[
+    "Spl Token program id",
+    "AMM Account",
+    "AMM authority",
+    "AMM open orders Account",
+    "AMM target orders Account",
+    "AMM lp mint Account",
+    "AMM coin vault Account",
+    "AMM pc vault Account",
+    "Market Account",
+    "User coin token Account",
+    "User pc token Account",
+    "User lp token Account",
+    "User wallet Account",
+    "Market event queue Account",
+];
+
+const AMM_WITHDRAW: &[&str] = &[
+    "Spl Token program id",
+    "AMM Account",
+    "AMM authority",
+    "AMM open orders Account",
+    "AMM target orders Account",
+    "AMM lp mint Account",
+    "AMM coin vault Account",
+    "AMM pc vault Account",
+    "Market program id",
+    "Market Account",
+    "Market coin vault Account",
+    "Market pc vault Account",
+    "Market vault signer Account",
+    "User lp token Account",
+    "User token coin Account",
+    "User token pc Account",
+    "User wallet Account",
+    "Market event queue Account",
+    "Market bids Account",
+    "Market asks Account",
+    "Referrer pc Account",
+];
+
+/// Index of the optional target orders in `AMM_SWAP`.
+const AMM_SWAP_TARGET_ORDERS: usize = 4;
+
+const AMM_SWAP: &[&str] = &[
+    "Spl Token program id",
+    "AMM Account",
+    "AMM authority",
+    "AMM open orders Account",
+    "AMM target orders Account",
+    "AMM coin vault Account",
+    "AMM pc vault Account",
+    "Market program id",
+    "Market Account",
+    "Market bids Account",
+    "Market asks Account",
+    "Market event queue Account",
+    "Market coin vault Account",
+    "Market pc vault Account",
+    "Market vault signer Account",
+    "User source token Account",
+    "User destination token Account",
+    "User wallet Account",
+];
+
+const STABLE_INITIALIZE: &[&str] = &[
+    "Spl Token program id",
+    "Sys program id",
+    "Rent program id",
+    "amm Account",
+    "$authority",
+    "amm open_orders Account",
+    "pool lp mint address",
+    "coin mint address",
+    "pc mint address",
+    "pool_token_coin Account",
+    "pool_token_pc Account",
+    "amm target_orders Account",
+    "model data Account",
+    "serum dex program id",
+    "serum market Account",
+    "token_dest_lp Account",
+    "user wallet Account",
+    "srm token Account",
+];
+
+const STABLE_PRE_INITIALIZE: &[&str] = &[
+    "Spl Token program id",
+    "Sys program id",
+    "Rent program id",
+    "amm target_orders Account",
+    "$authority",
+    "pool lp mint address",
+    "coin mint address",
+    "pc mint address",
+    "pool_token_coin Account",
+    "pool_token_pc Account",
+    "serum market Account",
+    "user wallet Account",
+];
+
+const STABLE_DEPOSIT: &[&str] = &[
+    "Spl Token program id",
+    "amm Account",
+    "$authority",
+    "amm open_orders Account",
+    "amm target_orders Account",
+    "pool lp mint address",
+    "pool_token_coin Account",
+    "pool_token_pc Account",
+    "model data Account",
+    "serum market Account",
+    "user coin token Account",
+    "user pc token Account",
+    "user lp token Account",
+    "user owner Account",
+];
+
+/// Index of the optional referrer in `STABLE_WITHDRAW`.
+const STABLE_WITHDRAW_REFERRER: usize = 18;
+
+const STABLE_WITHDRAW: &[&str] = &[
+    "Spl Token program id",
+    "amm Account",
+    "$authority",
+    "amm open_orders Account",
+    "amm target_orders Account",
+    "pool lp mint address",
+    "pool_token_coin Account",
+    "pool_token_pc Account",
+    "model data Account",
+    "serum dex program id",
+    "serum market Account",
+    "coin_vault Account",
+    "pc_vault Account",
+    "vault_signer Account",
+    "user lp token Account",
+    "user token coin Account",
+    "user token pc Account",
+    "user owner Account",
+    "referrer pc Account",
+    "event_q Account",
+    "bids Account",
+    "asks Account",
+];
+
+const STABLE_SWAP: &[&str] = &[
+    "Spl Token program id",
+    "amm Account",
+    "$authority",
+    "amm open_orders Account",
+    "pool_token_coin Account",
+    "pool_token_pc Account",
+    "model data Account",
+    "serum dex program id",
+    "serum market Account",
+    "bids Account",
+    "asks Account",
+    "event_q Account",
+    "coin_vault Account",
+    "pc_vault Account",
+    "vault_signer Account",
+    "user source token Account",
+    "user destination token Account",
+    "user owner Account",
+];
+
+const FARM_INITIALIZE: &[&str] = &[
+    "owner Account",
+    "farm pool Account",
+    "$authority",
+    "pool lp token vault",
+    "pool reward token a vault",
+    "Clock sysvar",
+    "pool reward token b vault",
+    "admin authority Account",
+];
+
+/// `FarmPoolInstruction::DepositV2` roles, the other deposits and withdraws stop at the reward b.
+const FARM_DEPOSIT: &[&str] = &[
+    "farm pool Account",
+    "$authority",
+    "user staker info Account",
+    "user owner Account",
+    "user lp token Account",
+    "pool lp token vault",
+    "user reward token a Account",
+    "pool reward token a vault",
+    "Clock sysvar",
+    "Spl Token program id",
+    "user reward token b Account",
+    "pool reward token b vault",
+    "user staker info v1 Account",
+];
+
+const FARM_UPDATE_POOL: &[&str] = &["farm pool Account", "pool lp token vault", "Clock sysvar"];
+
+const FARM_EMERGENCY_WITHDRAW: &[&str] = &[
+    "farm pool Account",
+    "$authority",
+    "user staker info Account",
+    "user owner Account",
+    "user lp token Account",
+    "pool lp token vault",
+    "Spl Token program id",
+];
+
+const FARM_CREATE_ASSOCIATED_ACCOUNT: &[&str] = &[
+    "farm pool Account",
+    "associated user staker info Account",
+    "user owner Account",
+    "Sys program id",
+    "Rent program id",
+];
+
+const STAKING_INITIALIZE: &[&str] = &[
+    "stake pool Account",
+    "$authority",
+    "pool lp token vault",
+    "pool reward token vault",
+    "Clock sysvar",
+];
+
+/// `StakePoolInstruction::DepositV2` and `WithdrawV2` roles, v1 stops at the token program.
+const STAKING_DEPOSIT: &[&str] = &[
+    "stake pool Account",
+    "$authority",
+    "user staker info Account",
+    "user owner Account",
+    "user lp token Account",
+    "pool lp token vault",
+    "user reward token Account",
+    "pool reward token vault",
+    "Clock sysvar",
+    "Spl Token program id",
+    "user staker info v1 Account",
+];
+
+const STAKING_UPDATE_POOL: &[&str] = &["stake pool Account", "pool lp token vault", "Clock sysvar"];
+
+const STAKING_EMERGENCY_WITHDRAW: &[&str] = &[
+    "stake pool Account",
+    "$authority",
+    "user staker info Account",
+    "user owner Account",
+    "user lp token Account",
+    "pool lp token vault",
+    "Spl Token program id",
+];
+
+const STAKING_CREATE_ASSOCIATED_ACCOUNT: &[&str] = &[
+    "stake pool Account",
+    "associated user staker info Account",
+    "user owner Account",
+    "Sys program id",
+    "Rent program id",
+];
+
+/// Decodes a compiled instruction of the AMM v4, stable swap, farm or staking program.
+///
+/// `accounts` are the instruction account keys, in the instruction order.
+pub fn decode_instruction(
+    program_id: &Pubkey,
+    accounts: &[Pubkey],
+    data: &[u8],
+) -> Result<RaydiumInstruction, ProgramError> {
+    let (data, roles) = if *program_id == amm_instruction::id() {
+        let instruction = amm_instruction::AmmInstruction::unpack(data)?;
+        let roles = amm_roles(&instruction, accounts.len());
+        (RaydiumInstructionData::Amm(instruction), roles)
+    } else if *program_id == stable_instruction::id() {
+        let instruction = stable_instruction::AmmInstruction::unpack(data)?;
+        let roles = stable_roles(&instruction, accounts.len());
+        (RaydiumInstructionData::StableAmm(instruction), roles)
+    } else if *program_id == farm_instruction::id() {
+        let instruction = FarmPoolInstruction::deserialize(data)?;
+        let roles = farm_roles(&instruction);
+        (RaydiumInstructionData::Farm(instruction), roles)
+    } else if *program_id == staking_instruction::id() {
+        let instruction = StakePoolInstruction::deserialize(data)?;
+        let roles = staking_roles(&instruction);
+        (RaydiumInstructionData::Staking(instruction), roles)
+    } else {
+        return Err(ProgramError::IncorrectProgramId);
+    };
+
+    let accounts = accounts
+        .iter()
+        .enumerate()
+        .map(|(i, pubkey)| LabelledAccount {
+            role: roles.get(i).copied().unwrap_or(UNKNOWN_ROLE),
+            pubkey: *pubkey,
+        })
+        .collect();
+    Ok(RaydiumInstruction {
+        program_id: *program_id,
+        data,
+        accounts,
+    })
+}
+
+fn amm_roles(instruction: &amm_instruction::AmmInstruction, len: usize) -> Vec<&'static str> {
+    use amm_instruction::AmmInstruction;
+    match instruction {
+        AmmInstruction::Initialize2(_) => AMM_INITIALIZE2.to_vec(),
+        AmmInstruction::Deposit(_) => AMM_DEPOSIT.to_vec(),
+        AmmInstruction::Withdraw(_) => AMM_WITHDRAW.to_vec(),
+        AmmInstruction::SwapBaseIn(_) | AmmInstruction::SwapBaseOut(_) => {
+            let mut roles = AMM_SWAP.to_vec();
+            if len < AMM_SWAP.len() {
+                roles.remove(AMM_SWAP_TARGET_ORDERS);
+            }
+            roles
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn stable_roles(instruction: &stable_instruction::AmmInstruction, len: usize) -> Vec<&'static str> {
+    use stable_instruction::AmmInstruction;
+    match instruction {
+        AmmInstruction::Initialize(_) => STABLE_INITIALIZE.to_vec(),
+        AmmInstruction::PreInitialize(_) => STABLE_PRE_INITIALIZE.to_vec(),
+        AmmInstruction::Deposit(_) => STABLE_DEPOSIT.to_vec(),
+        AmmInstruction::Withdraw(_) => {
+            let mut roles = STABLE_WITHDRAW.to_vec();
+            // the market accounts come without the referrer
+            if len == STABLE_WITHDRAW.len() - 1 {
+                roles.remove(STABLE_WITHDRAW_REFERRER);
+            }
+            roles
+        }
+        AmmInstruction::SwapBaseIn(_) | AmmInstruction::SwapBaseOut(_) => STABLE_SWAP.to_vec(),
+        _ => Vec::new(),
+    }
+}
+
+fn farm_roles(instruction: &FarmPoolInstruction) -> Vec<&'static str> {
+    match instruction {
+        FarmPoolInstruction::Initialize(_) => FARM_INITIALIZE.to_vec(),
+        FarmPoolInstruction::DepositV2(_) => FARM_DEPOSIT.to_vec(),
+        FarmPoolInstruction::Deposit(_)
+        | FarmPoolInstruction::Withdraw(_)
+        | FarmPoolInstruction::WithdrawV2(_) => FARM_DEPOSIT[..FARM_DEPOSIT.len() - 1].to_vec(),
+        FarmPoolInstruction::UpdatePool => FARM_UPDATE_POOL.to_vec(),
+        FarmPoolInstruction::EmergencyWithdraw => FARM_EMERGENCY_WITHDRAW.to_vec(),
+        FarmPoolInstruction::CreateAssociatedAccount => FARM_CREATE_ASSOCIATED_ACCOUNT.to_vec(),
+        _ => Vec::new(),
+    }
+}
+
+fn staking_roles(instruction: &StakePoolInstruction) -> Vec<&'static str> {
+    match instruction {
+        StakePoolInstruction::Initialize(_) => STAKING_INITIALIZE.to_vec(),
+        StakePoolInstruction::DepositV2(_) | StakePoolInstruction::WithdrawV2(_) => {
+            STAKING_DEPOSIT.to_vec()
+        }
+        StakePoolInstruction::Deposit(_) | StakePoolInstruction::Withdraw(_) => {
+            STAKING_DEPOSIT[..STAKING_DEPOSIT.len() - 1].to_vec()
+        }
+        StakePoolInstruction::UpdatePool => STAKING_UPDATE_POOL.to_vec(),
+        StakePoolInstruction::EmergencyWithdraw => STAKING_EMERGENCY_WITHDRAW.to_vec(),
+        StakePoolInstruction::CreateAssociatedAccount => STAKING_CREATE_ASSOCIATED_ACCOUNT.to_vec(),
+        _ => Vec::new(),
+    }
+}
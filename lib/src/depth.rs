@@ -0,0 +1,181 @@
+//! Liquidity depth of the AMM v4 and stable pools.
+//!
+//! A depth profile quotes a ladder of input amounts against the decoded pool
+//! and reports, for each, the output, the marginal price of the pool after the
+//! swap and the price impact. The prices are in output atoms per input atom,
+//! [PoolPrice](../amount/struct.PoolPrice.html) converts them to ui prices.
+
+use crate::{
+    pool::RaydiumPool,
+    slippage::{self, SwapPriceReport},
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+/// Swap of a depth profile.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DepthPoint {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// swap price, in output tokens per input token
+    pub effective_price: f64,
+    /// pool price before fee after the swap, in output tokens per input token
+    pub marginal_price: f64,
+    /// relative difference of the spot and effective prices, fee included,
+    /// in basis points
+    pub price_impact_bps: f64,
+    /// relative move of the pool price from the spot price to the marginal
+    /// price, in basis points
+    pub price_move_bps: f64,
+}
+
+/// Depth of a pool for swaps from `input_mint`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DepthProfile {
+    pub input_mint: Pubkey,
+    /// pool price before fee, in output tokens per input token
+    pub spot_price: f64,
+    /// swaps by increasing amount in
+    pub points: Vec<DepthPoint>,
+}
+
+/// Pool price before fee at the coin and pc reserves, in output tokens per
+/// input token.
+fn directed_price<P: RaydiumPool + ?Sized>(
+    pool: &P,
+    coin_reserve: u64,
+    pc_reserve: u64,
+    coin_to_pc: bool,
+) -> Option<f64> {
+    let price = pool.marginal_price_at(coin_reserve, pc_reserve)?;
+    Some(if coin_to_pc { price } else { 1.0 / price })
+}
+
+struct DepthQuoter<'a, P: RaydiumPool + ?Sized> {
+    pool: &'a P,
+    input_mint: Pubkey,
+    coin_to_pc: bool,
+    spot_price: f64,
+}
+
+impl<'a, P: RaydiumPool + ?Sized> DepthQuoter<'a, P> {
+    fn new(pool: &'a P, input_mint: &Pubkey) -> Result<Self, ProgramError> {
+        let coin_to_pc = pool.coin_to_pc(input_mint)?;
+        let (coin_reserve, pc_reserve) = pool.reserves();
+        let spot_price = directed_price(pool, coin_reserve, pc_reserve, coin_to_pc)
+            .ok_or(ProgramError::InsufficientFunds)?;
+        Ok(Self {
+            pool,
+            input_mint: *input_mint,
+            coin_to_pc,
+            spot_price,
+        })
+    }
+
+    fn point(&self, amount_in: u64) -> Result<DepthPoint, ProgramError> {
+        let amount_out = self.pool.quote_in(&self.input_mint, amount_in)?;
+        // the whole input, fee included, stays in the pool
+        let (coin_reserve, pc_reserve) = self.pool.reserves();
+        let (coin_reserve, pc_reserve) = if self.coin_to_pc {
+            (
+                coin_reserve.checked_add(amount_in),
+                pc_reserve.checked_sub(amount_out),
+            )
+        } else {
+            (
+                coin_reserve.checked_sub(amount_out),
+                pc_reserve.checked_add(amount_in),
+            )
+        };
+        let marginal_price = coin_reserve
+            .zip(pc_reserve)
+            .and_then(|(coin_reserve, pc_reserve)| {
+                directed_price(self.pool, coin_reserve, pc_reserve, self.coin_to_pc)
+            })
+            .ok_or(ProgramError::InsufficientFunds)?;
+        let report = SwapPriceReport::with_spot_price(self.spot_price, amount_in, amount_out)?;
+        Ok(DepthPoint {
+            amount_in,
+            amount_out,
+            effective_price: report.effective_price,
+            marginal_price,
+            price_impact_bps: report.price_impact_bps,
+            price_move_bps: (1.0 - marginal_price / self.spot_price)
+                * slippage::BPS_DENOMINATOR as f64,
+        })
+    }
+
+    fn price_move_point(&self, price_move_bps: u64) -> Result<DepthPoint, ProgramError> {
+        if price_move_bps == 0 || price_move_bps >= slippage::BPS_DENOMINATOR {
+            return Err(ProgramError::InvalidArgument);
+        }
+        // a failed quote is past the pool depth, so past the move
+        let moved = |amount_in: u64| {
+            self.point(amount_in)
+                .map_or(true, |point| point.price_move_bps >= price_move_bps as f64)
+        };
+        // !moved(low) && moved(high)
+        let (mut low, mut high) = (0u64, 1u64);
+        while !moved(high) {
+            low = high;
+            high = high.checked_mul(2).ok_or(ProgramError::InsufficientFunds)?;
+        }
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if moved(mid) {
+                high = mid;
+            } else {
+                low = mid;
+            }
+        }
+        self.point(high)
+    }
+
+    fn profile(&self, points: Vec<DepthPoint>) -> DepthProfile {
+        DepthProfile {
+            input_mint: self.input_mint,
+            spot_price: self.spot_price,
+            points,
+        }
+    }
+}
+
+/// Quotes the swaps of each of the non-zero `amounts_in` from `input_mint`.
+pub fn depth_profile<P: RaydiumPool + ?Sized>(
+    pool: &P,
+    input_mint: &Pubkey,
+    amounts_in: &[u64],
+) -> Result<DepthProfile, ProgramError> {
+    let quoter = DepthQuoter::new(pool, input_mint)?;
+    let mut points = amounts_in
+        .iter()
+        .map(|amount_in| quoter.point(*amount_in))
+        .collect::<Result<Vec<_>, _>>()?;
+    points.sort_by_key(|point| point.amount_in);
+    Ok(quoter.profile(points))
+}
+
+/// Smallest swap from `input_mint` moving the pool price by `price_move_bps`.
+/// Fails with `InsufficientFunds` if the pool is not deep enough.
+pub fn price_move_point<P: RaydiumPool + ?Sized>(
+    pool: &P,
+    input_mint: &Pubkey,
+    price_move_bps: u64,
+) -> Result<DepthPoint, ProgramError> {
+    DepthQuoter::new(pool, input_mint)?.price_move_point(price_move_bps)
+}
+
+/// Smallest swaps from `input_mint` moving the pool price by each of the
+/// `price_moves_bps`, such as 100, 200 and 500 for 1%, 2% and 5%.
+pub fn price_move_profile<P: RaydiumPool + ?Sized>(
+    pool: &P,
+    input_mint: &Pubkey,
+    price_moves_bps: &[u64],
+) -> Result<DepthProfile, ProgramError> {
+    let quoter = DepthQuoter::new(pool, input_mint)?;
+    let mut points = price_moves_bps
+        .iter()
+        .map(|price_move_bps| quoter.price_move_point(*price_move_bps))
+        .collect::<Result<Vec<_>, _>>()?;
+    points.sort_by_key(|point| point.amount_in);
+    Ok(quoter.profile(points))
+}
@@ -8,9 +8,13 @@ use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
 use solana_program::sysvar;
 
+use std::convert::TryInto;
 use std::mem::size_of;
 
+solana_program::declare_id!("CBuCnLe26faBpcBP2fktp4rp8abpcAnTWft6ZrP5Q4T");
+
 /// Inital values for the Stake Pool
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct InitArgs {
@@ -22,20 +26,71 @@ pub struct InitArgs {
     pub reward_per_slot_b: u64,
 }
 
+/// Reward b accounts of the farms with two rewards
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RewardBAccounts {
+    /// user reward token b account
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub dest_reward_token: Pubkey,
+    /// pool reward token b vault
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub vault_reward_token: Pubkey,
+}
+
 /// Instructions supported by the FarmPool program.
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq)]
 pub enum FarmPoolInstruction {
     ///   Initializes a new StakePool.
+    ///
+    ///   0. `[signer]` owner Account
+    ///   1. `[writable, signer]` New farm pool Account to create.
+    ///   2. `[]` $authority derived from `create_program_address(&[farm pool Account, &[nonce]])`
+    ///   3. `[]` pool lp token vault
+    ///   4. `[]` pool reward token a vault
+    ///   5. `[]` Clock sysvar
+    ///   6. `[]` (optional) pool reward token b vault
+    ///   7. `[]` (optional) admin authority Account
     Initialize(InitArgs),
 
     /// Deposit lp into the pool.
+    ///
+    ///   0. `[writable]` farm pool Account
+    ///   1. `[]` $authority derived from `create_program_address(&[farm pool Account, &[nonce]])`
+    ///   2. `[writable]` user staker info Account
+    ///   3. `[signer]` user owner Account
+    ///   4. `[writable]` user lp token Account to deposit from
+    ///   5. `[writable]` pool lp token vault
+    ///   6. `[writable]` user reward token a Account
+    ///   7. `[writable]` pool reward token a vault
+    ///   8. `[]` Clock sysvar
+    ///   9. `[]` Spl Token program id
+    ///   10. `[writable]` (optional) user reward token b Account
+    ///   11. `[writable]` (optional) pool reward token b vault
     Deposit(u64),
 
     /// Withdraw lp token from the pool .
+    ///
+    ///   0. `[writable]` farm pool Account
+    ///   1. `[]` $authority derived from `create_program_address(&[farm pool Account, &[nonce]])`
+    ///   2. `[writable]` user staker info Account
+    ///   3. `[signer]` user owner Account
+    ///   4. `[writable]` user lp token Account to withdraw to
+    ///   5. `[writable]` pool lp token vault
+    ///   6. `[writable]` user reward token a Account
+    ///   7. `[writable]` pool reward token a vault
+    ///   8. `[]` Clock sysvar
+    ///   9. `[]` Spl Token program id
+    ///   10. `[writable]` (optional) user reward token b Account
+    ///   11. `[writable]` (optional) pool reward token b vault
     Withdraw(u64),
 
     /// Update Pool
+    ///
+    ///   0. `[writable]` farm pool Account
+    ///   1. `[writable]` pool lp token vault
+    ///   2. `[]` Clock sysvar
     UpdatePool,
 
     /// Reserved
@@ -48,6 +103,14 @@ pub enum FarmPoolInstruction {
     Reserved2,
 
     /// EmergencyWithdraw
+    ///
+    ///   0. `[writable]` farm pool Account
+    ///   1. `[]` $authority derived from `create_program_address(&[farm pool Account, &[nonce]])`
+    ///   2. `[writable]` user staker info Account
+    ///   3. `[signer]` user owner Account
+    ///   4. `[writable]` user lp token Account to withdraw to
+    ///   5. `[writable]` pool lp token vault
+    ///   6. `[]` Spl Token program id
     EmergencyWithdraw,
 
     /// Reserved3
@@ -57,103 +120,144 @@ pub enum FarmPoolInstruction {
     Reserved4,
 
     /// CreateAssociatedAccount
+    ///
+    ///   0. `[writable]` farm pool Account
+    ///   1. `[writable]` associated user staker info Account to create.
+    ///   2. `[signer]` user owner Account
+    ///   3. `[]` Sys program id
+    ///   4. `[]` Rent program id
     CreateAssociatedAccount,
 
     /// Deposit lp into the pool V2.
+    ///
+    ///   0. `[writable]` farm pool Account
+    ///   1. `[]` $authority derived from `create_program_address(&[farm pool Account, &[nonce]])`
+    ///   2. `[writable]` user staker info Account
+    ///   3. `[signer]` user owner Account
+    ///   4. `[writable]` user lp token Account to deposit from
+    ///   5. `[writable]` pool lp token vault
+    ///   6. `[writable]` user reward token a Account
+    ///   7. `[writable]` pool reward token a vault
+    ///   8. `[]` Clock sysvar
+    ///   9. `[]` Spl Token program id
+    ///   10. `[writable]` user reward token b Account
+    ///   11. `[writable]` pool reward token b vault
+    ///   12. `[writable, signer]` (optional) user staker info v1 Account to migrate
     DepositV2(u64),
 
     /// ithdraw lp token from the pool V2.
+    ///
+    ///   0. `[writable]` farm pool Account
+    ///   1. `[]` $authority derived from `create_program_address(&[farm pool Account, &[nonce]])`
+    ///   2. `[writable]` user staker info Account
+    ///   3. `[signer]` user owner Account
+    ///   4. `[writable]` user lp token Account to withdraw to
+    ///   5. `[writable]` pool lp token vault
+    ///   6. `[writable]` user reward token a Account
+    ///   7. `[writable]` pool reward token a vault
+    ///   8. `[]` Clock sysvar
+    ///   9. `[]` Spl Token program id
+    ///   10. `[writable]` (optional) user reward token b Account
+    ///   11. `[writable]` (optional) pool reward token b vault
     WithdrawV2(u64),
 }
 
 impl FarmPoolInstruction {
     /// Deserializes a byte buffer into an [FarmPoolInstruction](enum.FarmPoolInstruction.html).
     pub fn deserialize(input: &[u8]) -> Result<Self, ProgramError> {
-        if input.len() < size_of::<u8>() {
-            return Err(ProgramError::InvalidAccountData);
-        }
-        Ok(match input[0] {
+        let (&tag, rest) = input
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        Ok(match tag {
             0 => {
-                let val: &InitArgs = unpack(input)?;
-                Self::Initialize(*val)
+                let (nonce, rest) = Self::unpack_u64(rest)?;
+                let (reward_per_slot_a, rest) = Self::unpack_u64(rest)?;
+                let (reward_per_slot_b, _rest) = Self::unpack_u64(rest)?;
+                Self::Initialize(InitArgs {
+                    nonce,
+                    reward_per_slot_a,
+                    reward_per_slot_b,
+                })
             }
             1 => {
-                let val: &u64 = unpack(input)?;
-                Self::Deposit(*val)
+                let (amount, _rest) = Self::unpack_u64(rest)?;
+                Self::Deposit(amount)
             }
             2 => {
-                let val: &u64 = unpack(input)?;
-                Self::Withdraw(*val)
+                let (amount, _rest) = Self::unpack_u64(rest)?;
+                Self::Withdraw(amount)
             }
             3 => Self::UpdatePool,
-
             7 => Self::EmergencyWithdraw,
-
             10 => Self::CreateAssociatedAccount,
             11 => {
-                let val: &u64 = unpack(input)?;
-                Self::DepositV2(*val)
+                let (amount, _rest) = Self::unpack_u64(rest)?;
+                Self::DepositV2(amount)
             }
             12 => {
-                let val: &u64 = unpack(input)?;
-                Self::WithdrawV2(*val)
+                let (amount, _rest) = Self::unpack_u64(rest)?;
+                Self::WithdrawV2(amount)
             }
-
-            _ => return Err(ProgramError::InvalidAccountData),
+            _ => return Err(ProgramError::InvalidInstructionData),
         })
     }
 
+    fn unpack_u64(input: &[u8]) -> Result<(u64, &[u8]), ProgramError> {
+        if input.len() >= 8 {
+            let (amount, rest) = input.split_at(8);
+            let amount = amount
+                .try_into()
+                .map(u64::from_le_bytes)
+                .map_err(|_| ProgramError::InvalidInstructionData)?;
+            Ok((amount, rest))
+        } else {
+            Err(ProgramError::InvalidInstructionData)
+        }
+    }
+
     /// Serializes an [FarmPoolInstruction](enum.FarmPoolInstruction.html) into a byte buffer.
     pub fn serialize(&self) -> Result<Vec<u8>, ProgramError> {
-        let mut output = vec![0u8; size_of::<FarmPoolInstruction>()];
+        let mut buf = Vec::with_capacity(size_of::<Self>());
         match self {
-            Self::Initialize(init) => {
-                output[0] = 0;
-                #[allow(clippy::cast_ptr_alignment)]
-                let value = unsafe { &mut *(&mut output[1] as *mut u8 as *mut InitArgs) };
-                *value = *init;
-            }
-            Self::Deposit(val) => {
-                output[0] = 1;
-                #[allow(clippy::cast_ptr_alignment)]
-                let value = unsafe { &mut *(&mut output[1] as *mut u8 as *mut u64) };
-                *value = *val;
-            }
-            Self::Withdraw(val) => {
-                output[0] = 2;
-                #[allow(clippy::cast_ptr_alignment)]
-                let value = unsafe { &mut *(&mut output[1] as *mut u8 as *mut u64) };
-                *value = *val;
+            Self::Initialize(InitArgs {
+                nonce,
+                reward_per_slot_a,
+                reward_per_slot_b,
+            }) => {
+                buf.push(0);
+                buf.extend_from_slice(&nonce.to_le_bytes());
+                buf.extend_from_slice(&reward_per_slot_a.to_le_bytes());
+                buf.extend_from_slice(&reward_per_slot_b.to_le_bytes());
             }
-            Self::UpdatePool => {
-                output[0] = 3;
+            Self::Deposit(amount) => {
+                buf.push(1);
+                buf.extend_from_slice(&amount.to_le_bytes());
             }
-
-            Self::EmergencyWithdraw => {
-                output[0] = 7;
-            }
-
-            Self::CreateAssociatedAccount => {
-                output[0] = 10;
+            Self::Withdraw(amount) => {
+                buf.push(2);
+                buf.extend_from_slice(&amount.to_le_bytes());
             }
-            Self::DepositV2(val) => {
-                output[0] = 11;
-                let value = unsafe { &mut *(&mut output[1] as *mut u8 as *mut u64) };
-                *value = *val;
+            Self::UpdatePool => buf.push(3),
+            Self::EmergencyWithdraw => buf.push(7),
+            Self::CreateAssociatedAccount => buf.push(10),
+            Self::DepositV2(amount) => {
+                buf.push(11);
+                buf.extend_from_slice(&amount.to_le_bytes());
             }
-            Self::WithdrawV2(val) => {
-                output[0] = 12;
-                let value = unsafe { &mut *(&mut output[1] as *mut u8 as *mut u64) };
-                *value = *val;
+            Self::WithdrawV2(amount) => {
+                buf.push(12);
+                buf.extend_from_slice(&amount.to_le_bytes());
             }
-
-            _ => return Err(ProgramError::InvalidAccountData),
+            _ => return Err(ProgramError::InvalidInstructionData),
         }
-        Ok(output)
+        Ok(buf)
     }
 }
 
 /// Unpacks a reference from a bytes buffer.
+#[deprecated(
+    note = "casts unaligned instruction data, use `FarmPoolInstruction::deserialize` instead"
+)]
 pub fn unpack<T>(input: &[u8]) -> Result<&T, ProgramError> {
     if input.len() < size_of::<u8>() + size_of::<T>() {
         return Err(ProgramError::InvalidAccountData);
@@ -209,8 +313,7 @@ pub fn deposit(
     vault_lp_token: &Pubkey,
     dest_reward_token_a: &Pubkey,
     vault_reward_token_a: &Pubkey,
-    dest_reward_token_b: Option<&Pubkey>,
-    vault_reward_token_b: Option<&Pubkey>,
+    reward_b: Option<RewardBAccounts>,
     token_program_id: &Pubkey,
     amount: u64,
 ) -> Result<Instruction, ProgramError> {
@@ -228,9 +331,9 @@ pub fn deposit(
         AccountMeta::new_readonly(sysvar::clock::id(), false),
         AccountMeta::new_readonly(*token_program_id, false),
     ];
-    if let Some(dest_reward_token_b) = dest_reward_token_b {
-        accounts.push(AccountMeta::new(*dest_reward_token_b, false));
-        accounts.push(AccountMeta::new(*vault_reward_token_b.unwrap(), false));
+    if let Some(reward_b) = reward_b {
+        accounts.push(AccountMeta::new(reward_b.dest_reward_token, false));
+        accounts.push(AccountMeta::new(reward_b.vault_reward_token, false));
     }
     Ok(Instruction {
         program_id: *program_id,
@@ -293,8 +396,7 @@ pub fn withdraw(
     vault_lp_token: &Pubkey,
     dest_reward_token_a: &Pubkey,
     vault_reward_token_a: &Pubkey,
-    dest_reward_token_b: Option<&Pubkey>,
-    vault_reward_token_b: Option<&Pubkey>,
+    reward_b: Option<RewardBAccounts>,
     token_program_id: &Pubkey,
     amount: u64,
 ) -> Result<Instruction, ProgramError> {
@@ -312,9 +414,9 @@ pub fn withdraw(
         AccountMeta::new_readonly(sysvar::clock::id(), false),
         AccountMeta::new_readonly(*token_program_id, false),
     ];
-    if let Some(dest_reward_token_b) = dest_reward_token_b {
-        accounts.push(AccountMeta::new(*dest_reward_token_b, false));
-        accounts.push(AccountMeta::new(*vault_reward_token_b.unwrap(), false));
+    if let Some(reward_b) = reward_b {
+        accounts.push(AccountMeta::new(reward_b.dest_reward_token, false));
+        accounts.push(AccountMeta::new(reward_b.vault_reward_token, false));
     }
     Ok(Instruction {
         program_id: *program_id,
@@ -334,8 +436,7 @@ pub fn withdraw_v2(
     vault_lp_token: &Pubkey,
     dest_reward_token_a: &Pubkey,
     vault_reward_token_a: &Pubkey,
-    dest_reward_token_b: Option<&Pubkey>,
-    vault_reward_token_b: Option<&Pubkey>,
+    reward_b: Option<RewardBAccounts>,
     token_program_id: &Pubkey,
     amount: u64,
 ) -> Result<Instruction, ProgramError> {
@@ -353,9 +454,9 @@ pub fn withdraw_v2(
         AccountMeta::new_readonly(sysvar::clock::id(), false),
         AccountMeta::new_readonly(*token_program_id, false),
     ];
-    if let Some(dest_reward_token_b) = dest_reward_token_b {
-        accounts.push(AccountMeta::new(*dest_reward_token_b, false));
-        accounts.push(AccountMeta::new(*vault_reward_token_b.unwrap(), false));
+    if let Some(reward_b) = reward_b {
+        accounts.push(AccountMeta::new(reward_b.dest_reward_token, false));
+        accounts.push(AccountMeta::new(reward_b.vault_reward_token, false));
     }
     Ok(Instruction {
         program_id: *program_id,
@@ -3,6 +3,7 @@
 use solana_program::pubkey::Pubkey;
 
 /// Initialized program details.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct FarmPool {
@@ -11,21 +12,26 @@ pub struct FarmPool {
     /// Nonce used in program address.
     pub nonce: u64,
     /// lp token vault
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub lp_vault: Pubkey,
     /// reward vault a
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub reward_vault_a: Pubkey,
     /// reward total a
     pub reward_total_a: u64,
     /// acc ray per share
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub acc_ray_per_share_a: u128,
     /// reward per slot
     pub reward_per_slot_a: u64,
 
     /// reward vault b
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::option_string"))]
     pub reward_vault_b: Option<Pubkey>,
     /// reward total b
     pub reward_total_b: u64,
     /// acc ray per share
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub acc_ray_per_share_b: u128,
     /// reward per slot
     pub reward_per_slot_b: u64,
@@ -33,10 +39,12 @@ pub struct FarmPool {
     /// Last slot pool updated
     pub last_pool_update_slot: u64,
     /// owner
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub owner: Pubkey,
 }
 
 /// Information about the singe  stake account
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct FarmerInfo {
@@ -44,9 +52,11 @@ pub struct FarmerInfo {
     pub state: u64,
 
     /// stake pool account
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub farm_pool: Pubkey,
 
     /// spl token owner account and havrest owner pubkey
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub farmer: Pubkey,
 
     /// staking balance in lp
@@ -60,6 +70,7 @@ pub struct FarmerInfo {
 }
 
 /// Information about the singe  stake account
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct FarmerInfoV2 {
@@ -67,18 +78,22 @@ pub struct FarmerInfoV2 {
     pub state: u64,
 
     /// stake pool account
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub farm_pool: Pubkey,
 
     /// spl token owner account and havrest owner pubkey
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub farmer: Pubkey,
 
     /// staking balance in lp
     pub deposit_balance: u64,
 
     /// reward debt a
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub reward_debt_a: u128,
 
     /// reward debt b
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub reward_debt_b: u128,
 
     /// account type
@@ -0,0 +1,550 @@
+//! Instruction types
+
+#![allow(clippy::too_many_arguments)]
+
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar,
+};
+use std::convert::TryInto;
+use std::mem::size_of;
+
+solana_program::declare_id!("FarmqiPv5eAj3j1GMdMCMUGXqPUvmquZtMy86QH6rzhG");
+
+/// Maximum number of rewards of a farm.
+pub const MAX_REWARD_COUNT: usize = 5;
+
+pub const FARMER_INFO_ASSOCIATED_SEED: &[u8] = b"farmer_info_associated_seed";
+pub const LP_VAULT_ASSOCIATED_SEED: &[u8] = b"lp_vault_associated_seed";
+pub const REWARD_VAULT_ASSOCIATED_SEED: &[u8] = b"reward_vault_associated_seed";
+
+/// Reward settings of a farm.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RewardInfoArgs {
+    /// 1 if the reward is set, 0 for the unused slots
+    pub is_set: u64,
+    /// reward per second
+    pub reward_per_second: u64,
+    /// utc timestamps of the reward start
+    pub reward_open_time: u64,
+    /// utc timestamps of the reward end
+    pub reward_end_time: u64,
+    /// see [RewardType](../farm_v6_stats/enum.RewardType.html)
+    pub reward_type: u64,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CreateFarmInstruction {
+    /// nonce used to create valid program address
+    pub nonce: u64,
+    /// rewards of the farm, the unused slots have `is_set` 0
+    pub reward_infos: [RewardInfoArgs; MAX_REWARD_COUNT],
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RestartRewardInstruction {
+    /// utc timestamps of the reward restart
+    pub reward_reopen_time: u64,
+    /// utc timestamps of the reward end
+    pub reward_end_time: u64,
+    /// reward per second
+    pub reward_per_second: u64,
+}
+
+/// Instructions supported by the farm v6 program.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum FarmV6Instruction {
+    ///   Creates a new farm.
+    ///
+    ///   0. `[]` Spl Token program id
+    ///   1. `[]` Sys program id
+    ///   2. `[]` Rent program id
+    ///   3. `[writable]` New farm Account to create.
+    ///   4. `[]` $authority derived from `create_program_address(&[farm Account, &[nonce]])`
+    ///   5. `[writable]` farm lp vault, derived from `find_program_address(&[farm Account, lp mint, LP_VAULT_ASSOCIATED_SEED])`
+    ///   6. `[]` lp mint Account
+    ///   7. `[writable]` lock vault Account
+    ///   8. `[]` lock mint Account
+    ///   9. `[writable]` user lock token Account
+    ///   10. `[signer]` user wallet Account
+    ///   11. `[]` reward mint Account, repeated with 12 and 13 for each reward
+    ///   12. `[writable]` user reward token Account
+    ///   13. `[writable]` farm reward vault, derived from `find_program_address(&[farm Account, reward mint, REWARD_VAULT_ASSOCIATED_SEED])`
+    CreateFarm(CreateFarmInstruction),
+
+    ///   Deposits lp into the farm, and harvests the pending rewards.
+    ///
+    ///   0. `[]` Spl Token program id
+    ///   1. `[]` Sys program id
+    ///   2. `[writable]` farm Account
+    ///   3. `[]` $authority
+    ///   4. `[writable]` farm lp vault
+    ///   5. `[writable]` user ledger Account, derived from `find_program_address(&[farm Account, user wallet, FARMER_INFO_ASSOCIATED_SEED])`
+    ///   6. `[signer]` user wallet Account
+    ///   7. `[writable]` user lp token Account
+    ///   8. `[writable]` farm reward vault, repeated with 9 for each reward
+    ///   9. `[writable]` user reward token Account
+    Deposit(u64),
+
+    ///   Withdraws lp from the farm, and harvests the pending rewards.
+    ///   A withdraw of 0 only harvests.
+    ///
+    ///   0. `[]` Spl Token program id
+    ///   1. `[writable]` farm Account
+    ///   2. `[]` $authority
+    ///   3. `[writable]` farm lp vault
+    ///   4. `[writable]` user ledger Account
+    ///   5. `[signer]` user wallet Account
+    ///   6. `[writable]` user lp token Account
+    ///   7. `[writable]` farm reward vault, repeated with 8 for each reward
+    ///   8. `[writable]` user reward token Account
+    Withdraw(u64),
+
+    ///   Restarts an ended reward of the farm.
+    ///
+    ///   0. `[]` Spl Token program id
+    ///   1. `[writable]` farm Account
+    ///   2. `[]` farm lp vault
+    ///   3. `[writable]` farm reward vault
+    ///   4. `[writable]` user reward token Account
+    ///   5. `[signer]` farm creator Account
+    RestartReward(RestartRewardInstruction),
+
+    ///   Adds a new reward to the farm.
+    ///
+    ///   0. `[]` Spl Token program id
+    ///   1. `[]` Sys program id
+    ///   2. `[]` Rent program id
+    ///   3. `[writable]` farm Account
+    ///   4. `[]` $authority
+    ///   5. `[]` reward mint Account
+    ///   6. `[writable]` farm reward vault
+    ///   7. `[writable]` user reward token Account
+    ///   8. `[signer]` farm creator Account
+    AddReward(RewardInfoArgs),
+}
+
+impl FarmV6Instruction {
+    /// Unpacks a byte buffer into a [FarmV6Instruction](enum.FarmV6Instruction.html).
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (&tag, rest) = input
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        Ok(match tag {
+            0 => {
+                let (nonce, mut rest) = Self::unpack_u64(rest)?;
+                let mut reward_infos = [RewardInfoArgs::default(); MAX_REWARD_COUNT];
+                for reward_info in reward_infos.iter_mut() {
+                    let (info, next) = Self::unpack_reward_info(rest)?;
+                    *reward_info = info;
+                    rest = next;
+                }
+                Self::CreateFarm(CreateFarmInstruction {
+                    nonce,
+                    reward_infos,
+                })
+            }
+            1 => {
+                let (amount, _rest) = Self::unpack_u64(rest)?;
+                Self::Deposit(amount)
+            }
+            2 => {
+                let (amount, _rest) = Self::unpack_u64(rest)?;
+                Self::Withdraw(amount)
+            }
+            3 => {
+                let (reward_reopen_time, rest) = Self::unpack_u64(rest)?;
+                let (reward_end_time, rest) = Self::unpack_u64(rest)?;
+                let (reward_per_second, _rest) = Self::unpack_u64(rest)?;
+                Self::RestartReward(RestartRewardInstruction {
+                    reward_reopen_time,
+                    reward_end_time,
+                    reward_per_second,
+                })
+            }
+            4 => {
+                let (reward_info, _rest) = Self::unpack_reward_info(rest)?;
+                Self::AddReward(reward_info)
+            }
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+
+    fn unpack_reward_info(input: &[u8]) -> Result<(RewardInfoArgs, &[u8]), ProgramError> {
+        let (is_set, rest) = Self::unpack_u64(input)?;
+        let (reward_per_second, rest) = Self::unpack_u64(rest)?;
+        let (reward_open_time, rest) = Self::unpack_u64(rest)?;
+        let (reward_end_time, rest) = Self::unpack_u64(rest)?;
+        let (reward_type, rest) = Self::unpack_u64(rest)?;
+        Ok((
+            RewardInfoArgs {
+                is_set,
+                reward_per_second,
+                reward_open_time,
+                reward_end_time,
+                reward_type,
+            },
+            rest,
+        ))
+    }
+
+    fn unpack_u64(input: &[u8]) -> Result<(u64, &[u8]), ProgramError> {
+        if input.len() >= 8 {
+            let (amount, rest) = input.split_at(8);
+            let amount = amount
+                .try_into()
+                .map(u64::from_le_bytes)
+                .map_err(|_| ProgramError::InvalidInstructionData)?;
+            Ok((amount, rest))
+        } else {
+            Err(ProgramError::InvalidInstructionData)
+        }
+    }
+
+    /// Packs a [FarmV6Instruction](enum.FarmV6Instruction.html) into a byte buffer.
+    pub fn pack(&self) -> Result<Vec<u8>, ProgramError> {
+        let mut buf = Vec::with_capacity(size_of::<Self>());
+        match self {
+            Self::CreateFarm(CreateFarmInstruction {
+                nonce,
+                reward_infos,
+            }) => {
+                buf.push(0);
+                buf.extend_from_slice(&nonce.to_le_bytes());
+                for reward_info in reward_infos {
+                    Self::pack_reward_info(reward_info, &mut buf);
+                }
+            }
+            Self::Deposit(amount) => {
+                buf.push(1);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            }
+            Self::Withdraw(amount) => {
+                buf.push(2);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            }
+            Self::RestartReward(RestartRewardInstruction {
+                reward_reopen_time,
+                reward_end_time,
+                reward_per_second,
+            }) => {
+                buf.push(3);
+                buf.extend_from_slice(&reward_reopen_time.to_le_bytes());
+                buf.extend_from_slice(&reward_end_time.to_le_bytes());
+                buf.extend_from_slice(&reward_per_second.to_le_bytes());
+            }
+            Self::AddReward(reward_info) => {
+                buf.push(4);
+                Self::pack_reward_info(reward_info, &mut buf);
+            }
+        }
+        Ok(buf)
+    }
+
+    fn pack_reward_info(reward_info: &RewardInfoArgs, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&reward_info.is_set.to_le_bytes());
+        buf.extend_from_slice(&reward_info.reward_per_second.to_le_bytes());
+        buf.extend_from_slice(&reward_info.reward_open_time.to_le_bytes());
+        buf.extend_from_slice(&reward_info.reward_end_time.to_le_bytes());
+        buf.extend_from_slice(&reward_info.reward_type.to_le_bytes());
+    }
+}
+
+/// Accounts of a reward set at the farm creation.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NewRewardAccounts {
+    /// reward mint
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub reward_mint: Pubkey,
+    /// user reward token account funding the reward
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub user_reward_token: Pubkey,
+    /// farm reward vault
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub reward_vault: Pubkey,
+}
+
+/// Accounts of a farm reward for deposit and withdraw.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RewardAccounts {
+    /// farm reward vault
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub reward_vault: Pubkey,
+    /// user reward token account receiving the reward
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub user_reward_token: Pubkey,
+}
+
+/// Finds the farm authority and its nonce.
+pub fn get_authority(program_id: &Pubkey, farm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[farm.as_ref()], program_id)
+}
+
+/// Finds the farm lp vault.
+pub fn get_lp_vault(program_id: &Pubkey, farm: &Pubkey, lp_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[farm.as_ref(), lp_mint.as_ref(), LP_VAULT_ASSOCIATED_SEED],
+        program_id,
+    )
+    .0
+}
+
+/// Finds the farm reward vault of a reward mint.
+pub fn get_reward_vault(program_id: &Pubkey, farm: &Pubkey, reward_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            farm.as_ref(),
+            reward_mint.as_ref(),
+            REWARD_VAULT_ASSOCIATED_SEED,
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Finds the user ledger of a farm.
+pub fn get_user_ledger(program_id: &Pubkey, farm: &Pubkey, owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[farm.as_ref(), owner.as_ref(), FARMER_INFO_ASSOCIATED_SEED],
+        program_id,
+    )
+    .0
+}
+
+/// Creates a 'create farm' instruction.
+pub fn create_farm(
+    program_id: &Pubkey,
+    farm: &Pubkey,
+    authority: &Pubkey,
+    lp_vault: &Pubkey,
+    lp_mint: &Pubkey,
+    lock_vault: &Pubkey,
+    lock_mint: &Pubkey,
+    user_lock_token: &Pubkey,
+    user_wallet: &Pubkey,
+    rewards: &[NewRewardAccounts],
+    nonce: u64,
+    reward_infos: &[RewardInfoArgs],
+) -> Result<Instruction, ProgramError> {
+    if rewards.is_empty() || rewards.len() > MAX_REWARD_COUNT || rewards.len() != reward_infos.len()
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let mut infos = [RewardInfoArgs::default(); MAX_REWARD_COUNT];
+    infos[..reward_infos.len()].copy_from_slice(reward_infos);
+    let data = FarmV6Instruction::CreateFarm(CreateFarmInstruction {
+        nonce,
+        reward_infos: infos,
+    })
+    .pack()?;
+
+    let mut accounts = vec![
+        // spl & sys
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        // farm
+        AccountMeta::new(*farm, false),
+        AccountMeta::new_readonly(*authority, false),
+        AccountMeta::new(*lp_vault, false),
+        AccountMeta::new_readonly(*lp_mint, false),
+        AccountMeta::new(*lock_vault, false),
+        AccountMeta::new_readonly(*lock_mint, false),
+        // user
+        AccountMeta::new(*user_lock_token, false),
+        AccountMeta::new_readonly(*user_wallet, true),
+    ];
+    for reward in rewards {
+        accounts.push(AccountMeta::new_readonly(reward.reward_mint, false));
+        accounts.push(AccountMeta::new(reward.user_reward_token, false));
+        accounts.push(AccountMeta::new(reward.reward_vault, false));
+    }
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an 'add reward' instruction.
+pub fn add_reward(
+    program_id: &Pubkey,
+    farm: &Pubkey,
+    authority: &Pubkey,
+    reward: &NewRewardAccounts,
+    creator: &Pubkey,
+    reward_info: RewardInfoArgs,
+) -> Result<Instruction, ProgramError> {
+    let data = FarmV6Instruction::AddReward(reward_info).pack()?;
+
+    let accounts = vec![
+        // spl & sys
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        // farm
+        AccountMeta::new(*farm, false),
+        AccountMeta::new_readonly(*authority, false),
+        AccountMeta::new_readonly(reward.reward_mint, false),
+        AccountMeta::new(reward.reward_vault, false),
+        // user
+        AccountMeta::new(reward.user_reward_token, false),
+        AccountMeta::new_readonly(*creator, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'restart reward' instruction.
+pub fn restart_reward(
+    program_id: &Pubkey,
+    farm: &Pubkey,
+    lp_vault: &Pubkey,
+    reward: &RewardAccounts,
+    creator: &Pubkey,
+    reward_reopen_time: u64,
+    reward_end_time: u64,
+    reward_per_second: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = FarmV6Instruction::RestartReward(RestartRewardInstruction {
+        reward_reopen_time,
+        reward_end_time,
+        reward_per_second,
+    })
+    .pack()?;
+
+    let accounts = vec![
+        // spl token
+        AccountMeta::new_readonly(spl_token::id(), false),
+        // farm
+        AccountMeta::new(*farm, false),
+        AccountMeta::new_readonly(*lp_vault, false),
+        AccountMeta::new(reward.reward_vault, false),
+        // user
+        AccountMeta::new(reward.user_reward_token, false),
+        AccountMeta::new_readonly(*creator, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'deposit' instruction.
+pub fn deposit(
+    program_id: &Pubkey,
+    farm: &Pubkey,
+    authority: &Pubkey,
+    lp_vault: &Pubkey,
+    user_ledger: &Pubkey,
+    user_wallet: &Pubkey,
+    user_lp_token: &Pubkey,
+    rewards: &[RewardAccounts],
+    amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = FarmV6Instruction::Deposit(amount).pack()?;
+
+    let mut accounts = vec![
+        // spl & sys
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        // farm
+        AccountMeta::new(*farm, false),
+        AccountMeta::new_readonly(*authority, false),
+        AccountMeta::new(*lp_vault, false),
+        // user
+        AccountMeta::new(*user_ledger, false),
+        AccountMeta::new_readonly(*user_wallet, true),
+        AccountMeta::new(*user_lp_token, false),
+    ];
+    for reward in rewards {
+        accounts.push(AccountMeta::new(reward.reward_vault, false));
+        accounts.push(AccountMeta::new(reward.user_reward_token, false));
+    }
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'withdraw' instruction.
+pub fn withdraw(
+    program_id: &Pubkey,
+    farm: &Pubkey,
+    authority: &Pubkey,
+    lp_vault: &Pubkey,
+    user_ledger: &Pubkey,
+    user_wallet: &Pubkey,
+    user_lp_token: &Pubkey,
+    rewards: &[RewardAccounts],
+    amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = FarmV6Instruction::Withdraw(amount).pack()?;
+
+    let mut accounts = vec![
+        // spl token
+        AccountMeta::new_readonly(spl_token::id(), false),
+        // farm
+        AccountMeta::new(*farm, false),
+        AccountMeta::new_readonly(*authority, false),
+        AccountMeta::new(*lp_vault, false),
+        // user
+        AccountMeta::new(*user_ledger, false),
+        AccountMeta::new_readonly(*user_wallet, true),
+        AccountMeta::new(*user_lp_token, false),
+    ];
+    for reward in rewards {
+        accounts.push(AccountMeta::new(reward.reward_vault, false));
+        accounts.push(AccountMeta::new(reward.user_reward_token, false));
+    }
+
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'harvest' instruction, a withdraw of 0 lp.
+pub fn harvest(
+    program_id: &Pubkey,
+    farm: &Pubkey,
+    authority: &Pubkey,
+    lp_vault: &Pubkey,
+    user_ledger: &Pubkey,
+    user_wallet: &Pubkey,
+    user_lp_token: &Pubkey,
+    rewards: &[RewardAccounts],
+) -> Result<Instruction, ProgramError> {
+    withdraw(
+        program_id,
+        farm,
+        authority,
+        lp_vault,
+        user_ledger,
+        user_wallet,
+        user_lp_token,
+        rewards,
+        0,
+    )
+}
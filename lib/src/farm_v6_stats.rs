@@ -0,0 +1,128 @@
+//! State transition types
+
+use crate::farm_v6_instruction::MAX_REWARD_COUNT;
+use solana_program::pubkey::Pubkey;
+
+#[cfg_attr(feature = "client", derive(Debug))]
+#[repr(u64)]
+pub enum RewardType {
+    /// standard spl token reward
+    Standard = 0u64,
+    /// option token reward
+    Option = 1u64,
+}
+
+#[cfg_attr(feature = "client", derive(Debug))]
+#[repr(u64)]
+pub enum RewardState {
+    Uninitialized = 0u64,
+    /// reward emitted between its open and end time
+    Running = 1u64,
+    Ended = 2u64,
+}
+
+/// Reward of a farm.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RewardInfo {
+    /// reward state
+    pub reward_state: u64,
+    /// utc timestamps of the reward start
+    pub reward_open_time: u64,
+    /// utc timestamps of the reward end
+    pub reward_end_time: u64,
+    /// utc timestamps of the last reward update
+    pub reward_last_update_time: u64,
+    /// total reward amount
+    pub total_reward: u64,
+    /// reward amount emitted
+    pub total_reward_emissioned: u64,
+    /// reward amount claimed by the farmers
+    pub reward_claimed: u64,
+    /// reward per second
+    pub reward_per_second: u64,
+    /// acc reward per share
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub acc_reward_per_share: u128,
+    /// reward vault
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub reward_vault: Pubkey,
+    /// reward mint
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub reward_mint: Pubkey,
+    /// account which funded the reward
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub reward_sender: Pubkey,
+    /// reward type
+    pub reward_type: u64,
+    /// padding
+    pub padding: [u64; 15],
+}
+
+/// Farm v6 state.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FarmState {
+    /// reserved
+    pub padding0: u64,
+    /// state
+    pub state: u64,
+    /// Nonce used in program address.
+    pub nonce: u64,
+    /// number of rewards set
+    pub valid_reward_token_num: u64,
+    /// multiplier of the acc reward per share
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub reward_multiplier: u128,
+    /// max reward period, in seconds
+    pub reward_period_max: u64,
+    /// min reward period, in seconds
+    pub reward_period_min: u64,
+    /// period a reward can be extended before its end, in seconds
+    pub reward_period_extend: u64,
+    /// lp mint
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub lp_mint: Pubkey,
+    /// lp vault
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub lp_vault: Pubkey,
+    /// rewards
+    pub reward_infos: [RewardInfo; MAX_REWARD_COUNT],
+    /// farm creator
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub creator: Pubkey,
+    /// reserved
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub padding1: Pubkey,
+    /// padding
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::array"))]
+    pub padding: [u64; 32],
+}
+
+/// Farmer ledger of a farm v6.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FarmerLedger {
+    /// reserved
+    pub padding0: u64,
+    /// state
+    pub state: u64,
+    /// farm account
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub farm: Pubkey,
+    /// owner of the ledger
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub owner: Pubkey,
+    /// staking balance in lp
+    pub deposited: u64,
+    /// reward debts, in the farm rewards order
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string_array"))]
+    pub reward_debts: [u128; MAX_REWARD_COUNT],
+    /// voting locked balance
+    pub vote_locked_balance: u64,
+    /// padding
+    pub padding: [u64; 15],
+}
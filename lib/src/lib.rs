@@ -1,8 +1,50 @@
+mod account_reader;
 pub mod amm_instruction;
+#[cfg(feature = "client")]
+pub mod amm_math;
 pub mod amm_stats;
+#[cfg(feature = "client")]
+pub mod amount;
+#[cfg(feature = "client")]
+pub mod analytics;
+#[cfg(feature = "client")]
+pub mod client;
+pub mod clmm_instruction;
+#[cfg(feature = "client")]
+pub mod clmm_math;
+pub mod clmm_stats;
+pub mod cpmm_instruction;
+pub mod cpmm_stats;
+#[cfg(feature = "client")]
+pub mod decoder;
+#[cfg(feature = "client")]
+pub mod depth;
 pub mod farm_instruction;
 pub mod farm_stats;
+pub mod farm_v6_instruction;
+pub mod farm_v6_stats;
+#[cfg(feature = "client")]
+pub mod log;
+#[cfg(feature = "client")]
+pub mod lp;
+pub mod market_stats;
+#[cfg(feature = "client")]
+pub mod pool;
+pub mod route_instruction;
+#[cfg(feature = "client")]
+pub mod router;
+#[cfg(feature = "serde")]
+pub mod serde_utils;
+#[cfg(feature = "client")]
+pub mod slippage;
 pub mod stable_instruction;
+#[cfg(feature = "client")]
+pub mod stable_math;
 pub mod stable_stats;
 pub mod staking_instruction;
 pub mod staking_stats;
+#[cfg(feature = "client")]
+pub mod transfer_fee;
+pub mod validation;
+#[cfg(feature = "client")]
+pub mod verify;
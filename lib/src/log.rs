@@ -0,0 +1,392 @@
+//! Events emitted by the AMM programs as `ray_log` messages
+//!
+//! Each event is written by the AMM v4 and stable programs as
+//! `Program log: ray_log: <base64>`, the base64 payload being the bincode
+//! encoding of the event, with the [LogType](enum.LogType.html) as first byte.
+
+use crate::{amm_instruction, stable_instruction};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use std::convert::TryInto;
+
+/// Prefix of the event messages.
+pub const RAY_LOG_PREFIX: &str = "Program log: ray_log: ";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LogType {
+    Init,
+    Deposit,
+    Withdraw,
+    SwapBaseIn,
+    SwapBaseOut,
+}
+
+impl LogType {
+    /// Converts the first byte of an event into a [LogType](enum.LogType.html).
+    pub fn from_u8(log_type: u8) -> Option<Self> {
+        match log_type {
+            0 => Some(LogType::Init),
+            1 => Some(LogType::Deposit),
+            2 => Some(LogType::Withdraw),
+            3 => Some(LogType::SwapBaseIn),
+            4 => Some(LogType::SwapBaseOut),
+            _ => None,
+        }
+    }
+
+    pub fn into_u8(&self) -> u8 {
+        match self {
+            LogType::Init => 0,
+            LogType::Deposit => 1,
+            LogType::Withdraw => 2,
+            LogType::SwapBaseIn => 3,
+            LogType::SwapBaseOut => 4,
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct InitLog {
+    pub log_type: u8,
+    /// utc timestamps of the initialization
+    pub time: u64,
+    pub pc_decimals: u8,
+    pub coin_decimals: u8,
+    pub pc_lot_size: u64,
+    pub coin_lot_size: u64,
+    /// init token pc amount
+    pub pc_amount: u64,
+    /// init token coin amount
+    pub coin_amount: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub market: Pubkey,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DepositLog {
+    pub log_type: u8,
+    // input
+    pub max_coin: u64,
+    pub max_pc: u64,
+    pub base: u64,
+    // pool info
+    pub pool_coin: u64,
+    pub pool_pc: u64,
+    pub pool_lp: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub calc_pnl_x: u128,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub calc_pnl_y: u128,
+    // calc result
+    pub deduct_coin: u64,
+    pub deduct_pc: u64,
+    pub mint_lp: u64,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WithdrawLog {
+    pub log_type: u8,
+    // input
+    pub withdraw_lp: u64,
+    // user info
+    pub user_lp: u64,
+    // pool info
+    pub pool_coin: u64,
+    pub pool_pc: u64,
+    pub pool_lp: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub calc_pnl_x: u128,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub calc_pnl_y: u128,
+    // calc result
+    pub out_coin: u64,
+    pub out_pc: u64,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SwapBaseInLog {
+    pub log_type: u8,
+    // input
+    pub amount_in: u64,
+    pub minimum_out: u64,
+    /// 1 for pc to coin, 2 for coin to pc
+    pub direction: u64,
+    // user info
+    pub user_source: u64,
+    // pool info
+    pub pool_coin: u64,
+    pub pool_pc: u64,
+    // calc result
+    pub out_amount: u64,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SwapBaseOutLog {
+    pub log_type: u8,
+    // input
+    pub max_in: u64,
+    pub amount_out: u64,
+    /// 1 for pc to coin, 2 for coin to pc
+    pub direction: u64,
+    // user info
+    pub user_source: u64,
+    // pool info
+    pub pool_coin: u64,
+    pub pool_pc: u64,
+    // calc result
+    pub deduct_in: u64,
+}
+
+/// Event decoded from a `ray_log` message.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RayLog {
+    Init(InitLog),
+    Deposit(DepositLog),
+    Withdraw(WithdrawLog),
+    SwapBaseIn(SwapBaseInLog),
+    SwapBaseOut(SwapBaseOutLog),
+}
+
+impl RayLog {
+    pub fn log_type(&self) -> LogType {
+        match self {
+            RayLog::Init(_) => LogType::Init,
+            RayLog::Deposit(_) => LogType::Deposit,
+            RayLog::Withdraw(_) => LogType::Withdraw,
+            RayLog::SwapBaseIn(_) => LogType::SwapBaseIn,
+            RayLog::SwapBaseOut(_) => LogType::SwapBaseOut,
+        }
+    }
+}
+
+/// Event of a transaction with the program which emitted it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RayLogEvent {
+    pub program_id: Pubkey,
+    /// decoded event, the error of a malformed `ray_log` payload
+    pub log: Result<RayLog, ProgramError>,
+}
+
+/// Decodes the base64 payload of a `ray_log` message.
+pub fn decode_ray_log(log: &str) -> Result<RayLog, ProgramError> {
+    let bytes = STANDARD
+        .decode(log.trim())
+        .map_err(|_| ProgramError::InvalidArgument)?;
+    let (&tag, _) = bytes.split_first().ok_or(ProgramError::InvalidArgument)?;
+    let log_type = LogType::from_u8(tag).ok_or(ProgramError::InvalidArgument)?;
+    let input = bytes.as_slice();
+    Ok(match log_type {
+        LogType::Init => {
+            let (log_type, rest) = unpack_u8(input)?;
+            let (time, rest) = unpack_u64(rest)?;
+            let (pc_decimals, rest) = unpack_u8(rest)?;
+            let (coin_decimals, rest) = unpack_u8(rest)?;
+            let (pc_lot_size, rest) = unpack_u64(rest)?;
+            let (coin_lot_size, rest) = unpack_u64(rest)?;
+            let (pc_amount, rest) = unpack_u64(rest)?;
+            let (coin_amount, rest) = unpack_u64(rest)?;
+            let (market, _rest) = unpack_pubkey(rest)?;
+            RayLog::Init(InitLog {
+                log_type,
+                time,
+                pc_decimals,
+                coin_decimals,
+                pc_lot_size,
+                coin_lot_size,
+                pc_amount,
+                coin_amount,
+                market,
+            })
+        }
+        LogType::Deposit => {
+            let (log_type, rest) = unpack_u8(input)?;
+            let (max_coin, rest) = unpack_u64(rest)?;
+            let (max_pc, rest) = unpack_u64(rest)?;
+            let (base, rest) = unpack_u64(rest)?;
+            let (pool_coin, rest) = unpack_u64(rest)?;
+            let (pool_pc, rest) = unpack_u64(rest)?;
+            let (pool_lp, rest) = unpack_u64(rest)?;
+            let (calc_pnl_x, rest) = unpack_u128(rest)?;
+            let (calc_pnl_y, rest) = unpack_u128(rest)?;
+            let (deduct_coin, rest) = unpack_u64(rest)?;
+            let (deduct_pc, rest) = unpack_u64(rest)?;
+            let (mint_lp, _rest) = unpack_u64(rest)?;
+            RayLog::Deposit(DepositLog {
+                log_type,
+                max_coin,
+                max_pc,
+                base,
+                pool_coin,
+                pool_pc,
+                pool_lp,
+                calc_pnl_x,
+                calc_pnl_y,
+                deduct_coin,
+                deduct_pc,
+                mint_lp,
+            })
+        }
+        LogType::Withdraw => {
+            let (log_type, rest) = unpack_u8(input)?;
+            let (withdraw_lp, rest) = unpack_u64(rest)?;
+            let (user_lp, rest) = unpack_u64(rest)?;
+            let (pool_coin, rest) = unpack_u64(rest)?;
+            let (pool_pc, rest) = unpack_u64(rest)?;
+            let (pool_lp, rest) = unpack_u64(rest)?;
+            let (calc_pnl_x, rest) = unpack_u128(rest)?;
+            let (calc_pnl_y, rest) = unpack_u128(rest)?;
+            let (out_coin, rest) = unpack_u64(rest)?;
+            let (out_pc, _rest) = unpack_u64(rest)?;
+            RayLog::Withdraw(WithdrawLog {
+                log_type,
+                withdraw_lp,
+                user_lp,
+                pool_coin,
+                pool_pc,
+                pool_lp,
+                calc_pnl_x,
+                calc_pnl_y,
+                out_coin,
+                out_pc,
+            })
+        }
+        LogType::SwapBaseIn => {
+            let (log_type, rest) = unpack_u8(input)?;
+            let (amount_in, rest) = unpack_u64(rest)?;
+            let (minimum_out, rest) = unpack_u64(rest)?;
+            let (direction, rest) = unpack_u64(rest)?;
+            let (user_source, rest) = unpack_u64(rest)?;
+            let (pool_coin, rest) = unpack_u64(rest)?;
+            let (pool_pc, rest) = unpack_u64(rest)?;
+            let (out_amount, _rest) = unpack_u64(rest)?;
+            RayLog::SwapBaseIn(SwapBaseInLog {
+                log_type,
+                amount_in,
+                minimum_out,
+                direction,
+                user_source,
+                pool_coin,
+                pool_pc,
+                out_amount,
+            })
+        }
+        LogType::SwapBaseOut => {
+            let (log_type, rest) = unpack_u8(input)?;
+            let (max_in, rest) = unpack_u64(rest)?;
+            let (amount_out, rest) = unpack_u64(rest)?;
+            let (direction, rest) = unpack_u64(rest)?;
+            let (user_source, rest) = unpack_u64(rest)?;
+            let (pool_coin, rest) = unpack_u64(rest)?;
+            let (pool_pc, rest) = unpack_u64(rest)?;
+            let (deduct_in, _rest) = unpack_u64(rest)?;
+            RayLog::SwapBaseOut(SwapBaseOutLog {
+                log_type,
+                max_in,
+                amount_out,
+                direction,
+                user_source,
+                pool_coin,
+                pool_pc,
+                deduct_in,
+            })
+        }
+    })
+}
+
+/// Scans the log messages of a transaction and decodes the events of the AMM v4 and stable programs.
+///
+/// The programs invoked are tracked through the `Program <id> invoke [<depth>]`,
+/// `Program <id> success` and `Program <id> failed: <error>` messages, so `ray_log` messages
+/// printed by other programs are skipped. A malformed `ray_log` payload is reported in its event
+/// and the scan goes on.
+pub fn parse_ray_logs<S: AsRef<str>>(log_messages: &[S]) -> Vec<RayLogEvent> {
+    // program ids as printed, a malformed one still balances its invoke and success
+    let mut invoked: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+    for message in log_messages {
+        let message = message.as_ref();
+        if let Some(log) = message.strip_prefix(RAY_LOG_PREFIX) {
+            let program_id = match invoked
+                .last()
+                .and_then(|program_id| program_id.parse().ok())
+            {
+                Some(program_id) => program_id,
+                None => continue,
+            };
+            if program_id == amm_instruction::id() || program_id == stable_instruction::id() {
+                events.push(RayLogEvent {
+                    program_id,
+                    log: decode_ray_log(log),
+                });
+            }
+            continue;
+        }
+        let words: Vec<&str> = message.split(' ').collect();
+        match words.as_slice() {
+            ["Program", program_id, "invoke", depth] => {
+                let depth = depth
+                    .strip_prefix('[')
+                    .and_then(|depth| depth.strip_suffix(']'))
+                    .and_then(|depth| depth.parse::<usize>().ok());
+                if let Some(depth) = depth.filter(|depth| *depth > 0) {
+                    // drops the programs whose success or failure was not logged
+                    invoked.truncate(depth - 1);
+                    invoked.push(program_id);
+                }
+            }
+            ["Program", program_id, "success"] | ["Program", program_id, "failed:", ..]
+                if invoked.last() == Some(program_id) =>
+            {
+                invoked.pop();
+            }
+            _ => {}
+        }
+    }
+    events
+}
+
+fn unpack_u8(input: &[u8]) -> Result<(u8, &[u8]), ProgramError> {
+    let (&value, rest) = input.split_first().ok_or(ProgramError::InvalidArgument)?;
+    Ok((value, rest))
+}
+
+fn unpack_u64(input: &[u8]) -> Result<(u64, &[u8]), ProgramError> {
+    if input.len() >= 8 {
+        let (amount, rest) = input.split_at(8);
+        let amount = amount
+            .try_into()
+            .map(u64::from_le_bytes)
+            .map_err(|_| ProgramError::InvalidArgument)?;
+        Ok((amount, rest))
+    } else {
+        Err(ProgramError::InvalidArgument)
+    }
+}
+
+fn unpack_u128(input: &[u8]) -> Result<(u128, &[u8]), ProgramError> {
+    if input.len() >= 16 {
+        let (amount, rest) = input.split_at(16);
+        let amount = amount
+            .try_into()
+            .map(u128::from_le_bytes)
+            .map_err(|_| ProgramError::InvalidArgument)?;
+        Ok((amount, rest))
+    } else {
+        Err(ProgramError::InvalidArgument)
+    }
+}
+
+fn unpack_pubkey(input: &[u8]) -> Result<(Pubkey, &[u8]), ProgramError> {
+    if input.len() >= 32 {
+        let (key, rest) = input.split_at(32);
+        let key = Pubkey::try_from(key).map_err(|_| ProgramError::InvalidArgument)?;
+        Ok((key, rest))
+    } else {
+        Err(ProgramError::InvalidArgument)
+    }
+}
@@ -0,0 +1,199 @@
+//! Lp position valuation and impermanent loss.
+//!
+//! An lp token is a share of the pool reserves, withdrawn at the pool ratio by
+//! the 'withdraw' instruction. A position is valued from the amounts it would
+//! withdraw, wallet and farm staked lp together, and compared with holding the
+//! amounts it was worth at entry to get the impermanent loss.
+
+use crate::{
+    amm_stats,
+    amount::{PoolPrice, TokenAmount},
+    farm_stats::FarmerInfoV2,
+    market_stats::OpenOrders,
+    slippage::BPS_DENOMINATOR,
+    stable_stats,
+};
+use solana_program::program_error::ProgramError;
+
+/// Lp supply and reserves of a pool, the state lp positions are valued
+/// against.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LpPoolState {
+    /// lp amount the withdraw shares the reserves between
+    pub lp_supply: u64,
+    pub coin_reserve: u64,
+    pub pc_reserve: u64,
+    pub coin_decimals: u8,
+    pub pc_decimals: u8,
+}
+
+impl LpPoolState {
+    pub fn new(
+        lp_supply: u64,
+        coin_reserve: u64,
+        pc_reserve: u64,
+        coin_decimals: u8,
+        pc_decimals: u8,
+    ) -> Self {
+        Self {
+            lp_supply,
+            coin_reserve,
+            pc_reserve,
+            coin_decimals,
+            pc_decimals,
+        }
+    }
+
+    /// State of an AMM v4 pool, with the lp amount recorded by the amm which
+    /// the program withdraws against.
+    pub fn from_amm(
+        amm: &amm_stats::AmmInfo,
+        coin_vault_amount: u64,
+        pc_vault_amount: u64,
+        open_orders: &OpenOrders,
+    ) -> Result<Self, ProgramError> {
+        let (coin_reserve, pc_reserve) =
+            amm.effective_reserves(coin_vault_amount, pc_vault_amount, open_orders)?;
+        Ok(Self::new(
+            amm.lp_amount,
+            coin_reserve,
+            pc_reserve,
+            amm.coin_decimals as u8,
+            amm.pc_decimals as u8,
+        ))
+    }
+
+    /// State of a stable pool, with the supply of its lp mint.
+    pub fn from_stable(
+        amm: &stable_stats::AmmInfo,
+        lp_supply: u64,
+        coin_vault_amount: u64,
+        pc_vault_amount: u64,
+        open_orders: &OpenOrders,
+    ) -> Result<Self, ProgramError> {
+        let (coin_reserve, pc_reserve) =
+            amm.effective_reserves(coin_vault_amount, pc_vault_amount, open_orders)?;
+        Ok(Self::new(
+            lp_supply,
+            coin_reserve,
+            pc_reserve,
+            amm.coin_decimals as u8,
+            amm.pc_decimals as u8,
+        ))
+    }
+
+    /// Coin and pc amounts of a 'withdraw' of `lp_amount`, rounded down like
+    /// the program.
+    pub fn withdraw_amounts(&self, lp_amount: u64) -> Result<(u64, u64), ProgramError> {
+        if self.lp_supply == 0 {
+            return Err(ProgramError::InsufficientFunds);
+        }
+        if lp_amount > self.lp_supply {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let share =
+            |reserve: u64| (lp_amount as u128 * reserve as u128 / self.lp_supply as u128) as u64;
+        Ok((share(self.coin_reserve), share(self.pc_reserve)))
+    }
+
+    /// Price of the pool reserves, `None` for an empty pool.
+    pub fn reserves_price(&self) -> Option<PoolPrice> {
+        PoolPrice::from_reserves(
+            self.coin_reserve,
+            self.pc_reserve,
+            self.coin_decimals,
+            self.pc_decimals,
+        )
+    }
+
+    /// Position of `wallet_lp_amount` lp tokens and the lp staked by the
+    /// `farmers`, accounts of farms staking the pool lp.
+    pub fn position(
+        &self,
+        wallet_lp_amount: u64,
+        farmers: &[FarmerInfoV2],
+    ) -> Result<LpPosition, ProgramError> {
+        let staked_lp_amount = farmers
+            .iter()
+            .try_fold(0u64, |amount, farmer| {
+                amount.checked_add(farmer.deposit_balance)
+            })
+            .ok_or(ProgramError::InvalidArgument)?;
+        let lp_amount = wallet_lp_amount
+            .checked_add(staked_lp_amount)
+            .ok_or(ProgramError::InvalidArgument)?;
+        let (coin_amount, pc_amount) = self.withdraw_amounts(lp_amount)?;
+        Ok(LpPosition {
+            wallet_lp_amount,
+            staked_lp_amount,
+            coin_amount: TokenAmount::new(coin_amount, self.coin_decimals),
+            pc_amount: TokenAmount::new(pc_amount, self.pc_decimals),
+        })
+    }
+
+    /// Impermanent loss of `lp_amount` lp tokens held since the `entry` state
+    /// of the pool, both valued at `price`. The fees accrued to the reserves
+    /// since the entry reduce the loss.
+    pub fn impermanent_loss(
+        &self,
+        entry: &LpPoolState,
+        lp_amount: u64,
+        price: &PoolPrice,
+    ) -> Result<ImpermanentLoss, ProgramError> {
+        let (coin_amount, pc_amount) = self.withdraw_amounts(lp_amount)?;
+        let (entry_coin_amount, entry_pc_amount) = entry.withdraw_amounts(lp_amount)?;
+        let value =
+            |coin_amount: u64, pc_amount: u64| coin_amount as f64 * price.raw + pc_amount as f64;
+        Ok(ImpermanentLoss {
+            lp_value: value(coin_amount, pc_amount),
+            hold_value: value(entry_coin_amount, entry_pc_amount),
+        })
+    }
+}
+
+/// Lp position of an owner in a pool.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LpPosition {
+    /// lp tokens in the owner wallet
+    pub wallet_lp_amount: u64,
+    /// lp tokens staked in farms
+    pub staked_lp_amount: u64,
+    /// coin the position withdraws
+    pub coin_amount: TokenAmount,
+    /// pc the position withdraws
+    pub pc_amount: TokenAmount,
+}
+
+impl LpPosition {
+    /// Lp tokens of the position, staked included.
+    pub fn lp_amount(&self) -> u64 {
+        self.wallet_lp_amount.saturating_add(self.staked_lp_amount)
+    }
+
+    /// Pc value of the position, its coin valued at `price`, rounded down.
+    pub fn pc_value(&self, price: &PoolPrice) -> TokenAmount {
+        let coin_value = price.pc_value(self.coin_amount.amount);
+        TokenAmount::new(
+            coin_value.amount.saturating_add(self.pc_amount.amount),
+            self.pc_amount.decimals,
+        )
+    }
+}
+
+/// Value of an lp position against holding its entry amounts, in pc atoms.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ImpermanentLoss {
+    /// value of the amounts the position withdraws
+    pub lp_value: f64,
+    /// value of the amounts the position withdrew at entry
+    pub hold_value: f64,
+}
+
+impl ImpermanentLoss {
+    /// Loss of the position against holding in basis points, negative if the
+    /// fees outgrow the loss, `None` for a zero hold value.
+    pub fn loss_bps(&self) -> Option<f64> {
+        (self.hold_value > 0.0)
+            .then(|| (1.0 - self.lp_value / self.hold_value) * BPS_DENOMINATOR as f64)
+    }
+}
@@ -0,0 +1,310 @@
+//! Serum v3 / OpenBook v1 market state types
+
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use std::convert::TryInto;
+
+/// Serum dex v3 program
+pub mod serum_program {
+    solana_program::declare_id!("9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin");
+}
+
+/// OpenBook v1 program, a fork of serum dex v3 with the same account layouts
+pub mod openbook_program {
+    solana_program::declare_id!("srmqPvymJeFKQ4zGQed1GFppgkRHL9kaELCbyksJtPX");
+}
+
+/// Every dex account starts with these bytes
+pub const ACCOUNT_HEAD_PADDING: &[u8; 5] = b"serum";
+/// Every dex account ends with these bytes
+pub const ACCOUNT_TAIL_PADDING: &[u8; 7] = b"padding";
+
+#[repr(u64)]
+pub enum AccountFlag {
+    Initialized = 1u64,
+    Market = 2u64,
+    OpenOrders = 4u64,
+    RequestQueue = 8u64,
+    EventQueue = 16u64,
+    Bids = 32u64,
+    Asks = 64u64,
+    Disabled = 128u64,
+    Closed = 256u64,
+    Permissioned = 512u64,
+    CrankAuthorityRequired = 1024u64,
+}
+
+/// Market account of the serum dex v3 and OpenBook v1 programs.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MarketState {
+    /// Initialized | Market
+    pub account_flags: u64,
+    /// market account key
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub own_address: Pubkey,
+    /// nonce used to derive the vault signer
+    pub vault_signer_nonce: u64,
+    /// coin mint
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub coin_mint: Pubkey,
+    /// pc mint
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub pc_mint: Pubkey,
+    /// market coin vault
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub coin_vault: Pubkey,
+    /// total coin deposited
+    pub coin_deposits_total: u64,
+    /// coin fees accrued
+    pub coin_fees_accrued: u64,
+    /// market pc vault
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub pc_vault: Pubkey,
+    /// total pc deposited
+    pub pc_deposits_total: u64,
+    /// pc fees accrued
+    pub pc_fees_accrued: u64,
+    /// pc dust threshold
+    pub pc_dust_threshold: u64,
+    /// request queue
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub req_q: Pubkey,
+    /// event queue
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub event_q: Pubkey,
+    /// bids
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub bids: Pubkey,
+    /// asks
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub asks: Pubkey,
+    /// coin lot size
+    pub coin_lot_size: u64,
+    /// pc lot size
+    pub pc_lot_size: u64,
+    /// fee rate in bps
+    pub fee_rate_bps: u64,
+    /// referrer rebates accrued
+    pub referrer_rebates_accrued: u64,
+}
+
+impl MarketState {
+    /// Size of the market state without the head and tail padding.
+    pub const STATE_LEN: usize = 376;
+    /// Size of a market account.
+    pub const LEN: usize =
+        ACCOUNT_HEAD_PADDING.len() + Self::STATE_LEN + ACCOUNT_TAIL_PADDING.len();
+
+    /// Unpacks a market account data into a [MarketState](struct.MarketState.html).
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let data = strip_account_padding(input, Self::STATE_LEN)?;
+        let (account_flags, rest) = unpack_u64(data)?;
+        if account_flags & (AccountFlag::Initialized as u64) == 0
+            || account_flags & (AccountFlag::Market as u64) == 0
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let (own_address, rest) = unpack_pubkey(rest)?;
+        let (vault_signer_nonce, rest) = unpack_u64(rest)?;
+        let (coin_mint, rest) = unpack_pubkey(rest)?;
+        let (pc_mint, rest) = unpack_pubkey(rest)?;
+        let (coin_vault, rest) = unpack_pubkey(rest)?;
+        let (coin_deposits_total, rest) = unpack_u64(rest)?;
+        let (coin_fees_accrued, rest) = unpack_u64(rest)?;
+        let (pc_vault, rest) = unpack_pubkey(rest)?;
+        let (pc_deposits_total, rest) = unpack_u64(rest)?;
+        let (pc_fees_accrued, rest) = unpack_u64(rest)?;
+        let (pc_dust_threshold, rest) = unpack_u64(rest)?;
+        let (req_q, rest) = unpack_pubkey(rest)?;
+        let (event_q, rest) = unpack_pubkey(rest)?;
+        let (bids, rest) = unpack_pubkey(rest)?;
+        let (asks, rest) = unpack_pubkey(rest)?;
+        let (coin_lot_size, rest) = unpack_u64(rest)?;
+        let (pc_lot_size, rest) = unpack_u64(rest)?;
+        let (fee_rate_bps, rest) = unpack_u64(rest)?;
+        let (referrer_rebates_accrued, _rest) = unpack_u64(rest)?;
+        Ok(Self {
+            account_flags,
+            own_address,
+            vault_signer_nonce,
+            coin_mint,
+            pc_mint,
+            coin_vault,
+            coin_deposits_total,
+            coin_fees_accrued,
+            pc_vault,
+            pc_deposits_total,
+            pc_fees_accrued,
+            pc_dust_threshold,
+            req_q,
+            event_q,
+            bids,
+            asks,
+            coin_lot_size,
+            pc_lot_size,
+            fee_rate_bps,
+            referrer_rebates_accrued,
+        })
+    }
+
+    /// Derives the market vault signer, the authority of the market coin and pc vaults.
+    pub fn vault_signer(&self, market_program: &Pubkey) -> Result<Pubkey, ProgramError> {
+        Pubkey::create_program_address(
+            &[
+                self.own_address.as_ref(),
+                &self.vault_signer_nonce.to_le_bytes(),
+            ],
+            market_program,
+        )
+        .map_err(|_| ProgramError::InvalidSeeds)
+    }
+
+    /// Checks the lot sizes recorded in an amm against the market ones.
+    pub fn check_lot_sizes(
+        &self,
+        coin_lot_size: u64,
+        pc_lot_size: u64,
+    ) -> Result<(), ProgramError> {
+        if self.coin_lot_size != coin_lot_size || self.pc_lot_size != pc_lot_size {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+}
+
+/// Open orders account of the serum dex v3 and OpenBook v1 programs.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OpenOrders {
+    /// Initialized | OpenOrders
+    pub account_flags: u64,
+    /// market key
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub market: Pubkey,
+    /// owner key, the amm authority for an amm open orders
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub owner: Pubkey,
+    /// coin not locked in orders
+    pub native_coin_free: u64,
+    /// coin free and locked in orders
+    pub native_coin_total: u64,
+    /// pc not locked in orders
+    pub native_pc_free: u64,
+    /// pc free and locked in orders
+    pub native_pc_total: u64,
+    /// bit set for each unused order slot
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub free_slot_bits: u128,
+    /// bit set for each bid order slot
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub is_bid_bits: u128,
+    /// order ids
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string_array"))]
+    pub orders: [u128; 128],
+    /// client order ids
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::array"))]
+    pub client_order_ids: [u64; 128],
+    /// referrer rebates accrued
+    pub referrer_rebates_accrued: u64,
+}
+
+impl OpenOrders {
+    /// Size of the open orders state without the head and tail padding.
+    pub const STATE_LEN: usize = 3216;
+    /// Size of an open orders account.
+    pub const LEN: usize =
+        ACCOUNT_HEAD_PADDING.len() + Self::STATE_LEN + ACCOUNT_TAIL_PADDING.len();
+
+    /// Unpacks an open orders account data into an [OpenOrders](struct.OpenOrders.html).
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let data = strip_account_padding(input, Self::STATE_LEN)?;
+        let (account_flags, rest) = unpack_u64(data)?;
+        if account_flags & (AccountFlag::Initialized as u64) == 0
+            || account_flags & (AccountFlag::OpenOrders as u64) == 0
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let (market, rest) = unpack_pubkey(rest)?;
+        let (owner, rest) = unpack_pubkey(rest)?;
+        let (native_coin_free, rest) = unpack_u64(rest)?;
+        let (native_coin_total, rest) = unpack_u64(rest)?;
+        let (native_pc_free, rest) = unpack_u64(rest)?;
+        let (native_pc_total, rest) = unpack_u64(rest)?;
+        let (free_slot_bits, rest) = unpack_u128(rest)?;
+        let (is_bid_bits, mut rest) = unpack_u128(rest)?;
+        let mut orders = [0u128; 128];
+        for order in orders.iter_mut() {
+            (*order, rest) = unpack_u128(rest)?;
+        }
+        let mut client_order_ids = [0u64; 128];
+        for client_order_id in client_order_ids.iter_mut() {
+            (*client_order_id, rest) = unpack_u64(rest)?;
+        }
+        let (referrer_rebates_accrued, _rest) = unpack_u64(rest)?;
+        Ok(Self {
+            account_flags,
+            market,
+            owner,
+            native_coin_free,
+            native_coin_total,
+            native_pc_free,
+            native_pc_total,
+            free_slot_bits,
+            is_bid_bits,
+            orders,
+            client_order_ids,
+            referrer_rebates_accrued,
+        })
+    }
+}
+
+/// Checks the `serum` head and `padding` tail and returns the account body.
+/// Permissioned markets are longer than `len`, the extra bytes are left in the body.
+pub fn strip_account_padding(input: &[u8], len: usize) -> Result<&[u8], ProgramError> {
+    let min_len = ACCOUNT_HEAD_PADDING.len() + len + ACCOUNT_TAIL_PADDING.len();
+    if input.len() < min_len {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let (head, rest) = input.split_at(ACCOUNT_HEAD_PADDING.len());
+    let (data, tail) = rest.split_at(rest.len() - ACCOUNT_TAIL_PADDING.len());
+    if head != ACCOUNT_HEAD_PADDING || tail != ACCOUNT_TAIL_PADDING {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(data)
+}
+
+fn unpack_u64(input: &[u8]) -> Result<(u64, &[u8]), ProgramError> {
+    if input.len() >= 8 {
+        let (amount, rest) = input.split_at(8);
+        let amount = amount
+            .try_into()
+            .map(u64::from_le_bytes)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        Ok((amount, rest))
+    } else {
+        Err(ProgramError::InvalidAccountData)
+    }
+}
+
+fn unpack_pubkey(input: &[u8]) -> Result<(Pubkey, &[u8]), ProgramError> {
+    if input.len() >= 32 {
+        let (key, rest) = input.split_at(32);
+        let key = Pubkey::try_from(key).map_err(|_| ProgramError::InvalidAccountData)?;
+        Ok((key, rest))
+    } else {
+        Err(ProgramError::InvalidAccountData)
+    }
+}
+
+fn unpack_u128(input: &[u8]) -> Result<(u128, &[u8]), ProgramError> {
+    if input.len() >= 16 {
+        let (amount, rest) = input.split_at(16);
+        let amount = amount
+            .try_into()
+            .map(u128::from_le_bytes)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        Ok((amount, rest))
+    } else {
+        Err(ProgramError::InvalidAccountData)
+    }
+}
@@ -0,0 +1,791 @@
+//! Pools of the AMM v4 and stable programs behind a common interface.
+//!
+//! [RaydiumPool](trait.RaydiumPool.html) quotes and builds the swaps, deposits
+//! and withdrawals of a decoded pool, so dashboards and routers can handle the
+//! pool types generically. Quotes are in vault amounts, between the reserves
+//! the program swaps against.
+
+#![allow(clippy::too_many_arguments)]
+
+use crate::{
+    amm_instruction, amm_math, amm_stats,
+    amount::{Amount, PoolPrice, TokenAmount},
+    market_stats::{MarketState, OpenOrders},
+    route_instruction::RoutePoolKeys,
+    slippage, stable_instruction,
+    stable_math::ModelData,
+    stable_stats,
+};
+use solana_program::{instruction::Instruction, program_error::ProgramError, pubkey::Pubkey};
+use spl_associated_token_account::get_associated_token_address;
+
+/// User accounts of the pool instructions.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct UserPoolAccounts {
+    /// user wallet, signer of the instructions
+    pub owner: Pubkey,
+    /// user coin token account
+    pub token_coin: Pubkey,
+    /// user pc token account
+    pub token_pc: Pubkey,
+    /// user lp token account
+    pub token_lp: Pubkey,
+}
+
+impl UserPoolAccounts {
+    /// Associated token accounts of `owner` for the pool mints.
+    pub fn associated<P: RaydiumPool + ?Sized>(owner: &Pubkey, pool: &P) -> Self {
+        let (coin_mint, pc_mint) = pool.mints();
+        Self {
+            owner: *owner,
+            token_coin: get_associated_token_address(owner, &coin_mint),
+            token_pc: get_associated_token_address(owner, &pc_mint),
+            token_lp: get_associated_token_address(owner, &pool.lp_mint()),
+        }
+    }
+}
+
+/// Decoded pool of a Raydium program.
+pub trait RaydiumPool {
+    /// Accounts of the pool swaps.
+    fn keys(&self) -> &RoutePoolKeys;
+
+    /// Coin and pc mints.
+    fn mints(&self) -> (Pubkey, Pubkey);
+
+    /// Lp mint.
+    fn lp_mint(&self) -> Pubkey;
+
+    /// Coin and pc decimals.
+    fn decimals(&self) -> (u8, u8);
+
+    /// Coin and pc reserves the program swaps against.
+    fn reserves(&self) -> (u64, u64);
+
+    /// Marginal price before fee at the coin and pc reserves, in pc per coin,
+    /// `None` for an empty pool or reserves the curve does not cover.
+    fn marginal_price_at(&self, coin_reserve: u64, pc_reserve: u64) -> Option<f64>;
+
+    /// Output amount of a swap of `amount_in` from `input_mint`.
+    fn quote_in(&self, input_mint: &Pubkey, amount_in: u64) -> Result<u64, ProgramError>;
+
+    /// Input amount of a swap for `amount_out` of `output_mint`.
+    fn quote_out(&self, output_mint: &Pubkey, amount_out: u64) -> Result<u64, ProgramError>;
+
+    /// Creates the swap instruction from `input_mint`, a 'swap base in' of
+    /// `amount` if `is_base_input`, else a 'swap base out' for `amount`.
+    /// `other_amount_threshold` is the minimum amount out, which cannot be
+    /// zero, or the maximum amount in.
+    fn build_swap(
+        &self,
+        user: &UserPoolAccounts,
+        input_mint: &Pubkey,
+        amount: u64,
+        other_amount_threshold: u64,
+        is_base_input: bool,
+    ) -> Result<Instruction, ProgramError>;
+
+    /// Creates the deposit instruction, `base_side` 0 for coin and 1 for pc.
+    fn build_deposit(
+        &self,
+        user: &UserPoolAccounts,
+        max_coin_amount: u64,
+        max_pc_amount: u64,
+        base_side: u64,
+    ) -> Result<Instruction, ProgramError>;
+
+    /// Creates the withdraw instruction of `amount` lp tokens.
+    fn build_withdraw(
+        &self,
+        user: &UserPoolAccounts,
+        amount: u64,
+    ) -> Result<Instruction, ProgramError>;
+
+    /// Output mint of a swap from `input_mint`, `None` if the pool does not
+    /// hold it.
+    fn other_mint(&self, input_mint: &Pubkey) -> Option<Pubkey> {
+        let (coin_mint, pc_mint) = self.mints();
+        if *input_mint == coin_mint {
+            Some(pc_mint)
+        } else if *input_mint == pc_mint {
+            Some(coin_mint)
+        } else {
+            None
+        }
+    }
+
+    /// Decimals of `mint`, the coin or pc mint.
+    fn mint_decimals(&self, mint: &Pubkey) -> Result<u8, ProgramError> {
+        let (coin_decimals, pc_decimals) = self.decimals();
+        Ok(if self.coin_to_pc(mint)? {
+            coin_decimals
+        } else {
+            pc_decimals
+        })
+    }
+
+    /// Price of the coin in pc at the reserves ratio, the spot price of a
+    /// constant product pool.
+    fn reserves_price(&self) -> Option<PoolPrice> {
+        let (coin_reserve, pc_reserve) = self.reserves();
+        let (coin_decimals, pc_decimals) = self.decimals();
+        PoolPrice::from_reserves(coin_reserve, pc_reserve, coin_decimals, pc_decimals)
+    }
+
+    /// [quote_in](#tymethod.quote_in) of an amount in atoms or with decimals.
+    fn quote_in_amount(
+        &self,
+        input_mint: &Pubkey,
+        amount_in: Amount,
+    ) -> Result<TokenAmount, ProgramError> {
+        let output_mint = self
+            .other_mint(input_mint)
+            .ok_or(ProgramError::InvalidArgument)?;
+        let amount_in = amount_in.to_atoms(self.mint_decimals(input_mint)?)?;
+        Ok(TokenAmount::new(
+            self.quote_in(input_mint, amount_in)?,
+            self.mint_decimals(&output_mint)?,
+        ))
+    }
+
+    /// [quote_out](#tymethod.quote_out) of an amount in atoms or with decimals.
+    fn quote_out_amount(
+        &self,
+        output_mint: &Pubkey,
+        amount_out: Amount,
+    ) -> Result<TokenAmount, ProgramError> {
+        let input_mint = self
+            .other_mint(output_mint)
+            .ok_or(ProgramError::InvalidArgument)?;
+        let amount_out = amount_out.to_atoms(self.mint_decimals(output_mint)?)?;
+        Ok(TokenAmount::new(
+            self.quote_out(output_mint, amount_out)?,
+            self.mint_decimals(&input_mint)?,
+        ))
+    }
+
+    /// [build_swap](#tymethod.build_swap) of amounts in atoms or with decimals.
+    fn build_swap_amount(
+        &self,
+        user: &UserPoolAccounts,
+        input_mint: &Pubkey,
+        amount: Amount,
+        other_amount_threshold: Amount,
+        is_base_input: bool,
+    ) -> Result<Instruction, ProgramError> {
+        let output_mint = self
+            .other_mint(input_mint)
+            .ok_or(ProgramError::InvalidArgument)?;
+        let (amount_mint, threshold_mint) = if is_base_input {
+            (input_mint, &output_mint)
+        } else {
+            (&output_mint, input_mint)
+        };
+        self.build_swap(
+            user,
+            input_mint,
+            amount.to_atoms(self.mint_decimals(amount_mint)?)?,
+            other_amount_threshold.to_atoms(self.mint_decimals(threshold_mint)?)?,
+            is_base_input,
+        )
+    }
+
+    /// [build_deposit](#tymethod.build_deposit) of amounts in atoms or with
+    /// decimals.
+    fn build_deposit_amount(
+        &self,
+        user: &UserPoolAccounts,
+        max_coin_amount: Amount,
+        max_pc_amount: Amount,
+        base_side: u64,
+    ) -> Result<Instruction, ProgramError> {
+        let (coin_decimals, pc_decimals) = self.decimals();
+        self.build_deposit(
+            user,
+            max_coin_amount.to_atoms(coin_decimals)?,
+            max_pc_amount.to_atoms(pc_decimals)?,
+            base_side,
+        )
+    }
+
+    /// Whether a swap from `input_mint` is from coin to pc.
+    fn coin_to_pc(&self, input_mint: &Pubkey) -> Result<bool, ProgramError> {
+        let (coin_mint, pc_mint) = self.mints();
+        if *input_mint == coin_mint {
+            Ok(true)
+        } else if *input_mint == pc_mint {
+            Ok(false)
+        } else {
+            Err(ProgramError::InvalidArgument)
+        }
+    }
+}
+
+/// User source and destination token accounts of a swap.
+fn swap_user_accounts(user: &UserPoolAccounts, coin_to_pc: bool) -> (&Pubkey, &Pubkey) {
+    if coin_to_pc {
+        (&user.token_coin, &user.token_pc)
+    } else {
+        (&user.token_pc, &user.token_coin)
+    }
+}
+
+/// Direction of a swap between the coin and pc of a pool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapDirection {
+    CoinToPc,
+    PcToCoin,
+}
+
+impl SwapDirection {
+    /// Direction of a swap from `input_mint`.
+    pub fn from_input_mint<P: RaydiumPool + ?Sized>(
+        pool: &P,
+        input_mint: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        Ok(if pool.coin_to_pc(input_mint)? {
+            Self::CoinToPc
+        } else {
+            Self::PcToCoin
+        })
+    }
+
+    /// Input and output mints of the swap.
+    pub fn mints<P: RaydiumPool + ?Sized>(&self, pool: &P) -> (Pubkey, Pubkey) {
+        let (coin_mint, pc_mint) = pool.mints();
+        match self {
+            Self::CoinToPc => (coin_mint, pc_mint),
+            Self::PcToCoin => (pc_mint, coin_mint),
+        }
+    }
+
+    /// User source and destination associated token accounts of the swap.
+    pub fn user_token_accounts<P: RaydiumPool + ?Sized>(
+        &self,
+        pool: &P,
+        user_owner: &Pubkey,
+    ) -> (Pubkey, Pubkey) {
+        let (input_mint, output_mint) = self.mints(pool);
+        (
+            get_associated_token_address(user_owner, &input_mint),
+            get_associated_token_address(user_owner, &output_mint),
+        )
+    }
+
+    /// Checks the user source and destination token accounts are the
+    /// associated token accounts of the swap mints.
+    pub fn check_user_token_accounts<P: RaydiumPool + ?Sized>(
+        &self,
+        pool: &P,
+        user_owner: &Pubkey,
+        user_token_source: &Pubkey,
+        user_token_destination: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        if self.user_token_accounts(pool, user_owner)
+            != (*user_token_source, *user_token_destination)
+        {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(())
+    }
+
+    /// Creates the swap instruction of `pool` between the associated token
+    /// accounts of `user_owner`, see [build_swap](trait.RaydiumPool.html#tymethod.build_swap).
+    pub fn build_swap<P: RaydiumPool + ?Sized>(
+        &self,
+        pool: &P,
+        user_owner: &Pubkey,
+        amount: u64,
+        other_amount_threshold: u64,
+        is_base_input: bool,
+    ) -> Result<Instruction, ProgramError> {
+        let (input_mint, _) = self.mints(pool);
+        pool.build_swap(
+            &UserPoolAccounts::associated(user_owner, pool),
+            &input_mint,
+            amount,
+            other_amount_threshold,
+            is_base_input,
+        )
+    }
+
+    /// [build_swap](#method.build_swap) with the user token accounts given
+    /// by the caller, failing if they are not the derived ones.
+    pub fn build_swap_checked<P: RaydiumPool + ?Sized>(
+        &self,
+        pool: &P,
+        user_owner: &Pubkey,
+        user_token_source: &Pubkey,
+        user_token_destination: &Pubkey,
+        amount: u64,
+        other_amount_threshold: u64,
+        is_base_input: bool,
+    ) -> Result<Instruction, ProgramError> {
+        self.check_user_token_accounts(
+            pool,
+            user_owner,
+            user_token_source,
+            user_token_destination,
+        )?;
+        self.build_swap(
+            pool,
+            user_owner,
+            amount,
+            other_amount_threshold,
+            is_base_input,
+        )
+    }
+}
+
+/// Decoded AMM v4 pool.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AmmPool {
+    /// pool accounts
+    pub keys: RoutePoolKeys,
+    pub amm_target_orders: Pubkey,
+    pub lp_mint: Pubkey,
+    pub coin_mint: Pubkey,
+    pub pc_mint: Pubkey,
+    pub coin_decimals: u8,
+    pub pc_decimals: u8,
+    /// coin reserve the program swaps against
+    pub coin_reserve: u64,
+    /// pc reserve the program swaps against
+    pub pc_reserve: u64,
+    pub swap_fee_numerator: u64,
+    pub swap_fee_denominator: u64,
+}
+
+impl AmmPool {
+    /// Creates the pool from its decoded accounts and vault balances.
+    pub fn new(
+        amm_pool: &Pubkey,
+        amm: &amm_stats::AmmInfo,
+        market: &MarketState,
+        open_orders: &OpenOrders,
+        coin_vault_amount: u64,
+        pc_vault_amount: u64,
+    ) -> Result<Self, ProgramError> {
+        let program_id = amm_instruction::id();
+        let (coin_reserve, pc_reserve) =
+            amm.effective_reserves(coin_vault_amount, pc_vault_amount, open_orders)?;
+        Ok(Self {
+            keys: RoutePoolKeys {
+                program_id,
+                amm_pool: *amm_pool,
+                amm_authority: amm_instruction::get_amm_authority(&program_id, amm.nonce)?,
+                amm_open_orders: amm.open_orders,
+                amm_coin_vault: amm.coin_vault,
+                amm_pc_vault: amm.pc_vault,
+                model_data_account: None,
+                market_program: amm.market_program,
+                market: amm.market,
+                market_bids: market.bids,
+                market_asks: market.asks,
+                market_event_queue: market.event_q,
+                market_coin_vault: market.coin_vault,
+                market_pc_vault: market.pc_vault,
+                market_vault_signer: market.vault_signer(&amm.market_program)?,
+            },
+            amm_target_orders: amm.target_orders,
+            lp_mint: amm.lp_mint,
+            coin_mint: amm.coin_vault_mint,
+            pc_mint: amm.pc_vault_mint,
+            coin_decimals: amm.coin_decimals as u8,
+            pc_decimals: amm.pc_decimals as u8,
+            coin_reserve,
+            pc_reserve,
+            swap_fee_numerator: amm.swap_fee_numerator,
+            swap_fee_denominator: amm.swap_fee_denominator,
+        })
+    }
+}
+
+impl RaydiumPool for AmmPool {
+    fn keys(&self) -> &RoutePoolKeys {
+        &self.keys
+    }
+
+    fn mints(&self) -> (Pubkey, Pubkey) {
+        (self.coin_mint, self.pc_mint)
+    }
+
+    fn lp_mint(&self) -> Pubkey {
+        self.lp_mint
+    }
+
+    fn decimals(&self) -> (u8, u8) {
+        (self.coin_decimals, self.pc_decimals)
+    }
+
+    fn reserves(&self) -> (u64, u64) {
+        (self.coin_reserve, self.pc_reserve)
+    }
+
+    fn marginal_price_at(&self, coin_reserve: u64, pc_reserve: u64) -> Option<f64> {
+        slippage::spot_price(coin_reserve, pc_reserve)
+    }
+
+    fn quote_in(&self, input_mint: &Pubkey, amount_in: u64) -> Result<u64, ProgramError> {
+        let (reserve_in, reserve_out) = if self.coin_to_pc(input_mint)? {
+            (self.coin_reserve, self.pc_reserve)
+        } else {
+            (self.pc_reserve, self.coin_reserve)
+        };
+        amm_math::swap_base_in_amount_out(
+            amount_in,
+            reserve_in,
+            reserve_out,
+            self.swap_fee_numerator,
+            self.swap_fee_denominator,
+        )
+    }
+
+    fn quote_out(&self, output_mint: &Pubkey, amount_out: u64) -> Result<u64, ProgramError> {
+        let (reserve_in, reserve_out) = if self.coin_to_pc(output_mint)? {
+            (self.pc_reserve, self.coin_reserve)
+        } else {
+            (self.coin_reserve, self.pc_reserve)
+        };
+        amm_math::swap_base_out_amount_in(
+            amount_out,
+            reserve_in,
+            reserve_out,
+            self.swap_fee_numerator,
+            self.swap_fee_denominator,
+        )
+    }
+
+    fn build_swap(
+        &self,
+        user: &UserPoolAccounts,
+        input_mint: &Pubkey,
+        amount: u64,
+        other_amount_threshold: u64,
+        is_base_input: bool,
+    ) -> Result<Instruction, ProgramError> {
+        let (user_token_source, user_token_destination) =
+            swap_user_accounts(user, self.coin_to_pc(input_mint)?);
+        let keys = &self.keys;
+        let swap = if is_base_input {
+            amm_instruction::swap_base_in
+        } else {
+            amm_instruction::swap_base_out
+        };
+        swap(
+            &keys.program_id,
+            &keys.amm_pool,
+            &keys.amm_authority,
+            &keys.amm_open_orders,
+            &keys.amm_coin_vault,
+            &keys.amm_pc_vault,
+            &keys.market_program,
+            &keys.market,
+            &keys.market_bids,
+            &keys.market_asks,
+            &keys.market_event_queue,
+            &keys.market_coin_vault,
+            &keys.market_pc_vault,
+            &keys.market_vault_signer,
+            user_token_source,
+            user_token_destination,
+            &user.owner,
+            amount,
+            other_amount_threshold,
+        )
+    }
+
+    fn build_deposit(
+        &self,
+        user: &UserPoolAccounts,
+        max_coin_amount: u64,
+        max_pc_amount: u64,
+        base_side: u64,
+    ) -> Result<Instruction, ProgramError> {
+        let keys = &self.keys;
+        amm_instruction::deposit(
+            &keys.program_id,
+            &keys.amm_pool,
+            &keys.amm_authority,
+            &keys.amm_open_orders,
+            &self.amm_target_orders,
+            &self.lp_mint,
+            &keys.amm_coin_vault,
+            &keys.amm_pc_vault,
+            &keys.market,
+            &keys.market_event_queue,
+            &user.token_coin,
+            &user.token_pc,
+            &user.token_lp,
+            &user.owner,
+            max_coin_amount,
+            max_pc_amount,
+            base_side,
+        )
+    }
+
+    fn build_withdraw(
+        &self,
+        user: &UserPoolAccounts,
+        amount: u64,
+    ) -> Result<Instruction, ProgramError> {
+        let keys = &self.keys;
+        amm_instruction::withdraw(
+            &keys.program_id,
+            &keys.amm_pool,
+            &keys.amm_authority,
+            &keys.amm_open_orders,
+            &self.amm_target_orders,
+            &self.lp_mint,
+            &keys.amm_coin_vault,
+            &keys.amm_pc_vault,
+            &keys.market_program,
+            &keys.market,
+            &keys.market_coin_vault,
+            &keys.market_pc_vault,
+            &keys.market_vault_signer,
+            &user.token_lp,
+            &user.token_coin,
+            &user.token_pc,
+            &user.owner,
+            &keys.market_event_queue,
+            &keys.market_bids,
+            &keys.market_asks,
+            None,
+            amount,
+        )
+    }
+}
+
+/// Decoded stable pool, priced on the curve of its model data account.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StablePool<'a> {
+    /// pool accounts
+    pub keys: RoutePoolKeys,
+    pub amm_target_orders: Pubkey,
+    pub lp_mint: Pubkey,
+    pub coin_mint: Pubkey,
+    pub pc_mint: Pubkey,
+    pub coin_decimals: u8,
+    pub pc_decimals: u8,
+    /// coin reserve the program swaps against
+    pub coin_reserve: u64,
+    /// pc reserve the program swaps against
+    pub pc_reserve: u64,
+    pub swap_fee_numerator: u64,
+    pub swap_fee_denominator: u64,
+    /// decoded model data account
+    pub model_data: &'a ModelData,
+}
+
+impl<'a> StablePool<'a> {
+    /// Creates the pool from its decoded accounts and vault balances.
+    pub fn new(
+        amm_pool: &Pubkey,
+        amm: &stable_stats::AmmInfo,
+        model_data: &'a ModelData,
+        market: &MarketState,
+        open_orders: &OpenOrders,
+        coin_vault_amount: u64,
+        pc_vault_amount: u64,
+    ) -> Result<Self, ProgramError> {
+        let program_id = stable_instruction::id();
+        let (coin_reserve, pc_reserve) =
+            amm.effective_reserves(coin_vault_amount, pc_vault_amount, open_orders)?;
+        Ok(Self {
+            keys: RoutePoolKeys {
+                program_id,
+                amm_pool: *amm_pool,
+                amm_authority: amm_instruction::get_amm_authority(&program_id, amm.nonce)?,
+                amm_open_orders: amm.open_orders,
+                amm_coin_vault: amm.coin_vault,
+                amm_pc_vault: amm.pc_vault,
+                model_data_account: Some(amm.model_data_key),
+                market_program: amm.serum_program,
+                market: amm.serum_market,
+                market_bids: market.bids,
+                market_asks: market.asks,
+                market_event_queue: market.event_q,
+                market_coin_vault: market.coin_vault,
+                market_pc_vault: market.pc_vault,
+                market_vault_signer: market.vault_signer(&amm.serum_program)?,
+            },
+            amm_target_orders: amm.target_orders,
+            lp_mint: amm.lp_mint,
+            coin_mint: amm.coin_mint,
+            pc_mint: amm.pc_mint,
+            coin_decimals: amm.coin_decimals as u8,
+            pc_decimals: amm.pc_decimals as u8,
+            coin_reserve,
+            pc_reserve,
+            swap_fee_numerator: amm.fees.swap_fee_numerator,
+            swap_fee_denominator: amm.fees.swap_fee_denominator,
+            model_data,
+        })
+    }
+
+    fn model_data_account(&self) -> Result<&Pubkey, ProgramError> {
+        self.keys
+            .model_data_account
+            .as_ref()
+            .ok_or(ProgramError::NotEnoughAccountKeys)
+    }
+}
+
+impl RaydiumPool for StablePool<'_> {
+    fn keys(&self) -> &RoutePoolKeys {
+        &self.keys
+    }
+
+    fn mints(&self) -> (Pubkey, Pubkey) {
+        (self.coin_mint, self.pc_mint)
+    }
+
+    fn lp_mint(&self) -> Pubkey {
+        self.lp_mint
+    }
+
+    fn decimals(&self) -> (u8, u8) {
+        (self.coin_decimals, self.pc_decimals)
+    }
+
+    fn reserves(&self) -> (u64, u64) {
+        (self.coin_reserve, self.pc_reserve)
+    }
+
+    fn marginal_price_at(&self, coin_reserve: u64, pc_reserve: u64) -> Option<f64> {
+        self.model_data.marginal_price(coin_reserve, pc_reserve)
+    }
+
+    fn quote_in(&self, input_mint: &Pubkey, amount_in: u64) -> Result<u64, ProgramError> {
+        let coin_to_pc = self.coin_to_pc(input_mint)?;
+        let fee = amm_math::swap_fee(
+            amount_in,
+            self.swap_fee_numerator,
+            self.swap_fee_denominator,
+        )?;
+        self.model_data.get_amount_out(
+            self.coin_reserve,
+            self.pc_reserve,
+            amount_in - fee.min(amount_in),
+            coin_to_pc,
+        )
+    }
+
+    fn quote_out(&self, output_mint: &Pubkey, amount_out: u64) -> Result<u64, ProgramError> {
+        let coin_to_pc = !self.coin_to_pc(output_mint)?;
+        let amount_in = self.model_data.get_amount_in(
+            self.coin_reserve,
+            self.pc_reserve,
+            amount_out,
+            coin_to_pc,
+        )?;
+        amm_math::amount_in_with_fee(
+            amount_in,
+            self.swap_fee_numerator,
+            self.swap_fee_denominator,
+        )
+    }
+
+    fn build_swap(
+        &self,
+        user: &UserPoolAccounts,
+        input_mint: &Pubkey,
+        amount: u64,
+        other_amount_threshold: u64,
+        is_base_input: bool,
+    ) -> Result<Instruction, ProgramError> {
+        let (user_token_source, user_token_destination) =
+            swap_user_accounts(user, self.coin_to_pc(input_mint)?);
+        let keys = &self.keys;
+        let swap = if is_base_input {
+            stable_instruction::swap_base_in
+        } else {
+            stable_instruction::swap_base_out
+        };
+        swap(
+            &keys.program_id,
+            &keys.amm_pool,
+            &keys.amm_authority,
+            &keys.amm_open_orders,
+            &keys.amm_coin_vault,
+            &keys.amm_pc_vault,
+            self.model_data_account()?,
+            &keys.market_program,
+            &keys.market,
+            &keys.market_bids,
+            &keys.market_asks,
+            &keys.market_event_queue,
+            &keys.market_coin_vault,
+            &keys.market_pc_vault,
+            &keys.market_vault_signer,
+            user_token_source,
+            user_token_destination,
+            &user.owner,
+            amount,
+            other_amount_threshold,
+        )
+    }
+
+    fn build_deposit(
+        &self,
+        user: &UserPoolAccounts,
+        max_coin_amount: u64,
+        max_pc_amount: u64,
+        base_side: u64,
+    ) -> Result<Instruction, ProgramError> {
+        let keys = &self.keys;
+        stable_instruction::deposit(
+            &keys.program_id,
+            &keys.amm_pool,
+            &keys.amm_authority,
+            &keys.amm_open_orders,
+            &self.amm_target_orders,
+            &self.lp_mint,
+            &keys.amm_coin_vault,
+            &keys.amm_pc_vault,
+            self.model_data_account()?,
+            &keys.market,
+            &user.token_coin,
+            &user.token_pc,
+            &user.token_lp,
+            &user.owner,
+            max_coin_amount,
+            max_pc_amount,
+            base_side,
+        )
+    }
+
+    fn build_withdraw(
+        &self,
+        user: &UserPoolAccounts,
+        amount: u64,
+    ) -> Result<Instruction, ProgramError> {
+        let keys = &self.keys;
+        stable_instruction::withdraw(
+            &keys.program_id,
+            &keys.amm_pool,
+            &keys.amm_authority,
+            &keys.amm_open_orders,
+            &self.amm_target_orders,
+            &self.lp_mint,
+            &keys.amm_coin_vault,
+            &keys.amm_pc_vault,
+            self.model_data_account()?,
+            &keys.market_program,
+            &keys.market,
+            &keys.market_coin_vault,
+            &keys.market_pc_vault,
+            &keys.market_vault_signer,
+            &user.token_lp,
+            &user.token_coin,
+            &user.token_pc,
+            &user.owner,
+            None,
+            Some(&keys.market_event_queue),
+            Some(&keys.market_bids),
+            Some(&keys.market_asks),
+            amount,
+        )
+    }
+}
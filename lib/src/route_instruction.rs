@@ -0,0 +1,325 @@
+//! Instruction types
+//!
+//! The route program swaps across two AMM v4 or stable pools in a single
+//! transaction. The first hop is a `SwapInFixedIn` instruction recording the
+//! middle token amount in a user status account, the second hop a
+//! `SwapOutFixedIn` instruction swapping the recorded amount. The final
+//! `minimum_amount_out` is given to the first hop, and is checked against the
+//! output of the second one.
+
+#![allow(clippy::too_many_arguments)]
+
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use std::convert::TryInto;
+
+solana_program::declare_id!("routeUGWgWzqBWFcrCfv8tritsqukccJPu3q5GPP3xS");
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RouteSwapInInstruction {
+    /// SOURCE amount to transfer to the first pool
+    pub amount_in: u64,
+    /// Minimum amount of DESTINATION token out of the second pool, prevents excessive slippage
+    pub minimum_amount_out: u64,
+}
+
+/// Instructions supported by the route program.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum RouteInstruction {
+    ///   Swaps through the first pool into the middle token.
+    ///
+    ///   0. `[]` Sys program id
+    ///   1. `[]` Spl Token program id
+    ///
+    ///   followed by the first pool accounts, see [RoutePoolKeys](struct.RoutePoolKeys.html),
+    ///   then the user accounts
+    ///
+    ///   0. `[writable]` user SOURCE token Account
+    ///   1. `[writable]` user middle token Account
+    ///   2. `[writable]` user middle status Account, created if needed
+    ///   3. `[signer]` user wallet Account
+    SwapInFixedIn(RouteSwapInInstruction),
+
+    ///   Swaps the middle token amount recorded by `SwapInFixedIn` through the
+    ///   second pool.
+    ///
+    ///   0. `[]` Sys program id
+    ///   1. `[]` Spl Token program id
+    ///
+    ///   followed by the second pool accounts, see [RoutePoolKeys](struct.RoutePoolKeys.html),
+    ///   then the user accounts
+    ///
+    ///   0. `[writable]` user middle token Account
+    ///   1. `[writable]` user DESTINATION token Account
+    ///   2. `[writable]` user middle status Account
+    ///   3. `[signer]` user wallet Account
+    SwapOutFixedIn,
+}
+
+impl RouteInstruction {
+    /// Unpacks a byte buffer into a [RouteInstruction](enum.RouteInstruction.html).
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (&tag, rest) = input
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        Ok(match tag {
+            0 => {
+                let (amount_in, rest) = Self::unpack_u64(rest)?;
+                let (minimum_amount_out, _rest) = Self::unpack_u64(rest)?;
+                Self::SwapInFixedIn(RouteSwapInInstruction {
+                    amount_in,
+                    minimum_amount_out,
+                })
+            }
+            1 => Self::SwapOutFixedIn,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+
+    fn unpack_u64(input: &[u8]) -> Result<(u64, &[u8]), ProgramError> {
+        if input.len() >= 8 {
+            let (amount, rest) = input.split_at(8);
+            let amount = amount
+                .try_into()
+                .map(u64::from_le_bytes)
+                .map_err(|_| ProgramError::InvalidInstructionData)?;
+            Ok((amount, rest))
+        } else {
+            Err(ProgramError::InvalidInstructionData)
+        }
+    }
+
+    /// Packs a [RouteInstruction](enum.RouteInstruction.html) into a byte buffer.
+    pub fn pack(&self) -> Result<Vec<u8>, ProgramError> {
+        let mut buf = Vec::with_capacity(17);
+        match self {
+            Self::SwapInFixedIn(RouteSwapInInstruction {
+                amount_in,
+                minimum_amount_out,
+            }) => {
+                buf.push(0);
+                buf.extend_from_slice(&amount_in.to_le_bytes());
+                buf.extend_from_slice(&minimum_amount_out.to_le_bytes());
+            }
+            Self::SwapOutFixedIn => buf.push(1),
+        }
+        Ok(buf)
+    }
+}
+
+/// Accounts of an AMM v4 or stable pool swapped through by a route.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RoutePoolKeys {
+    /// amm or stable program id
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub program_id: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub amm_pool: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub amm_authority: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub amm_open_orders: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub amm_coin_vault: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub amm_pc_vault: Pubkey,
+    /// model data account of a stable pool, `None` for an AMM v4 pool
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::option_string"))]
+    pub model_data_account: Option<Pubkey>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub market_program: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub market: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub market_bids: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub market_asks: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub market_event_queue: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub market_coin_vault: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub market_pc_vault: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
+    pub market_vault_signer: Pubkey,
+}
+
+impl RoutePoolKeys {
+    /// Pool accounts of a hop, in the order of the pool swap instruction.
+    pub fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            // amm
+            AccountMeta::new_readonly(self.program_id, false),
+            AccountMeta::new(self.amm_pool, false),
+            AccountMeta::new_readonly(self.amm_authority, false),
+            AccountMeta::new(self.amm_open_orders, false),
+            AccountMeta::new(self.amm_coin_vault, false),
+            AccountMeta::new(self.amm_pc_vault, false),
+        ];
+        if let Some(model_data_account) = self.model_data_account {
+            accounts.push(AccountMeta::new_readonly(model_data_account, false));
+        }
+        accounts.extend([
+            // market
+            AccountMeta::new_readonly(self.market_program, false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.market_bids, false),
+            AccountMeta::new(self.market_asks, false),
+            AccountMeta::new(self.market_event_queue, false),
+            AccountMeta::new(self.market_coin_vault, false),
+            AccountMeta::new(self.market_pc_vault, false),
+            AccountMeta::new_readonly(self.market_vault_signer, false),
+        ]);
+        accounts
+    }
+}
+
+/// Finds the user middle status account of a route from `from_pool` through `middle_mint`.
+pub fn get_middle_status(
+    program_id: &Pubkey,
+    from_pool: &Pubkey,
+    middle_mint: &Pubkey,
+    user_owner: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            from_pool.as_ref(),
+            middle_mint.as_ref(),
+            user_owner.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Assembles the accounts of a hop.
+pub fn route_accounts(
+    pool: &RoutePoolKeys,
+    middle_status: &Pubkey,
+    user_source_token: &Pubkey,
+    user_destination_token: &Pubkey,
+    user_owner: &Pubkey,
+) -> Vec<AccountMeta> {
+    let mut accounts = vec![
+        // programs
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    accounts.extend(pool.accounts());
+    accounts.extend([
+        // user
+        AccountMeta::new(*user_source_token, false),
+        AccountMeta::new(*user_destination_token, false),
+        AccountMeta::new(*middle_status, false),
+        AccountMeta::new_readonly(*user_owner, true),
+    ]);
+    accounts
+}
+
+/// Creates a 'swap in fixed in' instruction, the first hop of a route.
+pub fn swap_in_fixed_in(
+    route_program: &Pubkey,
+    from_pool: &RoutePoolKeys,
+    middle_mint: &Pubkey,
+    user_source_token: &Pubkey,
+    user_middle_token: &Pubkey,
+    user_owner: &Pubkey,
+
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<Instruction, ProgramError> {
+    let data = RouteInstruction::SwapInFixedIn(RouteSwapInInstruction {
+        amount_in,
+        minimum_amount_out,
+    })
+    .pack()?;
+
+    let middle_status =
+        get_middle_status(route_program, &from_pool.amm_pool, middle_mint, user_owner);
+    let accounts = route_accounts(
+        from_pool,
+        &middle_status,
+        user_source_token,
+        user_middle_token,
+        user_owner,
+    );
+
+    Ok(Instruction {
+        program_id: *route_program,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'swap out fixed in' instruction, the second hop of a route.
+pub fn swap_out_fixed_in(
+    route_program: &Pubkey,
+    from_pool: &Pubkey,
+    to_pool: &RoutePoolKeys,
+    middle_mint: &Pubkey,
+    user_middle_token: &Pubkey,
+    user_destination_token: &Pubkey,
+    user_owner: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = RouteInstruction::SwapOutFixedIn.pack()?;
+
+    let middle_status = get_middle_status(route_program, from_pool, middle_mint, user_owner);
+    let accounts = route_accounts(
+        to_pool,
+        &middle_status,
+        user_middle_token,
+        user_destination_token,
+        user_owner,
+    );
+
+    Ok(Instruction {
+        program_id: *route_program,
+        accounts,
+        data,
+    })
+}
+
+/// Creates the instructions of a two hops route swap of an exact `amount_in`,
+/// to send in this order in a single transaction.
+pub fn route_swap_base_in(
+    route_program: &Pubkey,
+    from_pool: &RoutePoolKeys,
+    to_pool: &RoutePoolKeys,
+    middle_mint: &Pubkey,
+    user_source_token: &Pubkey,
+    user_middle_token: &Pubkey,
+    user_destination_token: &Pubkey,
+    user_owner: &Pubkey,
+
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<[Instruction; 2], ProgramError> {
+    Ok([
+        swap_in_fixed_in(
+            route_program,
+            from_pool,
+            middle_mint,
+            user_source_token,
+            user_middle_token,
+            user_owner,
+            amount_in,
+            minimum_amount_out,
+        )?,
+        swap_out_fixed_in(
+            route_program,
+            &from_pool.amm_pool,
+            to_pool,
+            middle_mint,
+            user_middle_token,
+            user_destination_token,
+            user_owner,
+        )?,
+    ])
+}
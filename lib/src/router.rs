@@ -0,0 +1,305 @@
+//! Off-chain route finder over AMM v4 and stable pools.
+//!
+//! A route is a sequence of 1 to 3 pool swaps from an input mint to an output
+//! mint. Each hop is sent as its own `swap_base_in` instruction: the amount in
+//! of a hop is the minimum amount out of the previous one, so the user always
+//! holds it, and the intermediate tokens above the minimum stay in the user
+//! accounts.
+
+#![allow(clippy::too_many_arguments)]
+
+use crate::{
+    amm_stats,
+    market_stats::{MarketState, OpenOrders},
+    pool::{AmmPool, RaydiumPool, StablePool, UserPoolAccounts},
+    route_instruction::RoutePoolKeys,
+    slippage::minimum_amount_out,
+    stable_math::ModelData,
+    stable_stats,
+};
+use solana_program::{instruction::Instruction, program_error::ProgramError, pubkey::Pubkey};
+
+/// Maximum number of hops of a route.
+pub const MAX_HOPS: usize = 3;
+
+/// Decoded pool a route can swap through, of any pool type.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RoutePool<'a> {
+    Amm(AmmPool),
+    Stable(StablePool<'a>),
+}
+
+impl<'a> RoutePool<'a> {
+    /// Creates an AMM v4 pool from its decoded accounts and vault balances.
+    pub fn amm(
+        amm_pool: &Pubkey,
+        amm: &amm_stats::AmmInfo,
+        market: &MarketState,
+        open_orders: &OpenOrders,
+        coin_vault_amount: u64,
+        pc_vault_amount: u64,
+    ) -> Result<Self, ProgramError> {
+        AmmPool::new(
+            amm_pool,
+            amm,
+            market,
+            open_orders,
+            coin_vault_amount,
+            pc_vault_amount,
+        )
+        .map(Self::Amm)
+    }
+
+    /// Creates a stable pool from its decoded accounts and vault balances.
+    pub fn stable(
+        amm_pool: &Pubkey,
+        amm: &stable_stats::AmmInfo,
+        model_data: &'a ModelData,
+        market: &MarketState,
+        open_orders: &OpenOrders,
+        coin_vault_amount: u64,
+        pc_vault_amount: u64,
+    ) -> Result<Self, ProgramError> {
+        StablePool::new(
+            amm_pool,
+            amm,
+            model_data,
+            market,
+            open_orders,
+            coin_vault_amount,
+            pc_vault_amount,
+        )
+        .map(Self::Stable)
+    }
+
+    fn pool(&self) -> &dyn RaydiumPool {
+        match self {
+            Self::Amm(pool) => pool,
+            Self::Stable(pool) => pool,
+        }
+    }
+}
+
+impl RaydiumPool for RoutePool<'_> {
+    fn keys(&self) -> &RoutePoolKeys {
+        self.pool().keys()
+    }
+
+    fn mints(&self) -> (Pubkey, Pubkey) {
+        self.pool().mints()
+    }
+
+    fn lp_mint(&self) -> Pubkey {
+        self.pool().lp_mint()
+    }
+
+    fn decimals(&self) -> (u8, u8) {
+        self.pool().decimals()
+    }
+
+    fn reserves(&self) -> (u64, u64) {
+        self.pool().reserves()
+    }
+
+    fn marginal_price_at(&self, coin_reserve: u64, pc_reserve: u64) -> Option<f64> {
+        self.pool().marginal_price_at(coin_reserve, pc_reserve)
+    }
+
+    fn quote_in(&self, input_mint: &Pubkey, amount_in: u64) -> Result<u64, ProgramError> {
+        self.pool().quote_in(input_mint, amount_in)
+    }
+
+    fn quote_out(&self, output_mint: &Pubkey, amount_out: u64) -> Result<u64, ProgramError> {
+        self.pool().quote_out(output_mint, amount_out)
+    }
+
+    fn build_swap(
+        &self,
+        user: &UserPoolAccounts,
+        input_mint: &Pubkey,
+        amount: u64,
+        other_amount_threshold: u64,
+        is_base_input: bool,
+    ) -> Result<Instruction, ProgramError> {
+        self.pool().build_swap(
+            user,
+            input_mint,
+            amount,
+            other_amount_threshold,
+            is_base_input,
+        )
+    }
+
+    fn build_deposit(
+        &self,
+        user: &UserPoolAccounts,
+        max_coin_amount: u64,
+        max_pc_amount: u64,
+        base_side: u64,
+    ) -> Result<Instruction, ProgramError> {
+        self.pool()
+            .build_deposit(user, max_coin_amount, max_pc_amount, base_side)
+    }
+
+    fn build_withdraw(
+        &self,
+        user: &UserPoolAccounts,
+        amount: u64,
+    ) -> Result<Instruction, ProgramError> {
+        self.pool().build_withdraw(user, amount)
+    }
+}
+
+/// Swap of a route through one pool.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RouteHop {
+    /// index of the pool in the searched pools
+    pub pool: usize,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+}
+
+/// Best route found by [find_best_route](fn.find_best_route.html).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Route {
+    pub hops: Vec<RouteHop>,
+    pub amount_in: u64,
+    /// expected amount out of the last hop
+    pub amount_out: u64,
+}
+
+/// Finds the route of at most `max_hops` hops with the highest amount out of
+/// `output_mint` for `amount_in` of `input_mint`, `None` if no pool path
+/// connects the mints. A pool is swapped through at most once.
+pub fn find_best_route<P: RaydiumPool>(
+    pools: &[P],
+    input_mint: &Pubkey,
+    output_mint: &Pubkey,
+    amount_in: u64,
+    max_hops: usize,
+) -> Result<Option<Route>, ProgramError> {
+    if max_hops == 0 || max_hops > MAX_HOPS || input_mint == output_mint {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let mut best = None;
+    let mut hops = Vec::with_capacity(max_hops);
+    search(
+        pools,
+        input_mint,
+        output_mint,
+        amount_in,
+        max_hops,
+        &mut hops,
+        &mut best,
+    );
+    Ok(best.map(|hops: Vec<RouteHop>| Route {
+        amount_out: hops.last().map(|hop| hop.amount_out).unwrap_or_default(),
+        hops,
+        amount_in,
+    }))
+}
+
+fn search<P: RaydiumPool>(
+    pools: &[P],
+    mint: &Pubkey,
+    output_mint: &Pubkey,
+    amount_in: u64,
+    max_hops: usize,
+    hops: &mut Vec<RouteHop>,
+    best: &mut Option<Vec<RouteHop>>,
+) {
+    for (index, pool) in pools.iter().enumerate() {
+        let next_mint = match pool.other_mint(mint) {
+            Some(next_mint) => next_mint,
+            None => continue,
+        };
+        // each pool and mint at most once
+        if hops
+            .iter()
+            .any(|hop| hop.pool == index || hop.input_mint == next_mint)
+        {
+            continue;
+        }
+        let amount_out = match pool.quote_in(mint, amount_in) {
+            Ok(amount_out) if amount_out > 0 => amount_out,
+            _ => continue,
+        };
+        hops.push(RouteHop {
+            pool: index,
+            input_mint: *mint,
+            output_mint: next_mint,
+            amount_in,
+            amount_out,
+        });
+        if next_mint == *output_mint {
+            let best_amount_out = best
+                .as_ref()
+                .and_then(|best| best.last())
+                .map(|hop| hop.amount_out);
+            if best_amount_out.map_or(true, |best_amount_out| amount_out > best_amount_out) {
+                *best = Some(hops.clone());
+            }
+        } else if hops.len() < max_hops {
+            search(
+                pools,
+                &next_mint,
+                output_mint,
+                amount_out,
+                max_hops,
+                hops,
+                best,
+            );
+        }
+        hops.pop();
+    }
+}
+
+/// Swap instruction of a hop with its amounts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouteSwap {
+    pub instruction: Instruction,
+    pub amount_in: u64,
+    pub minimum_amount_out: u64,
+}
+
+impl Route {
+    /// Creates the swap instructions of the route, to send in order.
+    ///
+    /// Each hop is quoted again on the minimum amount out of the previous
+    /// one, and its minimum amount out allows `slippage_bps` below the quote.
+    /// `user_token_account` gives the user token account of a mint, usually
+    /// its associated token account.
+    pub fn swap_instructions<P: RaydiumPool>(
+        &self,
+        pools: &[P],
+        user_owner: &Pubkey,
+        user_token_account: impl Fn(&Pubkey) -> Pubkey,
+        slippage_bps: u64,
+    ) -> Result<Vec<RouteSwap>, ProgramError> {
+        let mut amount_in = self.amount_in;
+        let mut swaps = Vec::with_capacity(self.hops.len());
+        for hop in &self.hops {
+            let pool = pools.get(hop.pool).ok_or(ProgramError::InvalidArgument)?;
+            let amount_out = pool.quote_in(&hop.input_mint, amount_in)?;
+            let minimum_amount_out = minimum_amount_out(amount_out, slippage_bps)?;
+            let (coin_mint, pc_mint) = pool.mints();
+            let user = UserPoolAccounts {
+                owner: *user_owner,
+                token_coin: user_token_account(&coin_mint),
+                token_pc: user_token_account(&pc_mint),
+                token_lp: user_token_account(&pool.lp_mint()),
+            };
+            let instruction =
+                pool.build_swap(&user, &hop.input_mint, amount_in, minimum_amount_out, true)?;
+            swaps.push(RouteSwap {
+                instruction,
+                amount_in,
+                minimum_amount_out,
+            });
+            amount_in = minimum_amount_out;
+        }
+        Ok(swaps)
+    }
+}
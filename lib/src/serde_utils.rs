@@ -0,0 +1,116 @@
+//! Serde helpers of the `serde` feature.
+//!
+//! Keys are written in base58 and u128 values as decimal strings, so the
+//! output stays safe for JSON consumers limited to 53 bits integers.
+
+use serde::{de::Error, ser::SerializeSeq, Deserialize, Deserializer, Serialize, Serializer};
+use std::{convert::TryInto, fmt::Display, str::FromStr};
+
+/// (De)serializes a value through its `Display` and `FromStr` implementations.
+pub mod string {
+    use super::*;
+
+    pub fn serialize<T: Display, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Same as [string](string/index.html) for an optional value.
+pub mod option_string {
+    use super::*;
+
+    pub fn serialize<T: Display, S: Serializer>(
+        value: &Option<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.collect_str(value),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|value| value.parse().map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+/// Same as [string](string/index.html) for each item of an array.
+pub mod string_array {
+    use super::*;
+
+    pub fn serialize<T: Display, S: Serializer, const N: usize>(
+        values: &[T; N],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(N))?;
+        for value in values {
+            seq.serialize_element(&value.to_string())?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, T, D, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        let values = Vec::<String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|value| value.parse().map_err(D::Error::custom))
+            .collect::<Result<Vec<T>, D::Error>>()?;
+        into_array(values)
+    }
+}
+
+/// (De)serializes arrays longer than the 32 items supported by serde.
+pub mod array {
+    use super::*;
+
+    pub fn serialize<T: Serialize, S: Serializer, const N: usize>(
+        values: &[T; N],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(N))?;
+        for value in values {
+            seq.serialize_element(value)?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, T, D, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        into_array(Vec::<T>::deserialize(deserializer)?)
+    }
+}
+
+fn into_array<T, E: Error, const N: usize>(values: Vec<T>) -> Result<[T; N], E> {
+    let len = values.len();
+    values
+        .try_into()
+        .map_err(|_| E::invalid_length(len, &format!("an array of {} items", N).as_str()))
+}
@@ -0,0 +1,107 @@
+//! Swap slippage bounds and price reports.
+//!
+//! The bounds given to the swap instructions are rounded against the user: the
+//! minimum amount out of a 'swap base in' is rounded down and the maximum
+//! amount in of a 'swap base out' rounded up, so a quote filled exactly always
+//! passes the program check.
+
+use solana_program::program_error::ProgramError;
+
+/// Basis points denominator of the slippage and price impact.
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Rejects a zero `minimum_amount_out`, which would accept any swap output.
+pub fn check_minimum_amount_out(minimum_amount_out: u64) -> Result<u64, ProgramError> {
+    if minimum_amount_out == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(minimum_amount_out)
+}
+
+/// Minimum amount out of a 'swap base in' quoted `amount_out`, with
+/// `slippage_bps` of slippage, rounded down. Fails if it rounds to zero.
+pub fn minimum_amount_out(amount_out: u64, slippage_bps: u64) -> Result<u64, ProgramError> {
+    let remaining_bps = BPS_DENOMINATOR
+        .checked_sub(slippage_bps)
+        .ok_or(ProgramError::InvalidArgument)?;
+    let minimum_amount_out =
+        (amount_out as u128 * remaining_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+    check_minimum_amount_out(minimum_amount_out)
+}
+
+/// Maximum amount in of a 'swap base out' quoted `amount_in`, with
+/// `slippage_bps` of slippage, rounded up.
+pub fn maximum_amount_in(amount_in: u64, slippage_bps: u64) -> Result<u64, ProgramError> {
+    let bps = BPS_DENOMINATOR as u128 + slippage_bps as u128;
+    let maximum_amount_in =
+        (amount_in as u128 * bps + BPS_DENOMINATOR as u128 - 1) / BPS_DENOMINATOR as u128;
+    u64::try_from(maximum_amount_in).map_err(|_| ProgramError::InvalidArgument)
+}
+
+/// Spot price of a constant product pool, in output tokens per input token,
+/// `None` for an empty pool.
+pub fn spot_price(reserve_in: u64, reserve_out: u64) -> Option<f64> {
+    (reserve_in > 0 && reserve_out > 0).then(|| reserve_out as f64 / reserve_in as f64)
+}
+
+/// Effective price of a swap, in output tokens per input token, `None` for a
+/// zero `amount_in`.
+pub fn effective_price(amount_in: u64, amount_out: u64) -> Option<f64> {
+    (amount_in > 0).then(|| amount_out as f64 / amount_in as f64)
+}
+
+/// Price impact of a swap in basis points, the relative difference between
+/// the `spot_price` and the effective price, fee included.
+pub fn price_impact_bps(spot_price: f64, amount_in: u64, amount_out: u64) -> Option<f64> {
+    if spot_price <= 0.0 {
+        return None;
+    }
+    let effective_price = effective_price(amount_in, amount_out)?;
+    Some((1.0 - effective_price / spot_price) * BPS_DENOMINATOR as f64)
+}
+
+/// Price report of a swap quote.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SwapPriceReport {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// pool price before the swap, in output tokens per input token
+    pub spot_price: f64,
+    /// swap price, in output tokens per input token
+    pub effective_price: f64,
+    /// relative difference of the prices, in basis points
+    pub price_impact_bps: f64,
+}
+
+impl SwapPriceReport {
+    /// Reports a quote of a constant product pool with `reserve_in` and
+    /// `reserve_out` reserves.
+    pub fn new(
+        reserve_in: u64,
+        reserve_out: u64,
+        amount_in: u64,
+        amount_out: u64,
+    ) -> Result<Self, ProgramError> {
+        let spot_price =
+            spot_price(reserve_in, reserve_out).ok_or(ProgramError::InvalidArgument)?;
+        Self::with_spot_price(spot_price, amount_in, amount_out)
+    }
+
+    /// Reports a quote against a known `spot_price`, for curves where it is not
+    /// the reserve ratio.
+    pub fn with_spot_price(
+        spot_price: f64,
+        amount_in: u64,
+        amount_out: u64,
+    ) -> Result<Self, ProgramError> {
+        Ok(Self {
+            amount_in,
+            amount_out,
+            spot_price,
+            effective_price: effective_price(amount_in, amount_out)
+                .ok_or(ProgramError::InvalidArgument)?,
+            price_impact_bps: price_impact_bps(spot_price, amount_in, amount_out)
+                .ok_or(ProgramError::InvalidArgument)?,
+        })
+    }
+}
@@ -13,6 +13,7 @@ use std::mem::size_of;
 
 solana_program::declare_id!("5quBtoiQqxF9Jv6KYKctB59NT3gtJD2Y65kdnB1Uev3h");
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct InitializeInstruction {
@@ -22,6 +23,7 @@ pub struct InitializeInstruction {
     pub open_time: u64,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct PreInitializeInstruction {
@@ -29,6 +31,7 @@ pub struct PreInitializeInstruction {
     pub nonce: u8,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct DepositInstruction {
@@ -39,6 +42,7 @@ pub struct DepositInstruction {
     pub base_side: u64,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct WithdrawInstruction {
@@ -47,6 +51,7 @@ pub struct WithdrawInstruction {
     pub amount: u64,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct SwapInstructionBaseIn {
@@ -56,6 +61,7 @@ pub struct SwapInstructionBaseIn {
     pub minimum_amount_out: u64,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct SwapInstructionBaseOut {
@@ -72,19 +78,23 @@ pub enum AmmInstruction {
     ///   Initializes a new AmmInfo.
     ///
     ///   0. `[]` Spl Token program id
-    ///   1. `[writable, signer]` New amm Account to create.
-    ///   2. `[]` $authority derived from `create_program_address(&[amm Account])`
-    ///   3. `[]` amm open_orders Account
-    ///   4. `[writable]` pool lp mint address. Must be empty, owned by $authority.
-    ///   5. `[]` coin mint address
-    ///   6. `[]` pc mint address
-    ///   7. `[]` pool_token_coin Account. Must be non zero, owned by $authority.
-    ///   8. `[]` pool_token_pc Account. Must be non zero, owned by $authority.
-    ///   9. '[writable]` withdraw queue Account. To save withdraw dest_coin & dest_pc account with must cancle orders.
-    ///   10. `[writable]` token_dest_lp Account. To deposit the initial pool token supply, user is the owner.
-    ///   11. `[writable]` token_temp_lp Account. To save withdraw lp with must cancle orders as temp to transfer later.
-    ///   12. `[]` serum dex program id
-    ///   13. `[]` serum market Account. serum_dex program is the owner.
+    ///   1. `[]` Sys program id
+    ///   2. `[]` Rent program id
+    ///   3. `[writable]` New amm Account to create.
+    ///   4. `[]` $authority derived from `create_program_address(&[amm Account])`
+    ///   5. `[writable]` amm open_orders Account
+    ///   6. `[writable]` pool lp mint address. Must be empty, owned by $authority.
+    ///   7. `[]` coin mint address
+    ///   8. `[]` pc mint address
+    ///   9. `[]` pool_token_coin Account. Must be non zero, owned by $authority.
+    ///   10. `[]` pool_token_pc Account. Must be non zero, owned by $authority.
+    ///   11. `[writable]` amm target_orders Account. To store plan orders infomations.
+    ///   12. `[]` model data Account
+    ///   13. `[]` serum dex program id
+    ///   14. `[]` serum market Account. serum_dex program is the owner.
+    ///   15. `[writable]` token_dest_lp Account. To deposit the initial pool token supply, user is the owner.
+    ///   16. `[writable, signer]` user wallet Account
+    ///   17. `[writable]` (optional) srm token Account
     Initialize(InitializeInstruction),
 
     ///   reserved
@@ -104,11 +114,12 @@ pub enum AmmInstruction {
     ///   5. `[writable]` pool lp mint address. Must be empty, owned by $authority.
     ///   6. `[writable]` pool_token_coin $authority can transfer amount,
     ///   7. `[writable]` pool_token_pc $authority can transfer amount,
-    ///   8. `[]` serum market Account. serum_dex program is the owner.
-    ///   9. `[writable]` user coin token Base Account to deposit into.
-    ///   10. `[writable]` user pc token Base Account to deposit into.
-    ///   11. `[writable]` user lp token. To deposit the generated tokens, user is the owner.
-    ///   12. '[signer]` user owner Account
+    ///   8. `[]` model data Account
+    ///   9. `[]` serum market Account. serum_dex program is the owner.
+    ///   10. `[writable]` user coin token Base Account to deposit into.
+    ///   11. `[writable]` user pc token Base Account to deposit into.
+    ///   12. `[writable]` user lp token. To deposit the generated tokens, user is the owner.
+    ///   13. '[signer]` user owner Account
     Deposit(DepositInstruction),
 
     ///   Withdraw the token from the pool at the current ratio.
@@ -121,17 +132,20 @@ pub enum AmmInstruction {
     ///   5. `[writable]` pool lp mint address. Must be empty, owned by $authority.
     ///   6. `[writable]` pool_token_coin Amm Account to withdraw FROM,
     ///   7. `[writable]` pool_token_pc Amm Account to withdraw FROM,
-    ///   8. `[writable]` withdraw queue Account
-    ///   9. `[writable]` token_temp_lp Account
-    ///   10. `[]` serum dex program id
-    ///   11. `[writable]` serum market Account. serum_dex program is the owner.
-    ///   12. `[writable]` coin_vault Account
-    ///   13. `[writable]` pc_vault Account
-    ///   14. '[]` vault_signer Account
-    ///   15. `[writable]` user lp token Account. Source lp, amount is transferable by $authority.
-    ///   16. `[writable]` user token coin Account. user Account to credit.
-    ///   17. `[writable]` user token pc Account. user Account to credit.
-    ///   18. `[singer]` user owner Account
+    ///   8. `[]` model data Account
+    ///   9. `[]` serum dex program id
+    ///   10. `[writable]` serum market Account. serum_dex program is the owner.
+    ///   11. `[writable]` coin_vault Account
+    ///   12. `[writable]` pc_vault Account
+    ///   13. '[]` vault_signer Account
+    ///   14. `[writable]` user lp token Account. Source lp, amount is transferable by $authority.
+    ///   15. `[writable]` user token coin Account. user Account to credit.
+    ///   16. `[writable]` user token pc Account. user Account to credit.
+    ///   17. `[singer]` user owner Account
+    ///   18. `[writable]` (optional) referrer pc Account
+    ///   19. `[writable]` (optional) event_q Account, passed with bids and asks
+    ///   20. `[writable]` (optional) bids Account
+    ///   21. `[writable]` (optional) asks Account
     Withdraw(WithdrawInstruction),
 
     ///   reserved 2
@@ -152,9 +166,9 @@ pub enum AmmInstruction {
     ///   1. `[writable]` amm Account
     ///   2. `[]` $authority
     ///   3. `[writable]` amm open_orders Account
-    ///   4. `[writable]` amm target_orders Account
-    ///   5. `[writable]` pool_token_coin Amm Account to swap FROM or To,
-    ///   6. `[writable]` pool_token_pc Amm Account to swap FROM or To,
+    ///   4. `[writable]` pool_token_coin Amm Account to swap FROM or To,
+    ///   5. `[writable]` pool_token_pc Amm Account to swap FROM or To,
+    ///   6. `[]` model data Account
     ///   7. `[]` serum dex program id
     ///   8. `[writable]` serum market Account. serum_dex program is the owner.
     ///   9. `[writable]` bids Account
@@ -168,6 +182,20 @@ pub enum AmmInstruction {
     ///   17. `[singer]` user owner Account
     SwapBaseIn(SwapInstructionBaseIn),
 
+    ///   Creates the target orders, lp mint and vaults of a new AmmInfo.
+    ///
+    ///   0. `[]` Spl Token program id
+    ///   1. `[]` Sys program id
+    ///   2. `[]` Rent program id
+    ///   3. `[writable]` amm target_orders Account
+    ///   4. `[]` $authority
+    ///   5. `[writable]` pool lp mint address
+    ///   6. `[]` coin mint address
+    ///   7. `[]` pc mint address
+    ///   8. `[writable]` pool_token_coin Account
+    ///   9. `[writable]` pool_token_pc Account
+    ///   10. `[]` serum market Account
+    ///   11. `[writable, signer]` user wallet Account
     PreInitialize(PreInitializeInstruction),
 
     /// Swap coin or pc from pool, base amount_out with a slippage of max_amount_in
@@ -176,9 +204,9 @@ pub enum AmmInstruction {
     ///   1. `[writable]` amm Account
     ///   2. `[]` $authority
     ///   3. `[writable]` amm open_orders Account
-    ///   4. `[writable]` amm target_orders Account
-    ///   5. `[writable]` pool_token_coin Amm Account to swap FROM or To,
-    ///   6. `[writable]` pool_token_pc Amm Account to swap FROM or To,
+    ///   4. `[writable]` pool_token_coin Amm Account to swap FROM or To,
+    ///   5. `[writable]` pool_token_pc Amm Account to swap FROM or To,
+    ///   6. `[]` model data Account
     ///   7. `[]` serum dex program id
     ///   8. `[writable]` serum market Account. serum_dex program is the owner.
     ///   9. `[writable]` bids Account
@@ -243,12 +271,12 @@ impl AmmInstruction {
                     amount_out,
                 })
             }
-            _ => return Err(ProgramError::InvalidInstructionData.into()),
+            _ => return Err(ProgramError::InvalidInstructionData),
         })
     }
 
     fn unpack_u8(input: &[u8]) -> Result<(u8, &[u8]), ProgramError> {
-        if input.len() >= 1 {
+        if !input.is_empty() {
             let (amount, rest) = input.split_at(1);
             let amount = amount
                 .get(..1)
@@ -257,7 +285,7 @@ impl AmmInstruction {
                 .ok_or(ProgramError::InvalidInstructionData)?;
             Ok((amount, rest))
         } else {
-            Err(ProgramError::InvalidInstructionData.into())
+            Err(ProgramError::InvalidInstructionData)
         }
     }
 
@@ -271,14 +299,14 @@ impl AmmInstruction {
                 .ok_or(ProgramError::InvalidInstructionData)?;
             Ok((amount, rest))
         } else {
-            Err(ProgramError::InvalidInstructionData.into())
+            Err(ProgramError::InvalidInstructionData)
         }
     }
 
     /// Packs a [AmmInstruction](enum.AmmInstruction.html) into a byte buffer.
     pub fn pack(&self) -> Result<Vec<u8>, ProgramError> {
         let mut buf = Vec::with_capacity(size_of::<Self>());
-        match &*self {
+        match self {
             Self::Initialize(InitializeInstruction { nonce, open_time }) => {
                 buf.push(0);
                 buf.push(*nonce);
@@ -321,7 +349,7 @@ impl AmmInstruction {
                 buf.extend_from_slice(&amount_out.to_le_bytes());
             }
 
-            _ => return Err(ProgramError::InvalidInstructionData.into()),
+            _ => return Err(ProgramError::InvalidInstructionData),
         }
         Ok(buf)
     }
@@ -569,7 +597,8 @@ pub fn withdraw(
     })
 }
 
-/// Creates a 'swap base in' instruction.
+/// Creates a 'swap base in' instruction. Fails with `InvalidArgument` on a zero
+/// `minimum_amount_out`, which would accept any swap output.
 pub fn swap_base_in(
     program_id: &Pubkey,
     amm_id: &Pubkey,
@@ -593,6 +622,9 @@ pub fn swap_base_in(
     amount_in: u64,
     minimum_amount_out: u64,
 ) -> Result<Instruction, ProgramError> {
+    if minimum_amount_out == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
     let data = AmmInstruction::SwapBaseIn(SwapInstructionBaseIn {
         amount_in,
         minimum_amount_out,
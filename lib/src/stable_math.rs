@@ -0,0 +1,245 @@
+//! Stable swap curve.
+//!
+//! The stable program prices swaps on a curve sampled in its model data
+//! account, in table units. The reserves are converted to table units by a
+//! ratio, `real * multiplier / table` at the point of the curve where `x / y`
+//! is the reserves' coin / pc, and the curve is interpolated linearly between
+//! the samples. All the steps are integer and rounded against the user: the
+//! output of a swap is rounded down and its input rounded up.
+
+use solana_program::program_error::ProgramError;
+use std::convert::TryInto;
+
+/// Number of samples of the model data account.
+pub const ELEMENT_SIZE: usize = 50_000;
+/// Length of the model data account.
+pub const MODEL_DATA_LEN: usize = 32 + ELEMENT_SIZE * 24;
+
+/// Curve sample.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DataElement {
+    pub x: u64,
+    pub y: u64,
+    pub price: u64,
+}
+
+/// Model data account of the stable program, shared by the stable pools.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ModelData {
+    pub account_type: u64,
+    pub status: u64,
+    /// scale of the samples
+    pub multiplier: u64,
+    /// number of samples in use
+    pub valid_data_count: u64,
+    /// samples with `x` increasing and `y` decreasing
+    pub elements: Vec<DataElement>,
+}
+
+impl ModelData {
+    /// Decodes the model data account data.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        if input.len() < MODEL_DATA_LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let u64_at =
+            |offset: usize| u64::from_le_bytes(input[offset..offset + 8].try_into().unwrap());
+        let valid_data_count = u64_at(24);
+        if valid_data_count < 2 || valid_data_count as usize > ELEMENT_SIZE {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let elements = (0..valid_data_count as usize)
+            .map(|i| {
+                let offset = 32 + i * 24;
+                DataElement {
+                    x: u64_at(offset),
+                    y: u64_at(offset + 8),
+                    price: u64_at(offset + 16),
+                }
+            })
+            .collect();
+        Ok(Self {
+            account_type: u64_at(0),
+            status: u64_at(8),
+            multiplier: u64_at(16),
+            valid_data_count,
+            elements,
+        })
+    }
+
+    /// `x * multiplier / y` of a sample, increasing along the curve.
+    fn sample_ratio(&self, i: usize) -> u128 {
+        let element = &self.elements[i];
+        if element.y == 0 {
+            return u128::MAX;
+        }
+        element.x as u128 * self.multiplier as u128 / element.y as u128
+    }
+
+    /// Index of the segment holding the point of the curve at the coin and pc
+    /// reserves, where `x * multiplier / y` is `coin * multiplier / pc`.
+    fn segment_at_reserves(&self, coin_reserve: u64, pc_reserve: u64) -> Option<usize> {
+        if coin_reserve == 0 || pc_reserve == 0 {
+            return None;
+        }
+        let target = coin_reserve as u128 * self.multiplier as u128 / pc_reserve as u128;
+        self.segment(|i| self.sample_ratio(i) <= target)
+    }
+
+    /// Ratio of the real amounts to the table amounts times the multiplier,
+    /// `coin * multiplier / x` at the point of the curve where `x / y` is
+    /// `coin / pc`, `x` rounded down.
+    fn ratio(&self, coin_reserve: u64, pc_reserve: u64) -> Option<u128> {
+        let i = self.segment_at_reserves(coin_reserve, pc_reserve)?;
+        let (a, b) = (&self.elements[i], &self.elements[i + 1]);
+        let (coin, pc) = (coin_reserve as u128, pc_reserve as u128);
+        let dx = (b.x as u128).checked_sub(a.x as u128)?;
+        let dy = (a.y as u128).checked_sub(b.y as u128)?;
+        // (a.x + s * dx) * pc = (a.y - s * dy) * coin
+        let numerator = (a.y as u128 * coin).saturating_sub(a.x as u128 * pc);
+        let denominator = (dx * pc).checked_add(dy * coin)?;
+        let x = dx.checked_mul(numerator)? / denominator + a.x as u128;
+        coin.checked_mul(self.multiplier as u128)?
+            .checked_div(x)
+            .filter(|ratio| *ratio > 0)
+    }
+
+    /// `y` of the curve at `x`, rounded up.
+    fn y_at(&self, x: u128) -> Option<u128> {
+        let i = self.segment(|i| self.elements[i].x as u128 <= x)?;
+        let (a, b) = (&self.elements[i], &self.elements[i + 1]);
+        let dy = (a.y as u128).checked_sub(b.y as u128)?;
+        let decrease = (x - a.x as u128) * dy / (b.x - a.x) as u128;
+        Some(a.y as u128 - decrease)
+    }
+
+    /// `x` of the curve at `y`, rounded up.
+    fn x_at(&self, y: u128) -> Option<u128> {
+        let i = self.segment(|i| self.elements[i].y as u128 >= y)?;
+        let (a, b) = (&self.elements[i], &self.elements[i + 1]);
+        let dy = (a.y as u128).checked_sub(b.y as u128)?;
+        let dx = (b.x as u128).checked_sub(a.x as u128)?;
+        let increase = ((a.y as u128 - y) * dx + dy - 1) / dy;
+        Some(a.x as u128 + increase)
+    }
+
+    /// Index of the segment `[i, i + 1]` where `before` turns false, `before`
+    /// being true at the first samples then false.
+    fn segment(&self, before: impl Fn(usize) -> bool) -> Option<usize> {
+        let last = self.elements.len().checked_sub(1)?;
+        if !before(0) || before(last) {
+            return None;
+        }
+        // before(low) && !before(high)
+        let (mut low, mut high) = (0, last);
+        while high - low > 1 {
+            let mid = (low + high) / 2;
+            if before(mid) {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        Some(low)
+    }
+
+    /// Table amount of a real `amount`, rounded up if `round_up`.
+    fn real_to_table(&self, amount: u64, ratio: u128, round_up: bool) -> u128 {
+        let amount = amount as u128 * self.multiplier as u128;
+        amount / ratio + (round_up && amount % ratio > 0) as u128
+    }
+
+    /// Real amount of a table `amount`, rounded up if `round_up`.
+    fn table_to_real(&self, amount: u128, ratio: u128, round_up: bool) -> Option<u64> {
+        let amount = amount.checked_mul(ratio)?;
+        let multiplier = self.multiplier as u128;
+        let amount = if round_up {
+            amount.checked_add(multiplier - 1)? / multiplier
+        } else {
+            amount / multiplier
+        };
+        amount.try_into().ok()
+    }
+
+    /// Marginal price of the curve before fee at the coin and pc reserves, in
+    /// pc per coin, the slope of the curve segment the reserves are on.
+    pub fn marginal_price(&self, coin_reserve: u64, pc_reserve: u64) -> Option<f64> {
+        let i = self.segment_at_reserves(coin_reserve, pc_reserve)?;
+        let (a, b) = (&self.elements[i], &self.elements[i + 1]);
+        // the reserves are the curve amounts scaled alike, the slope is kept
+        let price = (a.y as f64 - b.y as f64) / (b.x as f64 - a.x as f64);
+        (price.is_finite() && price > 0.0).then_some(price)
+    }
+
+    /// Output amount of a swap of `amount_in` after fee, between the coin and
+    /// pc reserves, from coin to pc if `coin_to_pc`.
+    pub fn get_amount_out(
+        &self,
+        coin_reserve: u64,
+        pc_reserve: u64,
+        amount_in: u64,
+        coin_to_pc: bool,
+    ) -> Result<u64, ProgramError> {
+        if coin_reserve == 0 || pc_reserve == 0 {
+            return Err(ProgramError::InsufficientFunds);
+        }
+        let ratio = self
+            .ratio(coin_reserve, pc_reserve)
+            .ok_or(ProgramError::InvalidArgument)?;
+        let x = self.real_to_table(coin_reserve, ratio, false);
+        let y = self.real_to_table(pc_reserve, ratio, false);
+        let amount_in = self.real_to_table(amount_in, ratio, false);
+        let amount_out = if coin_to_pc {
+            let y2 = self
+                .y_at(x + amount_in)
+                .ok_or(ProgramError::InsufficientFunds)?;
+            y.saturating_sub(y2)
+        } else {
+            let x2 = self
+                .x_at(y + amount_in)
+                .ok_or(ProgramError::InsufficientFunds)?;
+            x.saturating_sub(x2)
+        };
+        self.table_to_real(amount_out, ratio, false)
+            .ok_or(ProgramError::InvalidArgument)
+    }
+
+    /// Input amount before fee of a swap for `amount_out`, between the coin
+    /// and pc reserves, from coin to pc if `coin_to_pc`.
+    pub fn get_amount_in(
+        &self,
+        coin_reserve: u64,
+        pc_reserve: u64,
+        amount_out: u64,
+        coin_to_pc: bool,
+    ) -> Result<u64, ProgramError> {
+        let reserve_out = if coin_to_pc { pc_reserve } else { coin_reserve };
+        if coin_reserve == 0 || pc_reserve == 0 || amount_out >= reserve_out {
+            return Err(ProgramError::InsufficientFunds);
+        }
+        let ratio = self
+            .ratio(coin_reserve, pc_reserve)
+            .ok_or(ProgramError::InvalidArgument)?;
+        let x = self.real_to_table(coin_reserve, ratio, false);
+        let y = self.real_to_table(pc_reserve, ratio, false);
+        let amount_out = self.real_to_table(amount_out, ratio, true);
+        let amount_in = if coin_to_pc {
+            let x2 = y
+                .checked_sub(amount_out)
+                .and_then(|y2| self.x_at(y2))
+                .ok_or(ProgramError::InsufficientFunds)?;
+            x2.saturating_sub(x)
+        } else {
+            let y2 = x
+                .checked_sub(amount_out)
+                .and_then(|x2| self.y_at(x2))
+                .ok_or(ProgramError::InsufficientFunds)?;
+            y2.saturating_sub(y)
+        };
+        self.table_to_real(amount_in, ratio, true)
+            .ok_or(ProgramError::InvalidArgument)
+    }
+}
@@ -1,16 +1,21 @@
 //! State transition types
 
-use solana_program::pubkey::Pubkey;
+use crate::market_stats::{MarketState, OpenOrders};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "client", derive(Debug))]
 #[derive(Clone, Copy)]
 pub struct TargetOrder {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub plan_price: u128,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub plan_vol: u128,
     pub place_price: u64,
     pub place_vol: u64,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "client", derive(Debug))]
 #[derive(Clone, Copy)]
 pub struct TargetOrders {
@@ -28,30 +33,38 @@ pub struct TargetOrders {
     pub valid_buy_order_num: u64,
     pub valid_sell_order_num: u64,
 
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub stable_mid_price: u128,
 
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string_array"))]
     pub padding0: [u128; 10],
     // padding, Unused bytes for future upgrades.
     pub padding: [u64; 32],
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd, Debug, Default)]
 pub struct Order {
     pub price: u64,
     pub qty: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub id: u128,
     pub slot: u64,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "client", derive(Debug))]
 #[derive(Clone, Copy)]
 pub struct MyOrders {
     pub buy_len: u64,
     pub sell_len: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::array"))]
     pub buy_orders: [Order; 64],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::array"))]
     pub sell_orders: [Order; 64],
 }
 
+#[cfg_attr(feature = "client", derive(Debug))]
 #[repr(u64)]
 pub enum AmmStatus {
     Uninitialized = 0u64,
@@ -68,6 +81,24 @@ pub enum AmmStatus {
     SwapPunish = 7u64,
 }
 
+impl AmmStatus {
+    /// Converts the `AmmInfo.status` value into an [AmmStatus](enum.AmmStatus.html).
+    pub fn from_u64(status: u64) -> Option<Self> {
+        match status {
+            0 => Some(AmmStatus::Uninitialized),
+            1 => Some(AmmStatus::Initialized),
+            2 => Some(AmmStatus::Disabled),
+            3 => Some(AmmStatus::WithdrawOnly),
+            4 => Some(AmmStatus::LiquidityOnly),
+            5 => Some(AmmStatus::OrderBookOnly),
+            6 => Some(AmmStatus::SwapOnly),
+            7 => Some(AmmStatus::SwapPunish),
+            _ => None,
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Fees {
@@ -93,6 +124,7 @@ pub struct Fees {
     pub swap_fee_denominator: u64,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct OutPutData {
@@ -114,13 +146,17 @@ pub struct OutPutData {
     pub orderbook_to_init_time: u64,
 
     /// swap coin in amount
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub swap_coin_in_amount: u128,
     /// swap pc out amount
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub swap_pc_out_amount: u128,
 
     /// swap pc in amount
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub swap_pc_in_amount: u128,
     /// swap coin out amount
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub swap_coin_out_amount: u128,
 
     /// swap pc fee
@@ -129,6 +165,7 @@ pub struct OutPutData {
     pub swap_coin_fee: u64,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "client", derive(Debug))]
 #[repr(C)]
 #[derive(Clone, Copy, PartialEq)]
@@ -182,27 +219,65 @@ pub struct AmmInfo {
     /// data calc to output
     pub out_put: OutPutData,
     /// Token coin vault
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub coin_vault: Pubkey,
     /// Token pc vault
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub pc_vault: Pubkey,
     /// Coin mint
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub coin_mint: Pubkey,
     /// Pc mint
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub pc_mint: Pubkey,
     /// lp mint
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub lp_mint: Pubkey,
     /// model data account key
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub model_data_key: Pubkey,
     /// open_orders key
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub open_orders: Pubkey,
     /// serum market key
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub serum_market: Pubkey,
     /// serum program key
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub serum_program: Pubkey,
     /// target_orders key
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub target_orders: Pubkey,
     /// amm admin key
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub amm_admin: Pubkey,
     /// padding, Unused bytes for future upgrades.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::array"))]
     pub padding: [u64; 64],
 }
+
+impl AmmInfo {
+    /// Checks the amm coin and pc lot sizes against its market.
+    pub fn check_market_lot_sizes(&self, market: &MarketState) -> Result<(), ProgramError> {
+        market.check_lot_sizes(self.coin_lot_size, self.pc_lot_size)
+    }
+
+    /// Calculates the pool (coin, pc) reserves the program swaps against,
+    /// the vault and open orders funds without the pnl not yet taken.
+    pub fn effective_reserves(
+        &self,
+        coin_vault_amount: u64,
+        pc_vault_amount: u64,
+        open_orders: &OpenOrders,
+    ) -> Result<(u64, u64), ProgramError> {
+        let coin_amount = coin_vault_amount
+            .checked_add(open_orders.native_coin_total)
+            .and_then(|amount| amount.checked_sub(self.out_put.need_take_pnl_coin))
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let pc_amount = pc_vault_amount
+            .checked_add(open_orders.native_pc_total)
+            .and_then(|amount| amount.checked_sub(self.out_put.need_take_pnl_pc))
+            .ok_or(ProgramError::InvalidAccountData)?;
+        Ok((coin_amount, pc_amount))
+    }
+}
@@ -7,9 +7,13 @@ use solana_program::instruction::Instruction;
 use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
 use solana_program::sysvar;
+use std::convert::TryInto;
 use std::mem::size_of;
 
+solana_program::declare_id!("EhhTKczWMGQt46ynNeRX1WfeagwwJd7ufHvCDjRxjo5Q");
+
 /// Inital values for the Stake Pool
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct InitArgs {
@@ -18,6 +22,7 @@ pub struct InitArgs {
     /// reward per slot
     pub reward_per_slot: u64,
     /// ignore
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub ignore: u128,
 }
 
@@ -26,15 +31,47 @@ pub struct InitArgs {
 #[derive(Clone, Debug, PartialEq)]
 pub enum StakePoolInstruction {
     ///   Initializes a new StakePool.
+    ///
+    ///   0. `[writable, signer]` New stake pool Account to create.
+    ///   1. `[]` $authority derived from `create_program_address(&[stake pool Account, &[nonce]])`
+    ///   2. `[]` pool lp token vault
+    ///   3. `[]` pool reward token vault
+    ///   4. `[]` Clock sysvar
     Initialize(InitArgs),
 
     /// Deposit lp into the pool.
+    ///
+    ///   0. `[writable]` stake pool Account
+    ///   1. `[]` $authority derived from `create_program_address(&[stake pool Account, &[nonce]])`
+    ///   2. `[writable]` user staker info Account
+    ///   3. `[signer]` user owner Account
+    ///   4. `[writable]` user lp token Account to deposit from
+    ///   5. `[writable]` pool lp token vault
+    ///   6. `[writable]` user reward token Account
+    ///   7. `[writable]` pool reward token vault
+    ///   8. `[]` Clock sysvar
+    ///   9. `[]` Spl Token program id
     Deposit(u64),
 
     /// Withdraw lp token from the pool .
+    ///
+    ///   0. `[writable]` stake pool Account
+    ///   1. `[]` $authority derived from `create_program_address(&[stake pool Account, &[nonce]])`
+    ///   2. `[writable]` user staker info Account
+    ///   3. `[signer]` user owner Account
+    ///   4. `[writable]` user lp token Account to withdraw to
+    ///   5. `[writable]` pool lp token vault
+    ///   6. `[writable]` user reward token Account
+    ///   7. `[writable]` pool reward token vault
+    ///   8. `[]` Clock sysvar
+    ///   9. `[]` Spl Token program id
     Withdraw(u64),
 
     /// Update Pool acc
+    ///
+    ///   0. `[writable]` stake pool Account
+    ///   1. `[writable]` pool lp token vault
+    ///   2. `[]` Clock sysvar
     UpdatePool,
 
     /// Reserved0
@@ -47,108 +84,168 @@ pub enum StakePoolInstruction {
     Reserved2,
 
     /// EmergencyWithdraw
+    ///
+    ///   0. `[writable]` stake pool Account
+    ///   1. `[]` $authority derived from `create_program_address(&[stake pool Account, &[nonce]])`
+    ///   2. `[writable]` user staker info Account
+    ///   3. `[signer]` user owner Account
+    ///   4. `[writable]` user lp token Account to withdraw to
+    ///   5. `[writable]` pool lp token vault
+    ///   6. `[]` Spl Token program id
     EmergencyWithdraw,
 
     /// Reserved3
     Reserved3,
 
     /// CreateAssociatedAccount
+    ///
+    ///   0. `[writable]` stake pool Account
+    ///   1. `[writable]` associated user staker info Account to create.
+    ///   2. `[signer]` user owner Account
+    ///   3. `[]` Sys program id
+    ///   4. `[]` Rent program id
     CreateAssociatedAccount,
 
     /// Deposit lp token into the pool v2
+    ///
+    ///   0. `[writable]` stake pool Account
+    ///   1. `[]` $authority derived from `create_program_address(&[stake pool Account, &[nonce]])`
+    ///   2. `[writable]` user staker info Account
+    ///   3. `[signer]` user owner Account
+    ///   4. `[writable]` user lp token Account to deposit from
+    ///   5. `[writable]` pool lp token vault
+    ///   6. `[writable]` user reward token Account
+    ///   7. `[writable]` pool reward token vault
+    ///   8. `[]` Clock sysvar
+    ///   9. `[]` Spl Token program id
+    ///   10. `[writable, signer]` (optional) user staker info v1 Account to migrate
     DepositV2(u64),
 
     /// Withdraw lp token from the pool v2
+    ///
+    ///   0. `[writable]` stake pool Account
+    ///   1. `[]` $authority derived from `create_program_address(&[stake pool Account, &[nonce]])`
+    ///   2. `[writable]` user staker info Account
+    ///   3. `[signer]` user owner Account
+    ///   4. `[writable]` user lp token Account to withdraw to
+    ///   5. `[writable]` pool lp token vault
+    ///   6. `[writable]` user reward token Account
+    ///   7. `[writable]` pool reward token vault
+    ///   8. `[]` Clock sysvar
+    ///   9. `[]` Spl Token program id
+    ///   10. `[writable, signer]` (optional) user staker info v1 Account to migrate
     WithdrawV2(u64),
 }
 
 impl StakePoolInstruction {
     /// Deserializes a byte buffer into an [StakePoolInstruction](enum.StakePoolInstruction.html).
     pub fn deserialize(input: &[u8]) -> Result<Self, ProgramError> {
-        if input.len() < size_of::<u8>() {
-            return Err(ProgramError::InvalidAccountData);
-        }
-        Ok(match input[0] {
+        let (&tag, rest) = input
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        Ok(match tag {
             0 => {
-                let val: &InitArgs = unpack(input)?;
-                Self::Initialize(*val)
+                let (nonce, rest) = Self::unpack_u64(rest)?;
+                let (reward_per_slot, rest) = Self::unpack_u64(rest)?;
+                let (ignore, _rest) = Self::unpack_u128(rest)?;
+                Self::Initialize(InitArgs {
+                    nonce,
+                    reward_per_slot,
+                    ignore,
+                })
             }
             1 => {
-                let val: &u64 = unpack(input)?;
-                Self::Deposit(*val)
+                let (amount, _rest) = Self::unpack_u64(rest)?;
+                Self::Deposit(amount)
             }
             2 => {
-                let val: &u64 = unpack(input)?;
-                Self::Withdraw(*val)
+                let (amount, _rest) = Self::unpack_u64(rest)?;
+                Self::Withdraw(amount)
             }
             3 => Self::UpdatePool,
-
             7 => Self::EmergencyWithdraw,
-
             9 => Self::CreateAssociatedAccount,
             10 => {
-                let val: &u64 = unpack(input)?;
-                Self::DepositV2(*val)
+                let (amount, _rest) = Self::unpack_u64(rest)?;
+                Self::DepositV2(amount)
             }
             11 => {
-                let val: &u64 = unpack(input)?;
-                Self::WithdrawV2(*val)
+                let (amount, _rest) = Self::unpack_u64(rest)?;
+                Self::WithdrawV2(amount)
             }
-            _ => return Err(ProgramError::InvalidAccountData),
+            _ => return Err(ProgramError::InvalidInstructionData),
         })
     }
 
+    fn unpack_u64(input: &[u8]) -> Result<(u64, &[u8]), ProgramError> {
+        if input.len() >= 8 {
+            let (amount, rest) = input.split_at(8);
+            let amount = amount
+                .try_into()
+                .map(u64::from_le_bytes)
+                .map_err(|_| ProgramError::InvalidInstructionData)?;
+            Ok((amount, rest))
+        } else {
+            Err(ProgramError::InvalidInstructionData)
+        }
+    }
+
+    fn unpack_u128(input: &[u8]) -> Result<(u128, &[u8]), ProgramError> {
+        if input.len() >= 16 {
+            let (amount, rest) = input.split_at(16);
+            let amount = amount
+                .try_into()
+                .map(u128::from_le_bytes)
+                .map_err(|_| ProgramError::InvalidInstructionData)?;
+            Ok((amount, rest))
+        } else {
+            Err(ProgramError::InvalidInstructionData)
+        }
+    }
+
     /// Serializes an [StakePoolInstruction](enum.StakePoolInstruction.html) into a byte buffer.
     pub fn serialize(&self) -> Result<Vec<u8>, ProgramError> {
-        let mut output = vec![0u8; size_of::<StakePoolInstruction>()];
+        let mut buf = Vec::with_capacity(size_of::<Self>());
         match self {
-            Self::Initialize(init) => {
-                output[0] = 0;
-                #[allow(clippy::cast_ptr_alignment)]
-                let value = unsafe { &mut *(&mut output[1] as *mut u8 as *mut InitArgs) };
-                *value = *init;
+            Self::Initialize(InitArgs {
+                nonce,
+                reward_per_slot,
+                ignore,
+            }) => {
+                buf.push(0);
+                buf.extend_from_slice(&nonce.to_le_bytes());
+                buf.extend_from_slice(&reward_per_slot.to_le_bytes());
+                buf.extend_from_slice(&ignore.to_le_bytes());
             }
-            Self::Deposit(val) => {
-                output[0] = 1;
-                #[allow(clippy::cast_ptr_alignment)]
-                let value = unsafe { &mut *(&mut output[1] as *mut u8 as *mut u64) };
-                *value = *val;
+            Self::Deposit(amount) => {
+                buf.push(1);
+                buf.extend_from_slice(&amount.to_le_bytes());
             }
-            Self::Withdraw(val) => {
-                output[0] = 2;
-                #[allow(clippy::cast_ptr_alignment)]
-                let value = unsafe { &mut *(&mut output[1] as *mut u8 as *mut u64) };
-                *value = *val;
+            Self::Withdraw(amount) => {
+                buf.push(2);
+                buf.extend_from_slice(&amount.to_le_bytes());
             }
-            Self::UpdatePool => {
-                output[0] = 3;
+            Self::UpdatePool => buf.push(3),
+            Self::EmergencyWithdraw => buf.push(7),
+            Self::CreateAssociatedAccount => buf.push(9),
+            Self::DepositV2(amount) => {
+                buf.push(10);
+                buf.extend_from_slice(&amount.to_le_bytes());
             }
-
-            Self::EmergencyWithdraw => {
-                output[0] = 7;
-            }
-
-            Self::CreateAssociatedAccount => {
-                output[0] = 9;
-            }
-            Self::DepositV2(val) => {
-                output[0] = 10;
-                let value = unsafe { &mut *(&mut output[1] as *mut u8 as *mut u64) };
-                *value = *val;
-            }
-            Self::WithdrawV2(val) => {
-                output[0] = 11;
-                let value = unsafe { &mut *(&mut output[1] as *mut u8 as *mut u64) };
-                *value = *val;
+            Self::WithdrawV2(amount) => {
+                buf.push(11);
+                buf.extend_from_slice(&amount.to_le_bytes());
             }
-
-            _ => return Err(ProgramError::InvalidAccountData),
+            _ => return Err(ProgramError::InvalidInstructionData),
         }
-        Ok(output)
+        Ok(buf)
     }
 }
 
 /// Unpacks a reference from a bytes buffer.
+#[deprecated(
+    note = "casts unaligned instruction data, use `StakePoolInstruction::deserialize` instead"
+)]
 pub fn unpack<T>(input: &[u8]) -> Result<&T, ProgramError> {
     if input.len() < size_of::<u8>() + size_of::<T>() {
         return Err(ProgramError::InvalidAccountData);
@@ -253,6 +350,84 @@ pub fn withdraw(
     })
 }
 
+/// Creates a 'DepositV2' instruction.
+pub fn deposit_v2(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    pool_authority: &Pubkey,
+    staker_info: &Pubkey,
+    staker_owner: &Pubkey,
+    src_lp_token: &Pubkey,
+    vault_lp_token: &Pubkey,
+    dest_reward_token: &Pubkey,
+    vault_reward_token: &Pubkey,
+    token_program_id: &Pubkey,
+    staker_info_v1: Option<&Pubkey>,
+    amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let args = StakePoolInstruction::DepositV2(amount);
+    let data = args.serialize()?;
+    let mut accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*pool_authority, false),
+        AccountMeta::new(*staker_info, false),
+        AccountMeta::new_readonly(*staker_owner, true),
+        AccountMeta::new(*src_lp_token, false),
+        AccountMeta::new(*vault_lp_token, false),
+        AccountMeta::new(*dest_reward_token, false),
+        AccountMeta::new(*vault_reward_token, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(*token_program_id, false),
+    ];
+    if let Some(staker_info_v1) = staker_info_v1 {
+        accounts.push(AccountMeta::new(*staker_info_v1, true));
+    }
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a 'withdrawV2' instruction.
+pub fn withdraw_v2(
+    program_id: &Pubkey,
+    stake_pool: &Pubkey,
+    pool_authority: &Pubkey,
+    staker_info: &Pubkey,
+    staker_owner: &Pubkey,
+    dest_lp_token: &Pubkey,
+    vault_lp_token: &Pubkey,
+    dest_reward_token: &Pubkey,
+    vault_reward_token: &Pubkey,
+    token_program_id: &Pubkey,
+    staker_info_v1: Option<&Pubkey>,
+    amount: u64,
+) -> Result<Instruction, ProgramError> {
+    let args = StakePoolInstruction::WithdrawV2(amount);
+    let data = args.serialize()?;
+    let mut accounts = vec![
+        AccountMeta::new(*stake_pool, false),
+        AccountMeta::new_readonly(*pool_authority, false),
+        AccountMeta::new(*staker_info, false),
+        AccountMeta::new_readonly(*staker_owner, true),
+        AccountMeta::new(*dest_lp_token, false),
+        AccountMeta::new(*vault_lp_token, false),
+        AccountMeta::new(*dest_reward_token, false),
+        AccountMeta::new(*vault_reward_token, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(*token_program_id, false),
+    ];
+    if let Some(staker_info_v1) = staker_info_v1 {
+        accounts.push(AccountMeta::new(*staker_info_v1, true));
+    }
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
 /// Creates a 'emergencyWithdraw' instruction.
 pub fn emergency_withdraw(
     program_id: &Pubkey,
@@ -3,6 +3,7 @@
 use solana_program::pubkey::Pubkey;
 
 /// Initialized program details.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct StakePool {
@@ -11,16 +12,20 @@ pub struct StakePool {
     /// Nonce used in program address.
     pub nonce: u64,
     /// lp token vault fro present all lp
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub lp_vault: Pubkey,
     /// reward vault
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub reward_vault: Pubkey,
     /// owner
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub owner: Pubkey,
     /// padding
     pub padding: [u64; 6],
     /// total reward multified  decimals
     pub reward_total: u64,
     /// acc ray per share
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub acc_ray_per_share: u128,
     /// Last slot pool updated
     pub last_pool_update_slot: u64,
@@ -29,6 +34,7 @@ pub struct StakePool {
 }
 
 /// Information about the singe  stake account
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct StakerInfo {
@@ -36,9 +42,11 @@ pub struct StakerInfo {
     pub state: u64,
 
     /// stake pool account
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub stake_pool: Pubkey,
 
     /// spl token owner account and havrest owner pubkey
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub staker_owner: Pubkey,
 
     /// staking balance in lp
@@ -49,6 +57,7 @@ pub struct StakerInfo {
 }
 
 /// Information about the singe  stake account
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct StakerInfoV2 {
@@ -56,15 +65,18 @@ pub struct StakerInfoV2 {
     pub state: u64,
 
     /// stake pool account
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub stake_pool: Pubkey,
 
     /// spl token owner account and havrest owner pubkey
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub staker_owner: Pubkey,
 
     /// staking balance in lp
     pub deposit_balance: u64,
 
     /// reward debt
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::string"))]
     pub reward_debt: u128,
 
     /// account type
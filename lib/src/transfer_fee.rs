@@ -0,0 +1,294 @@
+//! Token-2022 transfer fees.
+//!
+//! A mint with the transfer fee extension withholds part of every transfer in
+//! the destination account, so the amount arriving in a pool vault, or in the
+//! user account, is lower than the amount sent. The fee config is read from the
+//! raw mint account data, and the amounts are computed the same way as the
+//! Token-2022 program.
+
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use std::convert::TryInto;
+
+/// Length of an spl token mint.
+pub const MINT_LEN: usize = 82;
+/// Length of an spl token account, extended mints are padded to it.
+const ACCOUNT_LEN: usize = 165;
+/// Account type of a mint, stored right after the padding.
+const ACCOUNT_TYPE_MINT: u8 = 1;
+/// Tlv type of the transfer fee config extension.
+const TRANSFER_FEE_CONFIG_EXTENSION: u16 = 1;
+/// Tlv length of the transfer fee config extension.
+const TRANSFER_FEE_CONFIG_LEN: usize = 108;
+/// Basis points denominator, the fee rate cannot exceed it.
+pub const MAX_FEE_BASIS_POINTS: u16 = 10_000;
+
+/// Transfer fee of an epoch range.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TransferFee {
+    /// first epoch of the fee
+    pub epoch: u64,
+    /// maximum fee of a transfer
+    pub maximum_fee: u64,
+    /// fee rate, in basis points of the transfer amount
+    pub transfer_fee_basis_points: u16,
+}
+
+impl TransferFee {
+    fn read(input: &[u8]) -> Result<Self, ProgramError> {
+        let fee = Self {
+            epoch: u64::from_le_bytes(input[0..8].try_into().unwrap()),
+            maximum_fee: u64::from_le_bytes(input[8..16].try_into().unwrap()),
+            transfer_fee_basis_points: u16::from_le_bytes(input[16..18].try_into().unwrap()),
+        };
+        if fee.transfer_fee_basis_points > MAX_FEE_BASIS_POINTS {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(fee)
+    }
+
+    /// Fee withheld from a transfer of `pre_fee_amount`, rounded up and capped
+    /// by the maximum fee. `None` for a rate above `MAX_FEE_BASIS_POINTS`.
+    pub fn calculate_fee(&self, pre_fee_amount: u64) -> Option<u64> {
+        let basis_points = self.transfer_fee_basis_points as u128;
+        if basis_points > MAX_FEE_BASIS_POINTS as u128 {
+            return None;
+        }
+        if basis_points == 0 || pre_fee_amount == 0 {
+            return Some(0);
+        }
+        let fee = (pre_fee_amount as u128)
+            .checked_mul(basis_points)?
+            .checked_add(MAX_FEE_BASIS_POINTS as u128 - 1)?
+            / MAX_FEE_BASIS_POINTS as u128;
+        Some(u64::try_from(fee).ok()?.min(self.maximum_fee))
+    }
+
+    /// Smallest transfer amount leaving `post_fee_amount` once the fee is
+    /// withheld.
+    pub fn calculate_pre_fee_amount(&self, post_fee_amount: u64) -> Option<u64> {
+        let basis_points = self.transfer_fee_basis_points as u128;
+        match (basis_points, post_fee_amount) {
+            (0, _) => Some(post_fee_amount),
+            (_, 0) => Some(0),
+            (bps, _) if bps == MAX_FEE_BASIS_POINTS as u128 => {
+                post_fee_amount.checked_add(self.maximum_fee)
+            }
+            (bps, _) => {
+                let numerator =
+                    (post_fee_amount as u128).checked_mul(MAX_FEE_BASIS_POINTS as u128)?;
+                let denominator = (MAX_FEE_BASIS_POINTS as u128).checked_sub(bps)?;
+                let raw_pre_fee_amount = numerator
+                    .checked_add(denominator - 1)?
+                    .checked_div(denominator)?;
+                if raw_pre_fee_amount - post_fee_amount as u128 >= self.maximum_fee as u128 {
+                    post_fee_amount.checked_add(self.maximum_fee)
+                } else {
+                    u64::try_from(raw_pre_fee_amount).ok()
+                }
+            }
+        }
+    }
+
+    /// Fee withheld from the transfer leaving `post_fee_amount`.
+    pub fn calculate_inverse_fee(&self, post_fee_amount: u64) -> Option<u64> {
+        let pre_fee_amount = self.calculate_pre_fee_amount(post_fee_amount)?;
+        self.calculate_fee(pre_fee_amount)
+    }
+}
+
+/// Transfer fee config extension of a Token-2022 mint.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TransferFeeConfig {
+    /// authority allowed to set the fee
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::option_string"))]
+    pub transfer_fee_config_authority: Option<Pubkey>,
+    /// authority allowed to withdraw the withheld fees
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::option_string"))]
+    pub withdraw_withheld_authority: Option<Pubkey>,
+    /// fees withheld in the mint
+    pub withheld_amount: u64,
+    /// fee before `newer_transfer_fee.epoch`
+    pub older_transfer_fee: TransferFee,
+    /// fee from `newer_transfer_fee.epoch`
+    pub newer_transfer_fee: TransferFee,
+}
+
+impl TransferFeeConfig {
+    /// Reads the transfer fee config of a mint account data, `None` for an
+    /// spl token mint or a Token-2022 mint without the extension. Fails with
+    /// `InvalidAccountData` for a fee rate above `MAX_FEE_BASIS_POINTS`.
+    pub fn unpack_from_mint(input: &[u8]) -> Result<Option<Self>, ProgramError> {
+        if input.len() == MINT_LEN {
+            return Ok(None);
+        }
+        if input.len() <= ACCOUNT_LEN || input[ACCOUNT_LEN] != ACCOUNT_TYPE_MINT {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut tlv = &input[ACCOUNT_LEN + 1..];
+        while tlv.len() >= 4 {
+            let extension_type = u16::from_le_bytes(tlv[0..2].try_into().unwrap());
+            let length = u16::from_le_bytes(tlv[2..4].try_into().unwrap()) as usize;
+            let value = tlv
+                .get(4..4 + length)
+                .ok_or(ProgramError::InvalidAccountData)?;
+            match extension_type {
+                // uninitialized, the remaining data is padding
+                0 => break,
+                TRANSFER_FEE_CONFIG_EXTENSION => {
+                    if length != TRANSFER_FEE_CONFIG_LEN {
+                        return Err(ProgramError::InvalidAccountData);
+                    }
+                    return Self::read(value).map(Some);
+                }
+                _ => tlv = &tlv[4 + length..],
+            }
+        }
+        Ok(None)
+    }
+
+    fn read(input: &[u8]) -> Result<Self, ProgramError> {
+        let optional_pubkey = |bytes: &[u8]| {
+            let key = Pubkey::new_from_array(bytes.try_into().unwrap());
+            (key != Pubkey::default()).then_some(key)
+        };
+        Ok(Self {
+            transfer_fee_config_authority: optional_pubkey(&input[0..32]),
+            withdraw_withheld_authority: optional_pubkey(&input[32..64]),
+            withheld_amount: u64::from_le_bytes(input[64..72].try_into().unwrap()),
+            older_transfer_fee: TransferFee::read(&input[72..90])?,
+            newer_transfer_fee: TransferFee::read(&input[90..108])?,
+        })
+    }
+
+    /// Fee in effect at `epoch`.
+    pub fn get_epoch_fee(&self, epoch: u64) -> &TransferFee {
+        if epoch >= self.newer_transfer_fee.epoch {
+            &self.newer_transfer_fee
+        } else {
+            &self.older_transfer_fee
+        }
+    }
+
+    /// Fee withheld at `epoch` from a transfer of `pre_fee_amount`.
+    pub fn calculate_epoch_fee(&self, epoch: u64, pre_fee_amount: u64) -> Option<u64> {
+        self.get_epoch_fee(epoch).calculate_fee(pre_fee_amount)
+    }
+
+    /// Fee withheld at `epoch` from the transfer leaving `post_fee_amount`.
+    pub fn calculate_inverse_epoch_fee(&self, epoch: u64, post_fee_amount: u64) -> Option<u64> {
+        self.get_epoch_fee(epoch)
+            .calculate_inverse_fee(post_fee_amount)
+    }
+}
+
+/// Amount received from a transfer of `gross_amount`.
+pub fn net_amount(
+    config: Option<&TransferFeeConfig>,
+    epoch: u64,
+    gross_amount: u64,
+) -> Result<u64, ProgramError> {
+    let fee = match config {
+        Some(config) => config
+            .calculate_epoch_fee(epoch, gross_amount)
+            .ok_or(ProgramError::InvalidArgument)?,
+        None => 0,
+    };
+    gross_amount
+        .checked_sub(fee)
+        .ok_or(ProgramError::InvalidArgument)
+}
+
+/// Amount to transfer for `net_amount` to be received.
+pub fn gross_amount(
+    config: Option<&TransferFeeConfig>,
+    epoch: u64,
+    net_amount: u64,
+) -> Result<u64, ProgramError> {
+    let fee = match config {
+        Some(config) => config
+            .calculate_inverse_epoch_fee(epoch, net_amount)
+            .ok_or(ProgramError::InvalidArgument)?,
+        None => 0,
+    };
+    net_amount
+        .checked_add(fee)
+        .ok_or(ProgramError::InvalidArgument)
+}
+
+/// Transfer fees of both legs of a swap.
+///
+/// The input leg is the transfer from the user account to the pool vault, and
+/// the output leg the transfer from the pool vault to the user account. Pool
+/// quotes are in vault amounts, while the user amounts and the slippage bounds
+/// checked by the programs are the amounts sent and received by the user.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SwapTransferFees {
+    /// fee config of the input mint
+    pub input: Option<TransferFeeConfig>,
+    /// fee config of the output mint
+    pub output: Option<TransferFeeConfig>,
+    /// epoch of the swap
+    pub epoch: u64,
+}
+
+impl SwapTransferFees {
+    /// Reads the fee configs of the input and output mint account data.
+    pub fn from_mints(
+        input_mint: &[u8],
+        output_mint: &[u8],
+        epoch: u64,
+    ) -> Result<Self, ProgramError> {
+        Ok(Self {
+            input: TransferFeeConfig::unpack_from_mint(input_mint)?,
+            output: TransferFeeConfig::unpack_from_mint(output_mint)?,
+            epoch,
+        })
+    }
+
+    /// Amount arriving in the input vault when the user sends `amount_in`.
+    pub fn input_net_amount(&self, amount_in: u64) -> Result<u64, ProgramError> {
+        net_amount(self.input.as_ref(), self.epoch, amount_in)
+    }
+
+    /// Amount the user sends for `vault_amount_in` to arrive in the input vault.
+    pub fn input_gross_amount(&self, vault_amount_in: u64) -> Result<u64, ProgramError> {
+        gross_amount(self.input.as_ref(), self.epoch, vault_amount_in)
+    }
+
+    /// Amount the user receives when the output vault sends `vault_amount_out`.
+    pub fn output_net_amount(&self, vault_amount_out: u64) -> Result<u64, ProgramError> {
+        net_amount(self.output.as_ref(), self.epoch, vault_amount_out)
+    }
+
+    /// Amount the output vault sends for the user to receive `amount_out`.
+    pub fn output_gross_amount(&self, amount_out: u64) -> Result<u64, ProgramError> {
+        gross_amount(self.output.as_ref(), self.epoch, amount_out)
+    }
+
+    /// Quotes a swap of an exact `amount_in` sent by the user, `swap` quoting
+    /// the vault amount out of a vault amount in. Returns the amount received
+    /// by the user.
+    pub fn quote_base_in(
+        &self,
+        amount_in: u64,
+        swap: impl FnOnce(u64) -> Result<u64, ProgramError>,
+    ) -> Result<u64, ProgramError> {
+        let vault_amount_out = swap(self.input_net_amount(amount_in)?)?;
+        self.output_net_amount(vault_amount_out)
+    }
+
+    /// Quotes a swap for an exact `amount_out` received by the user, `swap`
+    /// quoting the vault amount in of a vault amount out. Returns the amount
+    /// sent by the user.
+    pub fn quote_base_out(
+        &self,
+        amount_out: u64,
+        swap: impl FnOnce(u64) -> Result<u64, ProgramError>,
+    ) -> Result<u64, ProgramError> {
+        let vault_amount_in = swap(self.output_gross_amount(amount_out)?)?;
+        self.input_gross_amount(vault_amount_in)
+    }
+}
@@ -0,0 +1,160 @@
+//! Validation of instruction accounts against the decoded pool state.
+//!
+//! The AMM program fails a transaction with a wrong pool account, without
+//! telling which one. The `validate_*_accounts` functions check the accounts
+//! of an instruction before it is sent, or before a CPI, and report every
+//! account not matching the pool.
+
+#![allow(clippy::too_many_arguments)]
+
+use crate::{amm_instruction, amm_stats::AmmInfo};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use std::fmt;
+
+/// Account of an instruction not matching the pool state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccountMismatch {
+    /// name of the instruction account
+    pub account: &'static str,
+    /// key expected from the pool state, the default key if it cannot be
+    /// derived
+    pub expected: Pubkey,
+    /// key given to the instruction
+    pub actual: Pubkey,
+}
+
+impl fmt::Display for AccountMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: expected {}, got {}",
+            self.account, self.expected, self.actual
+        )
+    }
+}
+
+/// Mismatches found by a validation, empty if all the accounts match.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccountsReport {
+    pub mismatches: Vec<AccountMismatch>,
+}
+
+impl AccountsReport {
+    fn check(&mut self, account: &'static str, expected: &Pubkey, actual: &Pubkey) {
+        if expected != actual {
+            self.mismatches.push(AccountMismatch {
+                account,
+                expected: *expected,
+                actual: *actual,
+            });
+        }
+    }
+
+    fn check_authority(&mut self, amm_program: &Pubkey, amm: &AmmInfo, amm_authority: &Pubkey) {
+        let expected =
+            amm_instruction::get_amm_authority(amm_program, amm.nonce).unwrap_or_default();
+        self.check("amm_authority", &expected, amm_authority);
+    }
+
+    /// Whether all the accounts match.
+    pub fn is_valid(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    /// Fails with `InvalidArgument` if an account does not match.
+    pub fn into_result(self) -> Result<(), ProgramError> {
+        if self.is_valid() {
+            Ok(())
+        } else {
+            Err(ProgramError::InvalidArgument)
+        }
+    }
+}
+
+impl fmt::Display for AccountsReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_valid() {
+            return f.write_str("all accounts match");
+        }
+        for (i, mismatch) in self.mismatches.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}", mismatch)?;
+        }
+        Ok(())
+    }
+}
+
+/// Validates the pool accounts of a 'swap base in' or 'swap base out'
+/// instruction.
+pub fn validate_swap_accounts(
+    amm_program: &Pubkey,
+    amm: &AmmInfo,
+    amm_authority: &Pubkey,
+    amm_open_orders: &Pubkey,
+    amm_coin_vault: &Pubkey,
+    amm_pc_vault: &Pubkey,
+    market_program: &Pubkey,
+    market: &Pubkey,
+) -> AccountsReport {
+    let mut report = AccountsReport::default();
+    report.check_authority(amm_program, amm, amm_authority);
+    report.check("amm_open_orders", &amm.open_orders, amm_open_orders);
+    report.check("amm_coin_vault", &amm.coin_vault, amm_coin_vault);
+    report.check("amm_pc_vault", &amm.pc_vault, amm_pc_vault);
+    report.check("market_program", &amm.market_program, market_program);
+    report.check("market", &amm.market, market);
+    report
+}
+
+/// Validates the pool accounts of a 'deposit' instruction.
+pub fn validate_deposit_accounts(
+    amm_program: &Pubkey,
+    amm: &AmmInfo,
+    amm_authority: &Pubkey,
+    amm_open_orders: &Pubkey,
+    amm_target_orders: &Pubkey,
+    amm_lp_mint: &Pubkey,
+    amm_coin_vault: &Pubkey,
+    amm_pc_vault: &Pubkey,
+    market: &Pubkey,
+) -> AccountsReport {
+    let mut report = AccountsReport::default();
+    report.check_authority(amm_program, amm, amm_authority);
+    report.check("amm_open_orders", &amm.open_orders, amm_open_orders);
+    report.check("amm_target_orders", &amm.target_orders, amm_target_orders);
+    report.check("amm_lp_mint", &amm.lp_mint, amm_lp_mint);
+    report.check("amm_coin_vault", &amm.coin_vault, amm_coin_vault);
+    report.check("amm_pc_vault", &amm.pc_vault, amm_pc_vault);
+    report.check("market", &amm.market, market);
+    report
+}
+
+/// Validates the pool accounts of a 'withdraw' instruction.
+pub fn validate_withdraw_accounts(
+    amm_program: &Pubkey,
+    amm: &AmmInfo,
+    amm_authority: &Pubkey,
+    amm_open_orders: &Pubkey,
+    amm_target_orders: &Pubkey,
+    amm_lp_mint: &Pubkey,
+    amm_coin_vault: &Pubkey,
+    amm_pc_vault: &Pubkey,
+    market_program: &Pubkey,
+    market: &Pubkey,
+) -> AccountsReport {
+    let mut report = validate_deposit_accounts(
+        amm_program,
+        amm,
+        amm_authority,
+        amm_open_orders,
+        amm_target_orders,
+        amm_lp_mint,
+        amm_coin_vault,
+        amm_pc_vault,
+        market,
+    );
+    report.check("market_program", &amm.market_program, market_program);
+    report
+}
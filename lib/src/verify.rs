@@ -0,0 +1,321 @@
+//! Authenticity checks of AMM v4 pools.
+//!
+//! A look-alike pool can copy the layout of a real one while pointing at
+//! vaults or an lp mint its authority does not control. The verifier checks
+//! the raw accounts of a pool, as fetched from the cluster, against what the
+//! AMM program would have created, and lists every failed check.
+
+use crate::{
+    amm_instruction,
+    amm_stats::{AmmInfo, AmmStatus},
+};
+use solana_program::{
+    program_option::COption,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+};
+use spl_token::state::{Account, Mint};
+use std::fmt;
+
+/// Account as fetched from the cluster.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RawAccount<'a> {
+    pub key: Pubkey,
+    /// program owning the account
+    pub owner: Pubkey,
+    pub data: &'a [u8],
+}
+
+/// Accounts of an AMM v4 pool to verify.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AmmPoolAccounts<'a> {
+    pub amm: RawAccount<'a>,
+    pub coin_vault: RawAccount<'a>,
+    pub pc_vault: RawAccount<'a>,
+    pub lp_mint: RawAccount<'a>,
+}
+
+/// Failed check of a pool account.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DistrustReason {
+    /// account owned by another program
+    ProgramOwner {
+        account: &'static str,
+        expected: Pubkey,
+        actual: Pubkey,
+    },
+    /// account data of another size
+    DataSize {
+        account: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    /// account data not decodable as the expected state
+    InvalidData { account: &'static str },
+    /// amm status unknown or uninitialized
+    AmmStatus(u64),
+    /// account key not the one recorded by the amm
+    Key {
+        account: &'static str,
+        expected: Pubkey,
+        actual: Pubkey,
+    },
+    /// vault mint not the one recorded by the amm
+    VaultMint {
+        account: &'static str,
+        expected: Pubkey,
+        actual: Pubkey,
+    },
+    /// vault not owned by the pool authority
+    VaultOwner {
+        account: &'static str,
+        expected: Pubkey,
+        actual: Pubkey,
+    },
+    /// lp mint authority not the pool authority
+    LpMintAuthority {
+        expected: Pubkey,
+        actual: Option<Pubkey>,
+    },
+    /// no valid pool authority for the amm nonce
+    Authority,
+}
+
+impl fmt::Display for DistrustReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ProgramOwner {
+                account,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{} is owned by {}, expected {}",
+                account, actual, expected
+            ),
+            Self::DataSize {
+                account,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{} has {} bytes of data, expected {}",
+                account, actual, expected
+            ),
+            Self::InvalidData { account } => write!(f, "{} data is invalid", account),
+            Self::AmmStatus(status) => write!(f, "amm status {} is not active", status),
+            Self::Key {
+                account,
+                expected,
+                actual,
+            } => write!(f, "{} is {}, the amm records {}", account, actual, expected),
+            Self::VaultMint {
+                account,
+                expected,
+                actual,
+            } => write!(f, "{} mint is {}, expected {}", account, actual, expected),
+            Self::VaultOwner {
+                account,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{} is owned by {}, expected the pool authority {}",
+                account, actual, expected
+            ),
+            Self::LpMintAuthority { expected, actual } => match actual {
+                Some(actual) => write!(
+                    f,
+                    "lp_mint authority is {}, expected the pool authority {}",
+                    actual, expected
+                ),
+                None => write!(
+                    f,
+                    "lp_mint has no authority, expected the pool authority {}",
+                    expected
+                ),
+            },
+            Self::Authority => f.write_str("amm nonce derives no pool authority"),
+        }
+    }
+}
+
+/// Trust verdict of a pool, trusted if no check failed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TrustVerdict {
+    /// failed checks
+    pub reasons: Vec<DistrustReason>,
+}
+
+impl TrustVerdict {
+    /// Whether all the checks passed.
+    pub fn is_trusted(&self) -> bool {
+        self.reasons.is_empty()
+    }
+}
+
+impl fmt::Display for TrustVerdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_trusted() {
+            return f.write_str("trusted");
+        }
+        f.write_str("untrusted: ")?;
+        for (i, reason) in self.reasons.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}", reason)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> AmmPoolAccounts<'a> {
+    /// Verifies the pool was created by the AMM program:
+    /// * the amm account is owned by the AMM program, with the amm size and
+    ///   an initialized status
+    /// * the vaults are the amm ones, spl token accounts of the amm mints
+    ///   owned by the pool authority
+    /// * the lp mint is the amm one, with the pool authority as mint
+    ///   authority
+    pub fn verify(&self) -> TrustVerdict {
+        let mut reasons = Vec::new();
+        let amm = match self.verify_amm(&mut reasons) {
+            Some(amm) => amm,
+            // nothing to check the other accounts against
+            None => return TrustVerdict { reasons },
+        };
+        let authority = amm_instruction::get_amm_authority(&amm_instruction::id(), amm.nonce);
+        let authority = match authority {
+            Ok(authority) => authority,
+            Err(_) => {
+                reasons.push(DistrustReason::Authority);
+                Pubkey::default()
+            }
+        };
+        for (account, vault, key, mint) in [
+            (
+                "coin_vault",
+                &self.coin_vault,
+                amm.coin_vault,
+                amm.coin_vault_mint,
+            ),
+            ("pc_vault", &self.pc_vault, amm.pc_vault, amm.pc_vault_mint),
+        ] {
+            check_key(&mut reasons, account, key, vault.key);
+            if let Some(vault) = unpack_token_state::<Account>(&mut reasons, account, vault) {
+                if vault.mint != mint {
+                    reasons.push(DistrustReason::VaultMint {
+                        account,
+                        expected: mint,
+                        actual: vault.mint,
+                    });
+                }
+                if vault.owner != authority {
+                    reasons.push(DistrustReason::VaultOwner {
+                        account,
+                        expected: authority,
+                        actual: vault.owner,
+                    });
+                }
+            }
+        }
+        check_key(&mut reasons, "lp_mint", amm.lp_mint, self.lp_mint.key);
+        if let Some(lp_mint) = unpack_token_state::<Mint>(&mut reasons, "lp_mint", &self.lp_mint) {
+            if lp_mint.mint_authority != COption::Some(authority) {
+                reasons.push(DistrustReason::LpMintAuthority {
+                    expected: authority,
+                    actual: lp_mint.mint_authority.into(),
+                });
+            }
+        }
+        TrustVerdict { reasons }
+    }
+
+    fn verify_amm(&self, reasons: &mut Vec<DistrustReason>) -> Option<AmmInfo> {
+        let account = &self.amm;
+        let mut valid = check_owner(reasons, "amm", &amm_instruction::id(), account);
+        if account.data.len() != AmmInfo::LEN {
+            reasons.push(DistrustReason::DataSize {
+                account: "amm",
+                expected: AmmInfo::LEN,
+                actual: account.data.len(),
+            });
+            valid = false;
+        }
+        let amm = match AmmInfo::unpack(account.data) {
+            Ok(amm) => amm,
+            Err(_) => {
+                if valid {
+                    reasons.push(DistrustReason::InvalidData { account: "amm" });
+                }
+                return None;
+            }
+        };
+        if !matches!(
+            AmmStatus::from_u64(amm.status),
+            Some(status) if !matches!(status, AmmStatus::Uninitialized)
+        ) {
+            reasons.push(DistrustReason::AmmStatus(amm.status));
+        }
+        valid.then_some(amm)
+    }
+}
+
+fn check_owner(
+    reasons: &mut Vec<DistrustReason>,
+    account: &'static str,
+    expected: &Pubkey,
+    raw: &RawAccount,
+) -> bool {
+    if raw.owner != *expected {
+        reasons.push(DistrustReason::ProgramOwner {
+            account,
+            expected: *expected,
+            actual: raw.owner,
+        });
+        return false;
+    }
+    true
+}
+
+fn check_key(
+    reasons: &mut Vec<DistrustReason>,
+    account: &'static str,
+    expected: Pubkey,
+    actual: Pubkey,
+) {
+    if expected != actual {
+        reasons.push(DistrustReason::Key {
+            account,
+            expected,
+            actual,
+        });
+    }
+}
+
+/// Decodes an spl token account or mint, owned by the spl token program.
+fn unpack_token_state<T: Pack + IsInitialized>(
+    reasons: &mut Vec<DistrustReason>,
+    account: &'static str,
+    raw: &RawAccount,
+) -> Option<T> {
+    if !check_owner(reasons, account, &spl_token::id(), raw) {
+        return None;
+    }
+    if raw.data.len() != T::LEN {
+        reasons.push(DistrustReason::DataSize {
+            account,
+            expected: T::LEN,
+            actual: raw.data.len(),
+        });
+        return None;
+    }
+    match T::unpack(raw.data) {
+        Ok(state) => Some(state),
+        Err(_) => {
+            reasons.push(DistrustReason::InvalidData { account });
+            None
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use raydium_contract_instructions::amm_instruction;
+use solana_program::{instruction::Instruction, program_error::ProgramError, pubkey::Pubkey};
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+fn swap_base_in(minimum_amount_out: u64) -> Result<Instruction, ProgramError> {
+    amm_instruction::swap_base_in(
+        &amm_instruction::id(),
+        &key(1),
+        &key(2),
+        &key(3),
+        &key(4),
+        &key(5),
+        &key(6),
+        &key(7),
+        &key(8),
+        &key(9),
+        &key(10),
+        &key(11),
+        &key(12),
+        &key(13),
+        &key(14),
+        &key(15),
+        &key(16),
+        1_000,
+        minimum_amount_out,
+    )
+}
+
+#[test]
+fn swap_base_in_minimum_amount_out() {
+    let instruction = swap_base_in(1).unwrap();
+    assert_eq!(
+        instruction.data,
+        [&[9][..], &1_000u64.to_le_bytes(), &1u64.to_le_bytes()].concat()
+    );
+    // a zero minimum accepts any swap output
+    assert_eq!(swap_base_in(0), Err(ProgramError::InvalidArgument));
+}
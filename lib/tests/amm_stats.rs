@@ -0,0 +1,178 @@
+use raydium_contract_instructions::{
+    amm_stats::{AmmInfo, AmmStatus},
+    market_stats::OpenOrders,
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+fn put(data: &mut [u8], offset: usize, bytes: &[u8]) {
+    data[offset..offset + bytes.len()].copy_from_slice(bytes);
+}
+
+fn open_orders(native_coin_total: u64, native_pc_total: u64) -> OpenOrders {
+    OpenOrders {
+        account_flags: 0,
+        market: Pubkey::default(),
+        owner: Pubkey::default(),
+        native_coin_free: 0,
+        native_coin_total,
+        native_pc_free: 0,
+        native_pc_total,
+        free_slot_bits: 0,
+        is_bid_bits: 0,
+        orders: [0; 128],
+        client_order_ids: [0; 128],
+        referrer_rebates_accrued: 0,
+    }
+}
+
+fn amm(status: u64) -> AmmInfo {
+    AmmInfo {
+        status,
+        need_take_pnl_coin: 10,
+        need_take_pnl_pc: 20,
+        ..AmmInfo::default()
+    }
+}
+
+#[test]
+fn effective_reserves_orderbook_permission() {
+    let open_orders = open_orders(1_000, 2_000);
+    for status in [
+        AmmStatus::Initialized,
+        AmmStatus::OrderBookOnly,
+        AmmStatus::WaitingTrade,
+    ] {
+        let status = status as u64;
+        assert_eq!(
+            amm(status).effective_reserves(100_000, 200_000, &open_orders),
+            Ok((100_990, 201_980)),
+            "status {}",
+            status
+        );
+    }
+    // the open orders funds are left out without orderbook trading
+    for status in [
+        AmmStatus::Uninitialized,
+        AmmStatus::Disabled,
+        AmmStatus::WithdrawOnly,
+        AmmStatus::LiquidityOnly,
+        AmmStatus::SwapOnly,
+    ] {
+        let status = status as u64;
+        assert_eq!(
+            amm(status).effective_reserves(100_000, 200_000, &open_orders),
+            Ok((99_990, 199_980)),
+            "status {}",
+            status
+        );
+    }
+    let unknown = AmmInfo {
+        status: 8,
+        ..amm(AmmStatus::Initialized as u64)
+    };
+    assert_eq!(
+        unknown.effective_reserves(100_000, 200_000, &open_orders),
+        Ok((99_990, 199_980))
+    );
+}
+
+#[test]
+fn effective_reserves_pnl() {
+    let amm = amm(AmmStatus::SwapOnly as u64);
+    let open_orders = open_orders(0, 0);
+    // all of the vaults is pnl not yet taken
+    assert_eq!(amm.effective_reserves(10, 20, &open_orders), Ok((0, 0)));
+    assert_eq!(
+        amm.effective_reserves(9, 20, &open_orders),
+        Err(ProgramError::InvalidAccountData)
+    );
+    assert_eq!(
+        amm.effective_reserves(10, 19, &open_orders),
+        Err(ProgramError::InvalidAccountData)
+    );
+    // the open orders funds cover the pnl
+    let amm = AmmInfo {
+        status: AmmStatus::Initialized as u64,
+        ..amm
+    };
+    assert_eq!(
+        amm.effective_reserves(0, 0, &self::open_orders(10, 20)),
+        Ok((0, 0))
+    );
+    assert_eq!(
+        amm.effective_reserves(u64::MAX, 0, &self::open_orders(1, 20)),
+        Err(ProgramError::InvalidAccountData)
+    );
+}
+
+#[test]
+fn amm_info_layout() {
+    let mut data = vec![0; AmmInfo::LEN];
+    put(&mut data, 0, &6u64.to_le_bytes());
+    put(&mut data, 8, &254u64.to_le_bytes());
+    put(&mut data, 32, &9u64.to_le_bytes());
+    put(&mut data, 40, &6u64.to_le_bytes());
+    put(&mut data, 144, &25u64.to_le_bytes());
+    put(&mut data, 152, &10_000u64.to_le_bytes());
+    put(&mut data, 192, &11u64.to_le_bytes());
+    put(&mut data, 200, &12u64.to_le_bytes());
+    put(&mut data, 224, &1_700_000_000u64.to_le_bytes());
+    put(&mut data, 248, &1_700_000_600u64.to_le_bytes());
+    put(&mut data, 256, &(u64::MAX as u128 + 1).to_le_bytes());
+    put(&mut data, 272, &2u128.to_le_bytes());
+    put(&mut data, 288, &3u64.to_le_bytes());
+    // 8 bytes aligned, where the host layout would pad to 304
+    put(&mut data, 296, &4u128.to_le_bytes());
+    put(&mut data, 312, &5u128.to_le_bytes());
+    put(&mut data, 328, &6u64.to_le_bytes());
+    for (i, offset) in (336..624).step_by(32).enumerate() {
+        put(&mut data, offset, key(i as u8 + 1).as_ref());
+    }
+    put(&mut data, 688, key(10).as_ref());
+    put(&mut data, 720, &1_000_000u64.to_le_bytes());
+    put(&mut data, 728, &7u64.to_le_bytes());
+
+    let amm = AmmInfo::unpack(&data).unwrap();
+    assert_eq!(amm.status, 6);
+    assert_eq!(amm.nonce, 254);
+    assert_eq!((amm.coin_decimals, amm.pc_decimals), (9, 6));
+    assert_eq!(
+        (amm.trade_fee_numerator, amm.trade_fee_denominator),
+        (25, 10_000)
+    );
+    assert_eq!((amm.need_take_pnl_coin, amm.need_take_pnl_pc), (11, 12));
+    assert_eq!(amm.pool_open_time, 1_700_000_000);
+    assert_eq!(amm.orderbook_to_init_time, 1_700_000_600);
+    assert_eq!(amm.swap_coin_in_amount, u64::MAX as u128 + 1);
+    assert_eq!(amm.swap_pc_out_amount, 2);
+    assert_eq!(amm.swap_acc_pc_fee, 3);
+    assert_eq!(amm.swap_pc_in_amount, 4);
+    assert_eq!(amm.swap_coin_out_amount, 5);
+    assert_eq!(amm.swap_acc_coin_fee, 6);
+    assert_eq!(
+        [
+            amm.coin_vault,
+            amm.pc_vault,
+            amm.coin_vault_mint,
+            amm.pc_vault_mint,
+            amm.lp_mint,
+            amm.open_orders,
+            amm.market,
+            amm.market_program,
+            amm.target_orders,
+        ],
+        [1, 2, 3, 4, 5, 6, 7, 8, 9].map(key)
+    );
+    assert_eq!(amm.amm_owner, key(10));
+    assert_eq!(amm.lp_amount, 1_000_000);
+    assert_eq!(amm.client_order_id, 7);
+
+    assert_eq!(
+        AmmInfo::unpack(&data[..AmmInfo::LEN - 1]).err(),
+        Some(ProgramError::InvalidAccountData)
+    );
+}
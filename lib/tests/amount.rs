@@ -0,0 +1,135 @@
+#![cfg(feature = "client")]
+
+use raydium_contract_instructions::{
+    amm_instruction::{self, AmmInstruction, SwapInstructionBaseIn},
+    amount::{Amount, PoolPrice, TokenAmount, UiAmount},
+    pool::{AmmPool, RaydiumPool, UserPoolAccounts},
+    route_instruction::RoutePoolKeys,
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use std::convert::TryFrom;
+
+#[test]
+fn token_amounts() {
+    let amount = TokenAmount::new(1_500_000, 6);
+    assert_eq!(amount.ui_amount(), 1.5);
+    assert_eq!(amount.to_ui_string(), "1.5");
+    assert_eq!(amount.to_string(), "1.5");
+    assert_eq!(TokenAmount::new(42, 6).to_ui_string(), "0.000042");
+    assert_eq!(TokenAmount::new(42_000_000, 6).to_ui_string(), "42");
+    assert_eq!(TokenAmount::new(42, 0).to_ui_string(), "42");
+
+    assert_eq!(TokenAmount::parse_ui("1.5", 6), Ok(amount));
+    assert_eq!(
+        TokenAmount::parse_ui(".000042", 6),
+        Ok(TokenAmount::new(42, 6))
+    );
+    assert_eq!(TokenAmount::parse_ui("42", 0), Ok(TokenAmount::new(42, 0)));
+    for invalid in ["", ".", "1.0000001", "-1", "1,5", "18446744073709.551616"] {
+        assert_eq!(
+            TokenAmount::parse_ui(invalid, 6),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+
+    // rounded to the nearest atom
+    assert_eq!(
+        TokenAmount::from_ui_amount(0.3, 6),
+        Ok(TokenAmount::new(300_000, 6))
+    );
+    assert_eq!(
+        TokenAmount::from_ui_amount(-1.0, 6),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(
+        TokenAmount::from_ui_amount(f64::NAN, 6),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(UiAmount::from(amount), UiAmount::new(1.5, 6));
+    assert_eq!(TokenAmount::try_from(UiAmount::new(1.5, 6)), Ok(amount));
+    assert_eq!(u64::from(amount), 1_500_000);
+
+    assert_eq!(Amount::from(7u64).to_atoms(9), Ok(7));
+    assert_eq!(Amount::from(amount).to_atoms(6), Ok(1_500_000));
+    assert_eq!(
+        Amount::from(UiAmount::new(2.0, 6)).to_atoms(6),
+        Ok(2_000_000)
+    );
+    assert_eq!(
+        Amount::from(amount).to_atoms(9),
+        Err(ProgramError::InvalidArgument)
+    );
+}
+
+#[test]
+fn pool_prices() {
+    // 1000 coin with 6 decimals for 2 pc with 9 decimals
+    let price = PoolPrice::from_reserves(1_000_000_000, 2_000_000_000, 6, 9).unwrap();
+    assert_eq!(price.raw, 2.0);
+    assert!((price.ui_price() - 0.002).abs() < 1e-12);
+    assert!((price.inverse_ui_price().unwrap() - 500.0).abs() < 1e-9);
+    assert_eq!(price.pc_value(1_000_000), TokenAmount::new(2_000_000, 9));
+    assert_eq!(PoolPrice::from_reserves(0, 1, 6, 9), None);
+}
+
+#[test]
+fn pool_amounts() {
+    let pool = AmmPool {
+        keys: RoutePoolKeys {
+            program_id: amm_instruction::id(),
+            ..RoutePoolKeys::default()
+        },
+        coin_mint: Pubkey::new_from_array([1; 32]),
+        pc_mint: Pubkey::new_from_array([2; 32]),
+        coin_decimals: 6,
+        pc_decimals: 9,
+        coin_reserve: 1_000_000_000,
+        pc_reserve: 2_000_000_000,
+        swap_fee_numerator: 25,
+        swap_fee_denominator: 10_000,
+        ..AmmPool::default()
+    };
+    let coin = pool.coin_mint;
+    assert_eq!(
+        pool.reserves_price(),
+        PoolPrice::from_reserves(1_000_000_000, 2_000_000_000, 6, 9)
+    );
+
+    let amount_out = pool
+        .quote_in_amount(&coin, UiAmount::new(1.0, 6).into())
+        .unwrap();
+    assert_eq!(
+        amount_out,
+        TokenAmount::new(pool.quote_in(&coin, 1_000_000).unwrap(), 9)
+    );
+    assert_eq!(
+        pool.quote_in_amount(&coin, Amount::Atoms(1_000_000)),
+        Ok(amount_out)
+    );
+    // pc decimals given for the coin
+    assert_eq!(
+        pool.quote_in_amount(&coin, UiAmount::new(1.0, 9).into()),
+        Err(ProgramError::InvalidArgument)
+    );
+    let amount_in = pool
+        .quote_out_amount(&pool.pc_mint, TokenAmount::new(1_000_000, 9).into())
+        .unwrap();
+    assert_eq!(amount_in.decimals, 6);
+
+    let swap = pool
+        .build_swap_amount(
+            &UserPoolAccounts::default(),
+            &coin,
+            UiAmount::new(1.0, 6).into(),
+            TokenAmount::parse_ui("0.0019", 9).unwrap().into(),
+            true,
+        )
+        .unwrap();
+    assert_eq!(
+        AmmInstruction::unpack(&swap.data),
+        Ok(AmmInstruction::SwapBaseIn(SwapInstructionBaseIn {
+            amount_in: 1_000_000,
+            minimum_amount_out: 1_900_000,
+        }))
+    );
+}
@@ -0,0 +1,156 @@
+#![cfg(feature = "client")]
+
+//! The snapshots are of a synthetic AMM v4 pool a day apart, with 9 coin and
+//! 6 pc decimals, a 150 pc per coin price and reserves of 100k coin and 15M
+//! pc. In between 4000 coin were swapped in for 598.5k pc, and 600k pc for
+//! 3990 coin, with 0.25% swap fees of which the protocol took 12%.
+
+use raydium_contract_instructions::{
+    amm_stats::AmmInfo,
+    amount::PoolPrice,
+    analytics::{PoolActivity, PoolCounters, PoolSnapshot, SECONDS_PER_YEAR},
+    stable_stats::OutPutData,
+};
+use solana_program::program_error::ProgramError;
+
+const START_TIME: i64 = 1_700_000_000;
+const END_TIME: i64 = START_TIME + 24 * 3600;
+
+fn snapshots() -> (PoolSnapshot, PoolSnapshot) {
+    let start = AmmInfo {
+        swap_coin_in_amount: 120_000_000_000_000,
+        swap_pc_out_amount: 17_900_000_000_000,
+        swap_pc_in_amount: 18_000_000_000_000,
+        swap_coin_out_amount: 119_500_000_000_000,
+        swap_acc_coin_fee: 300_000_000_000,
+        swap_acc_pc_fee: 45_000_000_000,
+        total_pnl_coin: 36_000_000_000,
+        total_pnl_pc: 5_400_000_000,
+        ..AmmInfo::default()
+    };
+    let end = AmmInfo {
+        // 4000 coin in for 598.5k pc
+        swap_coin_in_amount: start.swap_coin_in_amount + 4_000_000_000_000,
+        swap_pc_out_amount: start.swap_pc_out_amount + 598_500_000_000,
+        // 600k pc in for 3990 coin
+        swap_pc_in_amount: start.swap_pc_in_amount + 600_000_000_000,
+        swap_coin_out_amount: start.swap_coin_out_amount + 3_990_000_000_000,
+        // 0.25% of the amounts in
+        swap_acc_coin_fee: start.swap_acc_coin_fee + 10_000_000_000,
+        swap_acc_pc_fee: start.swap_acc_pc_fee + 1_500_000_000,
+        // 12% of the fees
+        total_pnl_coin: start.total_pnl_coin + 1_200_000_000,
+        total_pnl_pc: start.total_pnl_pc + 180_000_000,
+        ..start
+    };
+    (
+        PoolSnapshot::new(START_TIME, &start),
+        PoolSnapshot::new(END_TIME, &end),
+    )
+}
+
+fn price() -> PoolPrice {
+    PoolPrice::from_reserves(100_000_000_000_000, 15_000_000_000_000, 9, 6).unwrap()
+}
+
+fn assert_close(value: f64, expected: f64, tolerance: f64) {
+    assert!(
+        (value - expected).abs() <= tolerance,
+        "{} is not {} within {}",
+        value,
+        expected,
+        tolerance
+    );
+}
+
+#[test]
+fn amm_activity() {
+    let (start, end) = snapshots();
+    let activity = PoolActivity::between(&start, &end).unwrap();
+    assert_eq!(
+        activity,
+        PoolActivity {
+            elapsed_seconds: 24 * 3600,
+            coin_volume: 7_990_000_000_000,
+            pc_volume: 1_198_500_000_000,
+            coin_fee: 10_000_000_000,
+            pc_fee: 1_500_000_000,
+            coin_pnl: 1_200_000_000,
+            pc_pnl: 180_000_000,
+        }
+    );
+
+    let price = price();
+    assert_close(price.ui_price(), 150.0, 1e-9);
+    // 1500 pc of coin fees and 1500 pc of pc fees
+    assert_close(activity.pc_fee_revenue(&price), 3_000_000_000.0, 1e-3);
+    assert_close(activity.pc_protocol_pnl(&price), 360_000_000.0, 1e-3);
+    assert_close(activity.pc_lp_fees(&price), 2_640_000_000.0, 1e-3);
+
+    // 2640 pc a day on a 30M pc pool
+    let apr = activity
+        .lp_fee_apr(100_000_000_000_000, 15_000_000_000_000, &price)
+        .unwrap();
+    assert_close(apr, 2_640.0 / 30_000_000.0 * 365.0, 1e-12);
+    assert_eq!(activity.lp_fee_apr(0, 0, &price), None);
+}
+
+#[test]
+fn invalid_snapshots() {
+    let (start, end) = snapshots();
+    // out of order
+    assert_eq!(
+        PoolActivity::between(&end, &start),
+        Err(ProgramError::InvalidArgument)
+    );
+    let end_first = PoolSnapshot {
+        timestamp: START_TIME - 1,
+        ..end
+    };
+    assert_eq!(
+        PoolActivity::between(&start, &end_first),
+        Err(ProgramError::InvalidArgument)
+    );
+
+    // same time, no apr
+    let activity = PoolActivity::between(&start, &start).unwrap();
+    assert_eq!(activity.coin_volume, 0);
+    assert_eq!(activity.lp_fee_apr(1, 1, &price()), None);
+}
+
+#[test]
+fn stable_activity() {
+    let start = OutPutData {
+        swap_coin_in_amount: 1_000_000,
+        swap_pc_out_amount: 999_000,
+        swap_coin_fee: 100,
+        total_pnl_coin: 10,
+        ..OutPutData::default()
+    };
+    let end = OutPutData {
+        swap_coin_in_amount: 3_000_000,
+        swap_pc_out_amount: 2_997_000,
+        swap_pc_in_amount: 1_000_000,
+        swap_coin_out_amount: 999_000,
+        swap_coin_fee: 300,
+        swap_pc_fee: 100,
+        total_pnl_coin: 30,
+        total_pnl_pc: 10,
+        ..OutPutData::default()
+    };
+    assert_eq!(PoolCounters::from(&end).swap_pc_fee, 100);
+    let activity = PoolActivity::between(
+        &PoolSnapshot::new(0, &start),
+        &PoolSnapshot::new(SECONDS_PER_YEAR as i64, &end),
+    )
+    .unwrap();
+    assert_eq!(activity.coin_volume, 2_999_000);
+    assert_eq!(activity.pc_volume, 2_998_000);
+    let price = PoolPrice {
+        raw: 1.0,
+        coin_decimals: 6,
+        pc_decimals: 6,
+    };
+    assert_eq!(activity.pc_lp_fees(&price), 270.0);
+    assert_eq!(activity.lp_fee_apr(1_000, 1_700, &price), Some(0.1));
+}
@@ -0,0 +1,130 @@
+#![cfg(feature = "client")]
+
+//! The account data is synthetic, written at the offsets the programs lay
+//! the fields out at: BPF aligns `u128` on 8 bytes, not 16 like the host.
+
+use raydium_contract_instructions::{farm_stats, stable_stats};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+fn put(data: &mut [u8], offset: usize, bytes: &[u8]) {
+    data[offset..offset + bytes.len()].copy_from_slice(bytes);
+}
+
+#[test]
+fn stable_amm_info_layout() {
+    let mut data = vec![0; 1232];
+    put(&mut data, 8, &1u64.to_le_bytes());
+    put(&mut data, 152, &2u64.to_le_bytes());
+    put(&mut data, 176, &5u64.to_le_bytes());
+    put(&mut data, 184, &10_000u64.to_le_bytes());
+    put(&mut data, 224, &11u64.to_le_bytes());
+    put(&mut data, 232, &12u64.to_le_bytes());
+    put(&mut data, 288, &(u64::MAX as u128 + 1).to_le_bytes());
+    put(&mut data, 336, &4u128.to_le_bytes());
+    put(&mut data, 352, &5u64.to_le_bytes());
+    put(&mut data, 360, &6u64.to_le_bytes());
+    for (i, offset) in (368..720).step_by(32).enumerate() {
+        put(&mut data, offset, key(i as u8 + 1).as_ref());
+    }
+    put(&mut data, 1224, &9u64.to_le_bytes());
+
+    let amm = stable_stats::AmmInfo::unpack(&data).unwrap();
+    assert_eq!(amm.status, 1);
+    assert_eq!(amm.price_tick, 2);
+    assert_eq!(amm.fees.trade_fee_numerator, 5);
+    assert_eq!(amm.fees.trade_fee_denominator, 10_000);
+    assert_eq!(amm.out_put.need_take_pnl_coin, 11);
+    assert_eq!(amm.out_put.need_take_pnl_pc, 12);
+    assert_eq!(amm.out_put.swap_coin_in_amount, u64::MAX as u128 + 1);
+    assert_eq!(amm.out_put.swap_coin_out_amount, 4);
+    assert_eq!(amm.out_put.swap_pc_fee, 5);
+    assert_eq!(amm.out_put.swap_coin_fee, 6);
+    assert_eq!(amm.coin_vault, key(1));
+    assert_eq!(amm.model_data_key, key(6));
+    assert_eq!(amm.amm_admin, key(11));
+    assert_eq!(amm.padding[63], 9);
+
+    assert_eq!(
+        stable_stats::AmmInfo::unpack(&data[..1231]).err(),
+        Some(ProgramError::InvalidAccountData)
+    );
+}
+
+#[test]
+fn farm_pool_layout() {
+    let mut data = vec![0; 224];
+    put(&mut data, 0, &1u64.to_le_bytes());
+    put(&mut data, 16, key(1).as_ref());
+    put(&mut data, 48, key(2).as_ref());
+    put(&mut data, 88, &(u64::MAX as u128 + 1).to_le_bytes());
+    put(&mut data, 104, &7u64.to_le_bytes());
+    // the option tag and key are followed by padding to the next u64
+    put(&mut data, 112, &[1]);
+    put(&mut data, 113, key(3).as_ref());
+    put(&mut data, 152, &8u64.to_le_bytes());
+    put(&mut data, 160, &9u128.to_le_bytes());
+    put(&mut data, 184, &10u64.to_le_bytes());
+    put(&mut data, 192, key(4).as_ref());
+
+    let farm = farm_stats::FarmPool::unpack(&data).unwrap();
+    assert_eq!(
+        farm,
+        farm_stats::FarmPool {
+            state: 1,
+            nonce: 0,
+            lp_vault: key(1),
+            reward_vault_a: key(2),
+            reward_total_a: 0,
+            acc_ray_per_share_a: u64::MAX as u128 + 1,
+            reward_per_slot_a: 7,
+            reward_vault_b: Some(key(3)),
+            reward_total_b: 8,
+            acc_ray_per_share_b: 9,
+            reward_per_slot_b: 0,
+            last_pool_update_slot: 10,
+            owner: key(4),
+        }
+    );
+
+    data[112] = 0;
+    assert_eq!(
+        farm_stats::FarmPool::unpack(&data).unwrap().reward_vault_b,
+        None
+    );
+    data[112] = 2;
+    assert_eq!(
+        farm_stats::FarmPool::unpack(&data),
+        Err(ProgramError::InvalidAccountData)
+    );
+}
+
+#[test]
+fn farmer_info_layout() {
+    let mut data = vec![0; 248];
+    put(&mut data, 0, &1u64.to_le_bytes());
+    put(&mut data, 8, key(1).as_ref());
+    put(&mut data, 40, key(2).as_ref());
+    put(&mut data, 72, &100u64.to_le_bytes());
+    put(&mut data, 80, &(u64::MAX as u128 + 1).to_le_bytes());
+    put(&mut data, 96, &3u128.to_le_bytes());
+    put(&mut data, 112, &2u64.to_le_bytes());
+    put(&mut data, 144, &4u64.to_le_bytes());
+
+    let farmer = farm_stats::FarmerInfoV2::unpack(&data).unwrap();
+    assert_eq!(farmer.farm_pool, key(1));
+    assert_eq!(farmer.farmer, key(2));
+    assert_eq!(farmer.deposit_balance, 100);
+    assert_eq!(farmer.reward_debt_a, u64::MAX as u128 + 1);
+    assert_eq!(farmer.reward_debt_b, 3);
+    assert_eq!(farmer.account_type, 2);
+    assert_eq!(farmer.pending[3], 4);
+
+    // the v1 layout keeps u64 reward debts
+    let farmer = farm_stats::FarmerInfo::unpack(&data[..96]).unwrap();
+    assert_eq!(farmer.deposit_balance, 100);
+    assert_eq!((farmer.reward_debt_a, farmer.reward_debt_b), (0, 1));
+}
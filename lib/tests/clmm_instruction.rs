@@ -0,0 +1,633 @@
+mod common;
+
+use common::InstructionCodec;
+use raydium_contract_instructions::clmm_instruction::{
+    self, ClmmInstruction, DecreaseLiquidityV2Instruction, IncreaseLiquidityV2Instruction,
+    OpenPositionV2Instruction, RewardAccounts, SwapRouterBaseInInstruction, SwapRouterHop,
+    SwapV2Instruction, MEMO_PROGRAM_ID, METADATA_PROGRAM_ID, POSITION_SEED, TICK_ARRAY_SEED,
+    TOKEN_2022_PROGRAM_ID,
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+const OPEN_POSITION_V2: [u8; 8] = [77, 184, 74, 214, 112, 86, 241, 199];
+const INCREASE_LIQUIDITY_V2: [u8; 8] = [133, 29, 89, 223, 69, 238, 176, 10];
+const DECREASE_LIQUIDITY_V2: [u8; 8] = [58, 127, 188, 62, 79, 82, 196, 96];
+const CLOSE_POSITION: [u8; 8] = [123, 134, 81, 0, 49, 68, 98, 98];
+const SWAP_V2: [u8; 8] = [43, 4, 237, 11, 26, 201, 30, 98];
+const SWAP_ROUTER_BASE_IN: [u8; 8] = [69, 125, 115, 218, 245, 186, 242, 196];
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+/// Instruction data of a discriminator followed by the packed fields.
+fn data(discriminator: [u8; 8], fields: &[&[u8]]) -> Vec<u8> {
+    [&[&discriminator[..]], fields].concat().concat()
+}
+
+fn open_position_v2(base_flag: Option<bool>) -> OpenPositionV2Instruction {
+    OpenPositionV2Instruction {
+        tick_lower_index: -120,
+        tick_upper_index: 360,
+        tick_array_lower_start_index: -3_600,
+        tick_array_upper_start_index: 0,
+        liquidity: 0,
+        amount_0_max: 1_000,
+        amount_1_max: 2_000,
+        with_metadata: true,
+        base_flag,
+    }
+}
+
+fn open_position_v2_data(base_flag: &[u8]) -> Vec<u8> {
+    data(
+        OPEN_POSITION_V2,
+        &[
+            &(-120i32).to_le_bytes(),
+            &360i32.to_le_bytes(),
+            &(-3_600i32).to_le_bytes(),
+            &0i32.to_le_bytes(),
+            &0u128.to_le_bytes(),
+            &1_000u64.to_le_bytes(),
+            &2_000u64.to_le_bytes(),
+            &[1],
+            base_flag,
+        ],
+    )
+}
+
+fn increase_liquidity_v2_data(base_flag: &[u8]) -> Vec<u8> {
+    data(
+        INCREASE_LIQUIDITY_V2,
+        &[
+            &0u128.to_le_bytes(),
+            &1_000u64.to_le_bytes(),
+            &2_000u64.to_le_bytes(),
+            base_flag,
+        ],
+    )
+}
+
+fn decrease_liquidity_v2_data(liquidity: u128, amount_0_min: u64, amount_1_min: u64) -> Vec<u8> {
+    data(
+        DECREASE_LIQUIDITY_V2,
+        &[
+            &liquidity.to_le_bytes(),
+            &amount_0_min.to_le_bytes(),
+            &amount_1_min.to_le_bytes(),
+        ],
+    )
+}
+
+fn rewards() -> [RewardAccounts; 2] {
+    [
+        RewardAccounts {
+            reward_vault: key(30),
+            user_reward_token: key(31),
+            reward_mint: key(32),
+        },
+        RewardAccounts {
+            reward_vault: key(33),
+            user_reward_token: key(34),
+            reward_mint: key(35),
+        },
+    ]
+}
+
+fn assert_accounts(instruction: &Instruction, expected: &[AccountMeta]) {
+    assert_eq!(instruction.program_id, clmm_instruction::id());
+    assert_eq!(instruction.accounts, expected);
+}
+
+#[test]
+fn codec() {
+    InstructionCodec {
+        serialize: ClmmInstruction::pack,
+        deserialize: ClmmInstruction::unpack,
+        instructions: vec![
+            (
+                ClmmInstruction::OpenPositionV2(open_position_v2(None)),
+                open_position_v2_data(&[0]),
+            ),
+            (
+                ClmmInstruction::OpenPositionV2(open_position_v2(Some(true))),
+                open_position_v2_data(&[1, 1]),
+            ),
+            (
+                ClmmInstruction::OpenPositionV2(open_position_v2(Some(false))),
+                open_position_v2_data(&[1, 0]),
+            ),
+            (
+                ClmmInstruction::IncreaseLiquidityV2(IncreaseLiquidityV2Instruction {
+                    liquidity: 0,
+                    amount_0_max: 1_000,
+                    amount_1_max: 2_000,
+                    base_flag: None,
+                }),
+                increase_liquidity_v2_data(&[0]),
+            ),
+            (
+                ClmmInstruction::IncreaseLiquidityV2(IncreaseLiquidityV2Instruction {
+                    liquidity: 0,
+                    amount_0_max: 1_000,
+                    amount_1_max: 2_000,
+                    base_flag: Some(false),
+                }),
+                increase_liquidity_v2_data(&[1, 0]),
+            ),
+            (
+                ClmmInstruction::DecreaseLiquidityV2(DecreaseLiquidityV2Instruction {
+                    liquidity: u128::MAX,
+                    amount_0_min: 10,
+                    amount_1_min: 20,
+                }),
+                decrease_liquidity_v2_data(u128::MAX, 10, 20),
+            ),
+            (ClmmInstruction::ClosePosition, CLOSE_POSITION.to_vec()),
+            (
+                ClmmInstruction::SwapV2(SwapV2Instruction {
+                    amount: 5_000,
+                    other_amount_threshold: 4_900,
+                    sqrt_price_limit_x64: 1 << 64,
+                    is_base_input: true,
+                }),
+                data(
+                    SWAP_V2,
+                    &[
+                        &5_000u64.to_le_bytes(),
+                        &4_900u64.to_le_bytes(),
+                        &(1u128 << 64).to_le_bytes(),
+                        &[1],
+                    ],
+                ),
+            ),
+            (
+                ClmmInstruction::SwapRouterBaseIn(SwapRouterBaseInInstruction {
+                    amount_in: 5_000,
+                    amount_out_minimum: 4_900,
+                }),
+                data(
+                    SWAP_ROUTER_BASE_IN,
+                    &[&5_000u64.to_le_bytes(), &4_900u64.to_le_bytes()],
+                ),
+            ),
+        ],
+        unknown_tags: vec![0, 1, u8::MAX],
+        reserved: Vec::new(),
+    }
+    .check();
+
+    // a bool or an option tag other than 0 or 1
+    for base_flag in [[2, 1], [1, 2]] {
+        assert_eq!(
+            ClmmInstruction::unpack(&open_position_v2_data(&base_flag)),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
+
+#[test]
+fn pda_seeds() {
+    let program = clmm_instruction::id();
+    let pool = key(1);
+    assert_eq!(
+        clmm_instruction::get_protocol_position(&program, &pool, -120, 360),
+        Pubkey::find_program_address(
+            &[
+                POSITION_SEED,
+                pool.as_ref(),
+                &[0xff, 0xff, 0xff, 0x88],
+                &[0, 0, 1, 0x68],
+            ],
+            &program,
+        )
+        .0
+    );
+    assert_eq!(
+        clmm_instruction::get_tick_array(&program, &pool, -3_600),
+        Pubkey::find_program_address(
+            &[TICK_ARRAY_SEED, pool.as_ref(), &[0xff, 0xff, 0xf1, 0xf0]],
+            &program,
+        )
+        .0
+    );
+    // the seeds are big-endian, unlike the instruction data
+    assert_ne!(
+        clmm_instruction::get_tick_array(&program, &pool, 60),
+        Pubkey::find_program_address(
+            &[TICK_ARRAY_SEED, pool.as_ref(), &60i32.to_le_bytes()],
+            &program,
+        )
+        .0
+    );
+    assert_eq!(
+        clmm_instruction::get_personal_position(&program, &key(2)),
+        Pubkey::find_program_address(&[POSITION_SEED, key(2).as_ref()], &program).0
+    );
+}
+
+#[test]
+fn open_position_v2_accounts() {
+    let open_position_v2 = |tick_array_bitmap_extension, base_flag| {
+        clmm_instruction::open_position_v2(
+            &clmm_instruction::id(),
+            &key(1),
+            &key(2),
+            &key(3),
+            &key(4),
+            &key(5),
+            &key(6),
+            &key(7),
+            &key(8),
+            &key(9),
+            &key(10),
+            &key(11),
+            &key(12),
+            &key(13),
+            &key(14),
+            &key(15),
+            &key(16),
+            tick_array_bitmap_extension,
+            -120,
+            360,
+            -3_600,
+            0,
+            0,
+            1_000,
+            2_000,
+            true,
+            base_flag,
+        )
+        .unwrap()
+    };
+    let mut expected = vec![
+        AccountMeta::new(key(1), true),
+        AccountMeta::new_readonly(key(2), false),
+        AccountMeta::new(key(3), true),
+        AccountMeta::new(key(4), false),
+        AccountMeta::new(key(5), false),
+        AccountMeta::new(key(6), false),
+        AccountMeta::new(key(7), false),
+        AccountMeta::new(key(8), false),
+        AccountMeta::new(key(9), false),
+        AccountMeta::new(key(10), false),
+        AccountMeta::new(key(11), false),
+        AccountMeta::new(key(12), false),
+        AccountMeta::new(key(13), false),
+        AccountMeta::new(key(14), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(METADATA_PROGRAM_ID, false),
+        AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        AccountMeta::new_readonly(key(15), false),
+        AccountMeta::new_readonly(key(16), false),
+    ];
+
+    let instruction = open_position_v2(None, None);
+    assert_eq!(instruction.data, open_position_v2_data(&[0]));
+    assert_accounts(&instruction, &expected);
+
+    let instruction = open_position_v2(Some(&key(17)), Some(true));
+    assert_eq!(instruction.data, open_position_v2_data(&[1, 1]));
+    expected.push(AccountMeta::new(key(17), false));
+    assert_accounts(&instruction, &expected);
+}
+
+#[test]
+fn increase_liquidity_v2_accounts() {
+    let increase_liquidity_v2 = |tick_array_bitmap_extension, base_flag| {
+        clmm_instruction::increase_liquidity_v2(
+            &clmm_instruction::id(),
+            &key(1),
+            &key(2),
+            &key(3),
+            &key(4),
+            &key(5),
+            &key(6),
+            &key(7),
+            &key(8),
+            &key(9),
+            &key(10),
+            &key(11),
+            &key(12),
+            &key(13),
+            tick_array_bitmap_extension,
+            0,
+            1_000,
+            2_000,
+            base_flag,
+        )
+        .unwrap()
+    };
+    let mut expected = vec![
+        AccountMeta::new_readonly(key(1), true),
+        AccountMeta::new_readonly(key(2), false),
+        AccountMeta::new(key(3), false),
+        AccountMeta::new(key(4), false),
+        AccountMeta::new(key(5), false),
+        AccountMeta::new(key(6), false),
+        AccountMeta::new(key(7), false),
+        AccountMeta::new(key(8), false),
+        AccountMeta::new(key(9), false),
+        AccountMeta::new(key(10), false),
+        AccountMeta::new(key(11), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        AccountMeta::new_readonly(key(12), false),
+        AccountMeta::new_readonly(key(13), false),
+    ];
+
+    let instruction = increase_liquidity_v2(None, None);
+    assert_eq!(instruction.data, increase_liquidity_v2_data(&[0]));
+    assert_accounts(&instruction, &expected);
+
+    let instruction = increase_liquidity_v2(Some(&key(14)), Some(false));
+    assert_eq!(instruction.data, increase_liquidity_v2_data(&[1, 0]));
+    expected.push(AccountMeta::new(key(14), false));
+    assert_accounts(&instruction, &expected);
+}
+
+/// Accounts of a 'decrease liquidity v2' of the keys 1 to 13 and the
+/// bitmap extension 14, without the rewards.
+fn decrease_liquidity_v2_accounts() -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(key(1), true),
+        AccountMeta::new_readonly(key(2), false),
+        AccountMeta::new(key(3), false),
+        AccountMeta::new(key(4), false),
+        AccountMeta::new(key(5), false),
+        AccountMeta::new(key(6), false),
+        AccountMeta::new(key(7), false),
+        AccountMeta::new(key(8), false),
+        AccountMeta::new(key(9), false),
+        AccountMeta::new(key(10), false),
+        AccountMeta::new(key(11), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        AccountMeta::new_readonly(MEMO_PROGRAM_ID, false),
+        AccountMeta::new_readonly(key(12), false),
+        AccountMeta::new_readonly(key(13), false),
+        AccountMeta::new(key(14), false),
+    ]
+}
+
+fn reward_accounts() -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(key(30), false),
+        AccountMeta::new(key(31), false),
+        AccountMeta::new_readonly(key(32), false),
+        AccountMeta::new(key(33), false),
+        AccountMeta::new(key(34), false),
+        AccountMeta::new_readonly(key(35), false),
+    ]
+}
+
+#[test]
+fn decrease_liquidity_v2_accounts_with_rewards() {
+    let decrease_liquidity_v2 = |tick_array_bitmap_extension, rewards: &[RewardAccounts]| {
+        clmm_instruction::decrease_liquidity_v2(
+            &clmm_instruction::id(),
+            &key(1),
+            &key(2),
+            &key(3),
+            &key(4),
+            &key(5),
+            &key(6),
+            &key(7),
+            &key(8),
+            &key(9),
+            &key(10),
+            &key(11),
+            &key(12),
+            &key(13),
+            tick_array_bitmap_extension,
+            rewards,
+            1_000,
+            10,
+            20,
+        )
+        .unwrap()
+    };
+    let accounts = decrease_liquidity_v2_accounts();
+
+    let instruction = decrease_liquidity_v2(None, &[]);
+    assert_eq!(instruction.data, decrease_liquidity_v2_data(1_000, 10, 20));
+    assert_accounts(&instruction, &accounts[..16]);
+
+    let instruction = decrease_liquidity_v2(Some(&key(14)), &rewards());
+    assert_accounts(&instruction, &[accounts, reward_accounts()].concat());
+}
+
+#[test]
+fn collect_rewards_accounts() {
+    let instruction = clmm_instruction::collect_rewards(
+        &clmm_instruction::id(),
+        &key(1),
+        &key(2),
+        &key(3),
+        &key(4),
+        &key(5),
+        &key(6),
+        &key(7),
+        &key(8),
+        &key(9),
+        &key(10),
+        &key(11),
+        &key(12),
+        &key(13),
+        Some(&key(14)),
+        &rewards(),
+    )
+    .unwrap();
+    assert_eq!(instruction.data, decrease_liquidity_v2_data(0, 0, 0));
+    assert_accounts(
+        &instruction,
+        &[decrease_liquidity_v2_accounts(), reward_accounts()].concat(),
+    );
+}
+
+#[test]
+fn close_position_accounts() {
+    let instruction = clmm_instruction::close_position(
+        &clmm_instruction::id(),
+        &key(1),
+        &key(2),
+        &key(3),
+        &key(4),
+    )
+    .unwrap();
+    assert_eq!(instruction.data, CLOSE_POSITION);
+    assert_accounts(
+        &instruction,
+        &[
+            AccountMeta::new(key(1), true),
+            AccountMeta::new(key(2), false),
+            AccountMeta::new(key(3), false),
+            AccountMeta::new(key(4), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+    );
+}
+
+#[test]
+fn swap_v2_accounts() {
+    let swap_v2 = |tick_array_bitmap_extension: Option<Pubkey>, tick_arrays: &[Pubkey]| {
+        clmm_instruction::swap_v2(
+            &clmm_instruction::id(),
+            &key(1),
+            &key(2),
+            &key(3),
+            &key(4),
+            &key(5),
+            &key(6),
+            &key(7),
+            tick_array_bitmap_extension.as_ref(),
+            tick_arrays,
+            &key(8),
+            &key(9),
+            &key(10),
+            5_000,
+            4_900,
+            0,
+            true,
+        )
+    };
+    let instruction = swap_v2(Some(key(11)), &[key(20), key(21)]).unwrap();
+    assert_eq!(
+        instruction.data,
+        data(
+            SWAP_V2,
+            &[
+                &5_000u64.to_le_bytes(),
+                &4_900u64.to_le_bytes(),
+                &0u128.to_le_bytes(),
+                &[1],
+            ],
+        )
+    );
+    let expected = [
+        AccountMeta::new_readonly(key(10), true),
+        AccountMeta::new_readonly(key(1), false),
+        AccountMeta::new(key(2), false),
+        AccountMeta::new(key(8), false),
+        AccountMeta::new(key(9), false),
+        AccountMeta::new(key(3), false),
+        AccountMeta::new(key(4), false),
+        AccountMeta::new(key(5), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        AccountMeta::new_readonly(MEMO_PROGRAM_ID, false),
+        AccountMeta::new_readonly(key(6), false),
+        AccountMeta::new_readonly(key(7), false),
+        AccountMeta::new_readonly(key(11), false),
+        AccountMeta::new(key(20), false),
+        AccountMeta::new(key(21), false),
+    ];
+    assert_accounts(&instruction, &expected);
+
+    let instruction = swap_v2(None, &[key(20)]).unwrap();
+    assert_accounts(&instruction, &[&expected[..13], &expected[14..15]].concat());
+
+    assert_eq!(swap_v2(None, &[]), Err(ProgramError::NotEnoughAccountKeys));
+}
+
+#[test]
+fn swap_router_base_in_accounts() {
+    let tick_arrays = [key(20), key(21), key(22)];
+    let hops = [
+        SwapRouterHop {
+            amm_config: key(1),
+            pool_state: key(2),
+            user_output_token: key(3),
+            input_vault: key(4),
+            output_vault: key(5),
+            output_vault_mint: key(6),
+            observation_state: key(7),
+            tick_array_bitmap_extension: None,
+            tick_arrays: &tick_arrays[..1],
+        },
+        SwapRouterHop {
+            amm_config: key(1),
+            pool_state: key(12),
+            user_output_token: key(13),
+            input_vault: key(14),
+            output_vault: key(15),
+            output_vault_mint: key(16),
+            observation_state: key(17),
+            tick_array_bitmap_extension: Some(key(18)),
+            tick_arrays: &tick_arrays[1..],
+        },
+    ];
+    let swap_router_base_in = |hops: &[SwapRouterHop]| {
+        clmm_instruction::swap_router_base_in(
+            &clmm_instruction::id(),
+            &key(8),
+            hops,
+            &key(9),
+            &key(10),
+            5_000,
+            4_900,
+        )
+    };
+
+    let instruction = swap_router_base_in(&hops).unwrap();
+    assert_eq!(
+        instruction.data,
+        data(
+            SWAP_ROUTER_BASE_IN,
+            &[&5_000u64.to_le_bytes(), &4_900u64.to_le_bytes()],
+        )
+    );
+    assert_accounts(
+        &instruction,
+        &[
+            AccountMeta::new_readonly(key(10), true),
+            AccountMeta::new(key(9), false),
+            AccountMeta::new(key(8), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new_readonly(MEMO_PROGRAM_ID, false),
+            // first pool
+            AccountMeta::new_readonly(key(1), false),
+            AccountMeta::new(key(2), false),
+            AccountMeta::new(key(3), false),
+            AccountMeta::new(key(4), false),
+            AccountMeta::new(key(5), false),
+            AccountMeta::new_readonly(key(6), false),
+            AccountMeta::new(key(7), false),
+            AccountMeta::new(key(20), false),
+            // second pool
+            AccountMeta::new_readonly(key(1), false),
+            AccountMeta::new(key(12), false),
+            AccountMeta::new(key(13), false),
+            AccountMeta::new(key(14), false),
+            AccountMeta::new(key(15), false),
+            AccountMeta::new_readonly(key(16), false),
+            AccountMeta::new(key(17), false),
+            AccountMeta::new_readonly(key(18), false),
+            AccountMeta::new(key(21), false),
+            AccountMeta::new(key(22), false),
+        ],
+    );
+
+    assert_eq!(
+        swap_router_base_in(&[]),
+        Err(ProgramError::NotEnoughAccountKeys)
+    );
+    let no_tick_arrays = SwapRouterHop {
+        tick_arrays: &[],
+        ..hops[1]
+    };
+    assert_eq!(
+        swap_router_base_in(&[hops[0], no_tick_arrays]),
+        Err(ProgramError::NotEnoughAccountKeys)
+    );
+}
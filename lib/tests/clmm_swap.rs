@@ -0,0 +1,382 @@
+#![cfg(feature = "client")]
+
+//! The fixtures are synthetic accounts, not mainnet dumps: a CLMM pool with
+//! tick spacing 10 and a 0.05% trade fee, around a 150 token 1 per token 0
+//! price with decimals 9 and 6. Its positions are [-19500, -18500],
+//! [-19010, -18940] and [-21000, -17400]. The expected swaps are computed
+//! with big integers, walking the initialized ticks directly.
+//!
+//! The simulation still has to be checked against a mainnet pool, see
+//! `mainnet_swap` and `fixtures/clmm/README.md`.
+
+use raydium_contract_instructions::{
+    clmm_instruction,
+    clmm_math::{self, SwapSimulation},
+    clmm_stats::{AmmConfig, PoolState, TickArrayBitmapExtension, TickArrayState},
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use std::{fs, path::Path};
+
+const POOL_ID: Pubkey = Pubkey::new_from_array([0x11; 32]);
+
+fn amm_config() -> AmmConfig {
+    AmmConfig::unpack(include_bytes!("fixtures/clmm/amm_config.bin")).unwrap()
+}
+
+fn pool_state() -> PoolState {
+    PoolState::unpack(include_bytes!("fixtures/clmm/pool_state.bin")).unwrap()
+}
+
+fn tick_arrays() -> Vec<TickArrayState> {
+    [
+        &include_bytes!("fixtures/clmm/tick_array_-21000.bin")[..],
+        &include_bytes!("fixtures/clmm/tick_array_-19800.bin")[..],
+        &include_bytes!("fixtures/clmm/tick_array_-19200.bin")[..],
+        &include_bytes!("fixtures/clmm/tick_array_-18600.bin")[..],
+        &include_bytes!("fixtures/clmm/tick_array_-17400.bin")[..],
+    ]
+    .iter()
+    .map(|data| TickArrayState::unpack(data).unwrap())
+    .collect()
+}
+
+fn simulate(
+    amount: u64,
+    is_base_input: bool,
+    zero_for_one: bool,
+    sqrt_price_limit_x64: u128,
+) -> Result<SwapSimulation, ProgramError> {
+    clmm_math::simulate_swap(
+        &POOL_ID,
+        &pool_state(),
+        &amm_config(),
+        &tick_arrays(),
+        None,
+        amount,
+        is_base_input,
+        zero_for_one,
+        sqrt_price_limit_x64,
+    )
+}
+
+fn assert_tick_arrays(simulation: &SwapSimulation, start_indexes: &[i32]) {
+    assert_eq!(simulation.tick_array_start_indexes, start_indexes);
+    let program_id = clmm_instruction::id();
+    let tick_arrays: Vec<Pubkey> = start_indexes
+        .iter()
+        .map(|start_index| clmm_instruction::get_tick_array(&program_id, &POOL_ID, *start_index))
+        .collect();
+    assert_eq!(simulation.tick_arrays, tick_arrays);
+    assert!(!simulation.uses_bitmap_extension);
+}
+
+#[test]
+fn decode_accounts() {
+    let amm_config = amm_config();
+    assert_eq!(amm_config.index, 3);
+    assert_eq!(amm_config.trade_fee_rate, 500);
+    assert_eq!(amm_config.tick_spacing, 10);
+
+    let pool = pool_state();
+    assert_eq!(pool.amm_config, Pubkey::new_from_array([0x22; 32]));
+    assert_eq!(pool.token_mint_0, Pubkey::new_from_array([0x66; 32]));
+    assert_eq!(pool.observation_key, Pubkey::new_from_array([0xaa; 32]));
+    assert_eq!((pool.mint_decimals_0, pool.mint_decimals_1), (9, 6));
+    assert_eq!(pool.tick_spacing, 10);
+    assert_eq!(pool.liquidity, 11_000_000_000_000);
+    assert_eq!(pool.sqrt_price_x64, 7144565336511698929);
+    assert_eq!(pool.tick_current, -18972);
+    assert_eq!(pool.reward_infos[0].emissions_per_second_x64, 5 << 64);
+    assert_eq!(pool.reward_infos[0].reward_growth_global_x64, 42);
+    assert_eq!(pool.open_time, 1690000000);
+    assert_eq!(pool.recent_epoch, 600);
+
+    let tick_array = &tick_arrays()[2];
+    assert_eq!(tick_array.pool_id, POOL_ID);
+    assert_eq!(tick_array.start_tick_index, -19200);
+    assert_eq!(tick_array.initialized_tick_count, 2);
+    let tick = &tick_array.ticks[((-18940 + 19200) / 10) as usize];
+    assert_eq!(tick.tick, -18940);
+    assert_eq!(tick.liquidity_net, -8_000_000_000_000);
+    assert_eq!(tick.liquidity_gross, 8_000_000_000_000);
+
+    let price = clmm_math::sqrt_price_x64_to_price(pool.sqrt_price_x64, 9, 6);
+    assert!((price - 150.0).abs() < 0.1);
+}
+
+#[test]
+fn decode_wrong_discriminator() {
+    let data = include_bytes!("fixtures/clmm/pool_state.bin");
+    assert_eq!(
+        TickArrayState::unpack(data).unwrap_err(),
+        ProgramError::InvalidAccountData
+    );
+    assert_eq!(
+        PoolState::unpack(&data[..100]).unwrap_err(),
+        ProgramError::InvalidAccountData
+    );
+}
+
+#[test]
+fn tick_math() {
+    assert_eq!(clmm_math::get_sqrt_price_at_tick(0).unwrap(), 1 << 64);
+    assert_eq!(
+        clmm_math::get_sqrt_price_at_tick(clmm_math::MIN_TICK).unwrap(),
+        clmm_math::MIN_SQRT_PRICE_X64
+    );
+    assert_eq!(
+        clmm_math::get_sqrt_price_at_tick(clmm_math::MAX_TICK).unwrap(),
+        clmm_math::MAX_SQRT_PRICE_X64
+    );
+    assert!(clmm_math::get_sqrt_price_at_tick(clmm_math::MAX_TICK + 1).is_err());
+
+    for tick in [
+        clmm_math::MIN_TICK,
+        -18972,
+        -1,
+        0,
+        1,
+        18972,
+        clmm_math::MAX_TICK - 1,
+    ] {
+        let sqrt_price_x64 = clmm_math::get_sqrt_price_at_tick(tick).unwrap();
+        assert_eq!(
+            clmm_math::get_tick_at_sqrt_price(sqrt_price_x64).unwrap(),
+            tick
+        );
+        assert_eq!(
+            clmm_math::get_tick_at_sqrt_price(sqrt_price_x64 + 1).unwrap(),
+            tick
+        );
+    }
+    assert!(clmm_math::get_tick_at_sqrt_price(clmm_math::MAX_SQRT_PRICE_X64).is_err());
+
+    assert_eq!(clmm_math::get_tick_array_start_index(-18972, 10), -19200);
+    assert_eq!(clmm_math::get_tick_array_start_index(-19200, 10), -19200);
+    assert_eq!(clmm_math::get_tick_array_start_index(599, 10), 0);
+    assert_eq!(clmm_math::get_tick_array_start_index(600, 10), 600);
+}
+
+#[test]
+fn liquidity_amounts() {
+    let sqrt_price_current_x64 = pool_state().sqrt_price_x64;
+    let sqrt_price_a_x64 = clmm_math::get_sqrt_price_at_tick(-19500).unwrap();
+    let sqrt_price_b_x64 = clmm_math::get_sqrt_price_at_tick(-18500).unwrap();
+
+    let liquidity = clmm_math::get_liquidity_from_amounts(
+        sqrt_price_current_x64,
+        sqrt_price_a_x64,
+        sqrt_price_b_x64,
+        1_000_000_000_000,
+        100_000_000_000,
+    )
+    .unwrap();
+    let (amount_0, amount_1) = clmm_math::get_amounts_from_liquidity(
+        sqrt_price_current_x64,
+        sqrt_price_a_x64,
+        sqrt_price_b_x64,
+        liquidity,
+        true,
+    )
+    .unwrap();
+    assert!(amount_0 <= 1_000_000_000_000);
+    assert_eq!(amount_1, 100_000_000_000);
+
+    // below the range, only token 0 is needed
+    let (amount_0, amount_1) = clmm_math::get_amounts_from_liquidity(
+        sqrt_price_a_x64 - 1,
+        sqrt_price_a_x64,
+        sqrt_price_b_x64,
+        liquidity,
+        false,
+    )
+    .unwrap();
+    assert!(amount_0 > 0);
+    assert_eq!(amount_1, 0);
+}
+
+#[test]
+fn swap_base_input_zero_for_one() {
+    let simulation = simulate(10_000_000_000, true, true, 0).unwrap();
+    assert_eq!(simulation.amount_in, 10_000_000_000);
+    assert_eq!(simulation.amount_out, 1_498_794_763);
+    assert_eq!(simulation.fee_amount, 5_000_000);
+    assert_eq!(simulation.sqrt_price_x64, 7142051892564813312);
+    assert_eq!(simulation.tick_current, -18979);
+    assert_eq!(simulation.liquidity, 11_000_000_000_000);
+    assert_tick_arrays(&simulation, &[-19200]);
+}
+
+#[test]
+fn swap_base_input_crossing_tick_arrays() {
+    let simulation = simulate(200_000_000_000, true, true, 0).unwrap();
+    assert_eq!(simulation.amount_in, 200_000_000_000);
+    assert_eq!(simulation.amount_out, 29_487_668_023);
+    assert_eq!(simulation.fee_amount, 100_000_001);
+    assert_eq!(simulation.sqrt_price_x64, 6999728058936475664);
+    assert_eq!(simulation.tick_current, -19382);
+    assert_eq!(simulation.liquidity, 3_000_000_000_000);
+    assert_tick_arrays(&simulation, &[-19200, -19800]);
+}
+
+#[test]
+fn swap_base_input_one_for_zero() {
+    let simulation = simulate(30_000_000_000, true, false, 0).unwrap();
+    assert_eq!(simulation.amount_in, 30_000_000_000);
+    assert_eq!(simulation.amount_out, 196_307_286_003);
+    assert_eq!(simulation.fee_amount, 15_000_001);
+    assert_eq!(simulation.sqrt_price_x64, 7298752222895018133);
+    assert_eq!(simulation.tick_current, -18545);
+    assert_eq!(simulation.liquidity, 3_000_000_000_000);
+    assert_tick_arrays(&simulation, &[-19200, -18600]);
+}
+
+#[test]
+fn swap_base_output_zero_for_one() {
+    let simulation = simulate(1_000_000_000, false, true, 0).unwrap();
+    assert_eq!(simulation.amount_in, 6_671_246_256);
+    assert_eq!(simulation.amount_out, 1_000_000_000);
+    assert_eq!(simulation.fee_amount, 3_335_624);
+    assert_eq!(simulation.sqrt_price_x64, 7142888359777725333);
+    assert_eq!(simulation.tick_current, -18977);
+    assert_tick_arrays(&simulation, &[-19200]);
+}
+
+#[test]
+fn swap_base_output_skips_uninitialized_tick_arrays() {
+    let simulation = simulate(300_000_000_000, false, false, 0).unwrap();
+    assert_eq!(simulation.amount_in, 46_794_879_140);
+    assert_eq!(simulation.amount_out, 300_000_000_000);
+    assert_eq!(simulation.fee_amount, 23_397_440);
+    assert_eq!(simulation.sqrt_price_x64, 7575808809231171666);
+    assert_eq!(simulation.tick_current, -17800);
+    assert_eq!(simulation.liquidity, 1_000_000_000_000);
+    assert_tick_arrays(&simulation, &[-19200, -18600, -17400]);
+}
+
+#[test]
+fn swap_stops_at_price_limit() {
+    let limit = clmm_math::get_sqrt_price_at_tick(-19100).unwrap();
+    let simulation = simulate(1_000_000_000_000_000, true, true, limit).unwrap();
+    assert_eq!(simulation.amount_in, 89_529_687_451);
+    assert_eq!(simulation.amount_out, 13_364_167_629);
+    assert_eq!(simulation.fee_amount, 44_764_844);
+    assert_eq!(simulation.sqrt_price_x64, limit);
+    assert_eq!(simulation.tick_current, -19100);
+    // the next tick -19500 is read before stopping at the limit
+    assert_tick_arrays(&simulation, &[-19200, -19800]);
+
+    // the limit must be on the swap side of the current price
+    assert_eq!(
+        simulate(1_000_000, true, false, limit).unwrap_err(),
+        ProgramError::InvalidArgument
+    );
+}
+
+#[test]
+fn swap_errors() {
+    // drains every position, the search for a next tick array goes beyond
+    // the pool bitmap
+    assert_eq!(
+        simulate(500_000_000_000, true, true, 0).unwrap_err(),
+        ProgramError::NotEnoughAccountKeys
+    );
+    let bitmap_extension = TickArrayBitmapExtension {
+        pool_id: POOL_ID,
+        ..Default::default()
+    };
+    let err = clmm_math::simulate_swap(
+        &POOL_ID,
+        &pool_state(),
+        &amm_config(),
+        &tick_arrays(),
+        Some(&bitmap_extension),
+        500_000_000_000,
+        true,
+        true,
+        0,
+    )
+    .unwrap_err();
+    assert_eq!(err, ProgramError::InsufficientFunds);
+
+    let mut tick_arrays = tick_arrays();
+    tick_arrays.retain(|tick_array| tick_array.start_tick_index != -19800);
+    let err = clmm_math::simulate_swap(
+        &POOL_ID,
+        &pool_state(),
+        &amm_config(),
+        &tick_arrays,
+        None,
+        200_000_000_000,
+        true,
+        true,
+        0,
+    )
+    .unwrap_err();
+    assert_eq!(err, ProgramError::NotEnoughAccountKeys);
+
+    let err = clmm_math::simulate_swap(
+        &Pubkey::new_unique(),
+        &pool_state(),
+        &amm_config(),
+        &tick_arrays,
+        None,
+        1_000,
+        true,
+        true,
+        0,
+    )
+    .unwrap_err();
+    assert_eq!(err, ProgramError::InvalidAccountData);
+}
+
+#[test]
+#[ignore = "needs mainnet account dumps in tests/fixtures/clmm/mainnet"]
+fn mainnet_swap() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/clmm/mainnet");
+    let read = |name: &str| fs::read(dir.join(name)).unwrap();
+    let pool_id: Pubkey = fs::read_to_string(dir.join("pool_id"))
+        .unwrap()
+        .trim()
+        .parse()
+        .unwrap();
+    let mut tick_arrays = Vec::new();
+    for entry in fs::read_dir(&dir).unwrap() {
+        let name = entry.unwrap().file_name().into_string().unwrap();
+        if name.starts_with("tick_array_") {
+            tick_arrays.push(TickArrayState::unpack(&read(&name)).unwrap());
+        }
+    }
+    let swap = fs::read_to_string(dir.join("swap")).unwrap();
+    let mut swap = swap.split_whitespace();
+    let mut amounts = [0u64; 5];
+    for amount in &mut amounts {
+        *amount = swap.next().unwrap().parse().unwrap();
+    }
+    let [amount, is_base_input, zero_for_one, amount_in, amount_out] = amounts;
+    let passed_tick_arrays: Vec<Pubkey> = swap.map(|key| key.parse().unwrap()).collect();
+
+    let simulation = clmm_math::simulate_swap(
+        &pool_id,
+        &PoolState::unpack(&read("pool_state.bin")).unwrap(),
+        &AmmConfig::unpack(&read("amm_config.bin")).unwrap(),
+        &tick_arrays,
+        None,
+        amount,
+        is_base_input == 1,
+        zero_for_one == 1,
+        0,
+    )
+    .unwrap();
+    assert_eq!(
+        (simulation.amount_in, simulation.amount_out),
+        (amount_in, amount_out)
+    );
+    // the program reads the tick arrays in order, the swap may pass more
+    assert!(!simulation.uses_bitmap_extension);
+    assert!(
+        passed_tick_arrays.starts_with(&simulation.tick_arrays),
+        "{:?}",
+        simulation.tick_arrays
+    );
+}
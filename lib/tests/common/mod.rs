@@ -0,0 +1,75 @@
+//! Helpers shared by the integration tests.
+
+use solana_program::program_error::ProgramError;
+use std::fmt::Debug;
+
+/// Instruction data codec of a program, checked against a table of
+/// instructions and their exact bytes.
+pub struct InstructionCodec<I> {
+    pub serialize: fn(&I) -> Result<Vec<u8>, ProgramError>,
+    pub deserialize: fn(&[u8]) -> Result<I, ProgramError>,
+    /// instructions with their exact bytes, one per tag
+    pub instructions: Vec<(I, Vec<u8>)>,
+    /// tags without an instruction
+    pub unknown_tags: Vec<u8>,
+    /// instructions the program does not accept
+    pub reserved: Vec<I>,
+}
+
+/// Instruction data of a `tag` followed by a little-endian `amount`.
+pub fn amount_data(tag: u8, amount: u64) -> Vec<u8> {
+    [&[tag][..], &amount.to_le_bytes()].concat()
+}
+
+impl<I: Debug + PartialEq> InstructionCodec<I> {
+    pub fn check(&self) {
+        for (instruction, data) in &self.instructions {
+            assert_eq!(
+                (self.serialize)(instruction).as_ref(),
+                Ok(data),
+                "{:?}",
+                instruction
+            );
+            assert_eq!(
+                (self.deserialize)(data).as_ref(),
+                Ok(instruction),
+                "{:?}",
+                data
+            );
+
+            // data built with the previous codec was padded to the enum size
+            let padded = [&data[..], &[0; 32]].concat();
+            assert_eq!((self.deserialize)(&padded).as_ref(), Ok(instruction));
+
+            if data.len() > 1 {
+                for len in 0..data.len() {
+                    assert_eq!(
+                        (self.deserialize)(&data[..len]),
+                        Err(ProgramError::InvalidInstructionData),
+                        "{:?} truncated to {}",
+                        instruction,
+                        len
+                    );
+                }
+            }
+        }
+
+        for &tag in &self.unknown_tags {
+            assert_eq!(
+                (self.deserialize)(&amount_data(tag, 0)),
+                Err(ProgramError::InvalidInstructionData),
+                "tag {}",
+                tag
+            );
+        }
+
+        for instruction in &self.reserved {
+            assert_eq!(
+                (self.serialize)(instruction),
+                Err(ProgramError::InvalidInstructionData),
+                "{:?}",
+                instruction
+            );
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use raydium_contract_instructions::cpmm_instruction::{
+    CpmmInstruction, DepositInstruction, InitializeInstruction, SwapBaseInputInstruction,
+    SwapBaseOutputInstruction, WithdrawInstruction,
+};
+use solana_program::program_error::ProgramError;
+
+fn args(discriminator: [u8; 8], args: &[u64]) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    for arg in args {
+        data.extend_from_slice(&arg.to_le_bytes());
+    }
+    data
+}
+
+fn all_instructions() -> Vec<(CpmmInstruction, Vec<u8>)> {
+    vec![
+        (
+            CpmmInstruction::Initialize(InitializeInstruction {
+                init_amount_0: 1_000,
+                init_amount_1: 2_000,
+                open_time: 1_700_000_000,
+            }),
+            args(
+                [175, 175, 109, 31, 13, 152, 155, 237],
+                &[1_000, 2_000, 1_700_000_000],
+            ),
+        ),
+        (
+            CpmmInstruction::Deposit(DepositInstruction {
+                lp_token_amount: 10,
+                maximum_token_0_amount: 11,
+                maximum_token_1_amount: u64::MAX,
+            }),
+            args([242, 35, 198, 137, 82, 225, 242, 182], &[10, 11, u64::MAX]),
+        ),
+        (
+            CpmmInstruction::Withdraw(WithdrawInstruction {
+                lp_token_amount: 10,
+                minimum_token_0_amount: 0,
+                minimum_token_1_amount: 1,
+            }),
+            args([183, 18, 70, 156, 148, 109, 161, 34], &[10, 0, 1]),
+        ),
+        (
+            CpmmInstruction::SwapBaseInput(SwapBaseInputInstruction {
+                amount_in: 5_000,
+                minimum_amount_out: 4_900,
+            }),
+            args([143, 190, 90, 218, 196, 30, 51, 222], &[5_000, 4_900]),
+        ),
+        (
+            CpmmInstruction::SwapBaseOutput(SwapBaseOutputInstruction {
+                max_amount_in: 5_100,
+                amount_out: 5_000,
+            }),
+            args([55, 217, 98, 86, 163, 74, 180, 173], &[5_100, 5_000]),
+        ),
+    ]
+}
+
+#[test]
+fn pack_exact_bytes() {
+    for (instruction, expected) in all_instructions() {
+        assert_eq!(instruction.pack().unwrap(), expected, "{:?}", instruction);
+    }
+}
+
+#[test]
+fn round_trip() {
+    for (instruction, _) in all_instructions() {
+        let data = instruction.pack().unwrap();
+        assert_eq!(CpmmInstruction::unpack(&data).unwrap(), instruction);
+    }
+}
+
+#[test]
+fn unpack_truncated_data() {
+    for (instruction, data) in all_instructions() {
+        for len in 0..data.len() {
+            assert_eq!(
+                CpmmInstruction::unpack(&data[..len]),
+                Err(ProgramError::InvalidInstructionData),
+                "{:?} truncated to {}",
+                instruction,
+                len
+            );
+        }
+    }
+}
+
+#[test]
+fn unpack_unknown_discriminator() {
+    let data = args([0; 8], &[1, 2, 3]);
+    assert_eq!(
+        CpmmInstruction::unpack(&data),
+        Err(ProgramError::InvalidInstructionData)
+    );
+}
+
+#[cfg(feature = "client")]
+#[test]
+fn decode_amm_config() {
+    use raydium_contract_instructions::cpmm_stats::{AmmConfig, AMM_CONFIG_DISCRIMINATOR};
+    use solana_program::pubkey::Pubkey;
+
+    let mut data = AMM_CONFIG_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&[254, 0]);
+    data.extend_from_slice(&1u16.to_le_bytes());
+    for rate in [2_500u64, 120_000, 40_000, 150_000_000] {
+        data.extend_from_slice(&rate.to_le_bytes());
+    }
+    data.extend_from_slice(&[1; 32]);
+    data.extend_from_slice(&[2; 32]);
+    data.extend_from_slice(&[0; 128]);
+    assert_eq!(data.len(), 236);
+
+    let config = AmmConfig::unpack(&data).unwrap();
+    assert_eq!(config.bump, 254);
+    assert!(!config.disable_create_pool);
+    assert_eq!(config.index, 1);
+    assert_eq!(config.trade_fee_rate, 2_500);
+    assert_eq!(config.protocol_fee_rate, 120_000);
+    assert_eq!(config.fund_fee_rate, 40_000);
+    assert_eq!(config.create_pool_fee, 150_000_000);
+    assert_eq!(config.protocol_owner, Pubkey::new_from_array([1; 32]));
+    assert_eq!(config.fund_owner, Pubkey::new_from_array([2; 32]));
+
+    data[9] = 2;
+    assert_eq!(
+        AmmConfig::unpack(&data),
+        Err(ProgramError::InvalidAccountData)
+    );
+    data[9] = 0;
+    data[0] ^= 1;
+    assert_eq!(
+        AmmConfig::unpack(&data),
+        Err(ProgramError::InvalidAccountData)
+    );
+}
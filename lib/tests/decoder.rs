@@ -0,0 +1,167 @@
+#![cfg(feature = "client")]
+
+use raydium_contract_instructions::{
+    amm_instruction,
+    decoder::{decode_instruction, RaydiumInstructionData, UNKNOWN_ROLE},
+    farm_instruction::{self, FarmPoolInstruction},
+    stable_instruction,
+    staking_instruction::{self, StakePoolInstruction},
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+fn keys(len: u8) -> Vec<Pubkey> {
+    (1..=len).map(|n| Pubkey::new_from_array([n; 32])).collect()
+}
+
+fn roles(program_id: &Pubkey, len: u8, data: &[u8]) -> Vec<&'static str> {
+    let accounts = keys(len);
+    let instruction = decode_instruction(program_id, &accounts, data).unwrap();
+    assert_eq!(instruction.program_id, *program_id);
+    assert_eq!(
+        instruction
+            .accounts
+            .iter()
+            .map(|account| account.pubkey)
+            .collect::<Vec<_>>(),
+        accounts
+    );
+    instruction
+        .accounts
+        .into_iter()
+        .map(|account| account.role)
+        .collect()
+}
+
+#[test]
+fn amm_swap() {
+    let data =
+        amm_instruction::AmmInstruction::SwapBaseIn(amm_instruction::SwapInstructionBaseIn {
+            amount_in: 1_000,
+            minimum_amount_out: 900,
+        })
+        .pack()
+        .unwrap();
+    let instruction = decode_instruction(&amm_instruction::id(), &keys(17), &data).unwrap();
+    assert_eq!(
+        instruction.data,
+        RaydiumInstructionData::Amm(amm_instruction::AmmInstruction::SwapBaseIn(
+            amm_instruction::SwapInstructionBaseIn {
+                amount_in: 1_000,
+                minimum_amount_out: 900,
+            }
+        ))
+    );
+
+    // the target orders are optional
+    let with_target_orders = roles(&amm_instruction::id(), 18, &data);
+    assert_eq!(with_target_orders[3], "AMM open orders Account");
+    assert_eq!(with_target_orders[4], "AMM target orders Account");
+    assert_eq!(with_target_orders[5], "AMM coin vault Account");
+    assert_eq!(with_target_orders[17], "User wallet Account");
+    let without_target_orders = roles(&amm_instruction::id(), 17, &data);
+    assert_eq!(without_target_orders[3], "AMM open orders Account");
+    assert_eq!(without_target_orders[4], "AMM coin vault Account");
+    assert_eq!(without_target_orders[14], "User source token Account");
+    assert_eq!(without_target_orders[16], "User wallet Account");
+    assert!(!without_target_orders.contains(&"AMM target orders Account"));
+
+    // the extra accounts are unknown
+    let extra = roles(&amm_instruction::id(), 19, &data);
+    assert_eq!(extra[18], UNKNOWN_ROLE);
+}
+
+#[test]
+fn stable_withdraw() {
+    let data =
+        stable_instruction::AmmInstruction::Withdraw(stable_instruction::WithdrawInstruction {
+            amount: 10,
+        })
+        .pack()
+        .unwrap();
+    let with_referrer = roles(&stable_instruction::id(), 22, &data);
+    assert_eq!(with_referrer[17], "user owner Account");
+    assert_eq!(with_referrer[18], "referrer pc Account");
+    assert_eq!(with_referrer[19], "event_q Account");
+    assert_eq!(with_referrer[21], "asks Account");
+    // the market accounts follow the owner without a referrer
+    let without_referrer = roles(&stable_instruction::id(), 21, &data);
+    assert_eq!(without_referrer[17], "user owner Account");
+    assert_eq!(without_referrer[18], "event_q Account");
+    assert_eq!(without_referrer[20], "asks Account");
+    // a withdraw without the market accounts
+    let without_market = roles(&stable_instruction::id(), 18, &data);
+    assert_eq!(without_market[17], "user owner Account");
+}
+
+#[test]
+fn farm_deposit() {
+    let data = FarmPoolInstruction::DepositV2(5).serialize().unwrap();
+    let instruction = decode_instruction(&farm_instruction::id(), &keys(13), &data).unwrap();
+    assert_eq!(
+        instruction.data,
+        RaydiumInstructionData::Farm(FarmPoolInstruction::DepositV2(5))
+    );
+    let roles_v2 = roles(&farm_instruction::id(), 13, &data);
+    assert_eq!(roles_v2[0], "farm pool Account");
+    assert_eq!(roles_v2[9], "Spl Token program id");
+    assert_eq!(roles_v2[12], "user staker info v1 Account");
+
+    // v1 stops at the reward b vault
+    let data = FarmPoolInstruction::Withdraw(5).serialize().unwrap();
+    let roles_v1 = roles(&farm_instruction::id(), 13, &data);
+    assert_eq!(roles_v1[11], "pool reward token b vault");
+    assert_eq!(roles_v1[12], UNKNOWN_ROLE);
+}
+
+#[test]
+fn staking_deposit() {
+    let data = StakePoolInstruction::DepositV2(5).serialize().unwrap();
+    let roles_v2 = roles(&staking_instruction::id(), 11, &data);
+    assert_eq!(roles_v2[0], "stake pool Account");
+    assert_eq!(roles_v2[2], "user staker info Account");
+    assert_eq!(roles_v2[10], "user staker info v1 Account");
+
+    let data = StakePoolInstruction::Deposit(5).serialize().unwrap();
+    let instruction = decode_instruction(&staking_instruction::id(), &keys(10), &data).unwrap();
+    assert_eq!(
+        instruction.data,
+        RaydiumInstructionData::Staking(StakePoolInstruction::Deposit(5))
+    );
+    let roles_v1 = roles(&staking_instruction::id(), 11, &data);
+    assert_eq!(roles_v1[9], "Spl Token program id");
+    assert_eq!(roles_v1[10], UNKNOWN_ROLE);
+
+    let data = StakePoolInstruction::UpdatePool.serialize().unwrap();
+    assert_eq!(
+        roles(&staking_instruction::id(), 3, &data),
+        ["stake pool Account", "pool lp token vault", "Clock sysvar"]
+    );
+}
+
+#[test]
+fn invalid_instructions() {
+    assert_eq!(
+        decode_instruction(&Pubkey::new_unique(), &keys(1), &[9]),
+        Err(ProgramError::IncorrectProgramId)
+    );
+    // a swap without its amounts
+    assert_eq!(
+        decode_instruction(&amm_instruction::id(), &keys(17), &[9, 1, 2]),
+        Err(ProgramError::InvalidInstructionData)
+    );
+    for program_id in [
+        amm_instruction::id(),
+        stable_instruction::id(),
+        farm_instruction::id(),
+        staking_instruction::id(),
+    ] {
+        assert_eq!(
+            decode_instruction(&program_id, &keys(1), &[]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+    assert_eq!(
+        decode_instruction(&farm_instruction::id(), &keys(1), &[11, 0]),
+        Err(ProgramError::InvalidInstructionData)
+    );
+}
@@ -0,0 +1,171 @@
+#![cfg(feature = "client")]
+
+use raydium_contract_instructions::{
+    amm_instruction, depth,
+    pool::{AmmPool, RaydiumPool, StablePool},
+    route_instruction::RoutePoolKeys,
+    router::RoutePool,
+    stable_instruction,
+    stable_math::{ModelData, MODEL_DATA_LEN},
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+fn amm_pool() -> AmmPool {
+    AmmPool {
+        keys: RoutePoolKeys {
+            program_id: amm_instruction::id(),
+            ..RoutePoolKeys::default()
+        },
+        coin_mint: key(1),
+        pc_mint: key(2),
+        coin_reserve: 1_000_000_000,
+        pc_reserve: 2_000_000_000,
+        swap_fee_numerator: 25,
+        swap_fee_denominator: 10_000,
+        ..AmmPool::default()
+    }
+}
+
+/// Model data sampling the `x * y = 10^18` curve, finely enough for the slope
+/// of the segments not to be skewed by the integer samples.
+fn constant_product_model_data() -> ModelData {
+    let count = 50_000u64;
+    let mut data = vec![0u8; MODEL_DATA_LEN];
+    data[16..24].copy_from_slice(&1_000_000u64.to_le_bytes());
+    data[24..32].copy_from_slice(&count.to_le_bytes());
+    for i in 0..count {
+        let x = 100_000_000 + i * 200_000;
+        let y = 1_000_000_000_000_000_000 / x;
+        let offset = 32 + i as usize * 24;
+        data[offset..offset + 8].copy_from_slice(&x.to_le_bytes());
+        data[offset + 8..offset + 16].copy_from_slice(&y.to_le_bytes());
+    }
+    ModelData::unpack(&data).unwrap()
+}
+
+fn assert_close(value: f64, expected: f64, tolerance: f64) {
+    assert!(
+        (value - expected).abs() <= tolerance,
+        "{} is not {} within {}",
+        value,
+        expected,
+        tolerance
+    );
+}
+
+#[test]
+fn amm_depth_profile() {
+    let pool = amm_pool();
+    let amounts_in = [10_000_000, 1_000_000, 100_000_000];
+    let profile = depth::depth_profile(&pool, &key(1), &amounts_in).unwrap();
+    assert_eq!(profile.input_mint, key(1));
+    assert_eq!(profile.spot_price, 2.0);
+
+    let point = profile.points[0];
+    assert_eq!(point.amount_in, 1_000_000);
+    assert_eq!(point.amount_out, 1_993_011);
+    assert_eq!(point.effective_price, 1.993011);
+    // fee and curve impact
+    assert_close(point.price_impact_bps, 34.945, 1e-9);
+    assert_close(
+        point.marginal_price,
+        (2_000_000_000.0 - 1_993_011.0) / 1_001_000_000.0,
+        1e-12,
+    );
+    for pair in profile.points.windows(2) {
+        assert!(pair[0].amount_in < pair[1].amount_in);
+        assert!(pair[0].amount_out < pair[1].amount_out);
+        assert!(pair[0].marginal_price > pair[1].marginal_price);
+        assert!(pair[0].price_impact_bps < pair[1].price_impact_bps);
+    }
+
+    // from pc, in coin per pc
+    let profile = depth::depth_profile(&pool, &key(2), &[2_000_000]).unwrap();
+    assert_eq!(profile.spot_price, 0.5);
+    assert!(profile.points[0].marginal_price < 0.5);
+
+    assert_eq!(
+        depth::depth_profile(&pool, &key(3), &[1_000]),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(
+        depth::depth_profile(&pool, &key(1), &[0]),
+        Err(ProgramError::InvalidArgument)
+    );
+}
+
+#[test]
+fn amm_price_moves() {
+    let pool = amm_pool();
+    let profile = depth::price_move_profile(&pool, &key(1), &[500, 100, 200]).unwrap();
+    for (point, price_move_bps) in profile.points.iter().zip([100.0, 200.0, 500.0]) {
+        assert!(point.price_move_bps >= price_move_bps);
+        // smallest amount moving the price that much
+        let before = depth::depth_profile(&pool, &key(1), &[point.amount_in - 1]).unwrap();
+        assert!(before.points[0].price_move_bps < price_move_bps);
+        // the price is inversely proportional to the square of the coin
+        // reserve, the fee left in the pool adds a little
+        let expected = 1e9 / (1.0 - price_move_bps / 1e4).sqrt() - 1e9;
+        assert!(point.amount_in as f64 >= expected);
+        assert_close(point.amount_in as f64, expected, expected * 0.01);
+    }
+
+    assert_eq!(
+        depth::price_move_point(&pool, &key(1), 10_000),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(
+        depth::price_move_point(&pool, &key(1), 0),
+        Err(ProgramError::InvalidArgument)
+    );
+}
+
+#[test]
+fn stable_depth() {
+    let model_data = constant_product_model_data();
+    let amm = amm_pool();
+    let pool = StablePool {
+        keys: RoutePoolKeys {
+            program_id: stable_instruction::id(),
+            model_data_account: Some(key(13)),
+            ..amm.keys
+        },
+        coin_mint: amm.coin_mint,
+        pc_mint: amm.pc_mint,
+        coin_reserve: 1_000_000_000,
+        pc_reserve: 1_000_000_000,
+        swap_fee_numerator: 25,
+        swap_fee_denominator: 10_000,
+        amm_target_orders: amm.amm_target_orders,
+        lp_mint: amm.lp_mint,
+        coin_decimals: amm.coin_decimals,
+        pc_decimals: amm.pc_decimals,
+        model_data: &model_data,
+    };
+    assert_close(pool.marginal_price_at(1_000, 1_000).unwrap(), 1.0, 1e-3);
+    assert_eq!(pool.marginal_price_at(0, 1_000), None);
+
+    // pools of both types charted alike
+    let pools = [RoutePool::Amm(amm), RoutePool::Stable(pool)];
+    let profile = depth::depth_profile(&pools[1], &key(1), &[1_000_000, 50_000_000]).unwrap();
+    assert_close(profile.spot_price, 1.0, 1e-3);
+    assert!(profile.points[0].marginal_price > profile.points[1].marginal_price);
+
+    // the constant product amount, up to the next segment of the curve where
+    // the marginal price steps
+    let point = depth::price_move_point(&pools[1], &key(1), 100).unwrap();
+    let expected = 1e9 / 0.99f64.sqrt() - 1e9;
+    assert!(point.price_move_bps >= 100.0);
+    assert_close(point.amount_in as f64, expected, 200_000.0);
+
+    // past the end of the curve
+    assert_eq!(
+        depth::price_move_point(&pools[1], &key(1), 9_999),
+        Err(ProgramError::InsufficientFunds)
+    );
+    assert!(depth::price_move_point(&pools[0], &key(1), 9_999).is_ok());
+}
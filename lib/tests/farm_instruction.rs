@@ -0,0 +1,141 @@
+mod common;
+
+use common::{amount_data, InstructionCodec};
+use raydium_contract_instructions::farm_instruction::{
+    self, FarmPoolInstruction, InitArgs, RewardBAccounts,
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar,
+};
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+/// Accounts of a 'deposit' or 'withdraw' built from the keys 1 to 9.
+fn reward_a_accounts() -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(key(1), false),
+        AccountMeta::new_readonly(key(2), false),
+        AccountMeta::new(key(3), false),
+        AccountMeta::new_readonly(key(4), true),
+        AccountMeta::new(key(5), false),
+        AccountMeta::new(key(6), false),
+        AccountMeta::new(key(7), false),
+        AccountMeta::new(key(8), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(key(9), false),
+    ]
+}
+
+#[test]
+fn codec() {
+    let amount = 0x0102_0304_0506_0708;
+    let init = InitArgs {
+        nonce: 254,
+        reward_per_slot_a: 1_000,
+        reward_per_slot_b: u64::MAX,
+    };
+    let init_data = [
+        &[0][..],
+        &254u64.to_le_bytes(),
+        &1_000u64.to_le_bytes(),
+        &u64::MAX.to_le_bytes(),
+    ]
+    .concat();
+
+    InstructionCodec {
+        serialize: FarmPoolInstruction::serialize,
+        deserialize: FarmPoolInstruction::deserialize,
+        instructions: vec![
+            (FarmPoolInstruction::Initialize(init), init_data),
+            (FarmPoolInstruction::Deposit(amount), amount_data(1, amount)),
+            (
+                FarmPoolInstruction::Withdraw(amount),
+                amount_data(2, amount),
+            ),
+            (FarmPoolInstruction::UpdatePool, vec![3]),
+            (FarmPoolInstruction::EmergencyWithdraw, vec![7]),
+            (FarmPoolInstruction::CreateAssociatedAccount, vec![10]),
+            (
+                FarmPoolInstruction::DepositV2(amount),
+                amount_data(11, amount),
+            ),
+            (
+                FarmPoolInstruction::WithdrawV2(amount),
+                amount_data(12, amount),
+            ),
+        ],
+        unknown_tags: vec![4, 5, 6, 8, 9, 13, u8::MAX],
+        reserved: vec![
+            FarmPoolInstruction::Reserved,
+            FarmPoolInstruction::Reserved1,
+            FarmPoolInstruction::Reserved2,
+            FarmPoolInstruction::Reserved3,
+            FarmPoolInstruction::Reserved4,
+        ],
+    }
+    .check();
+}
+
+#[test]
+fn reward_b_accounts() {
+    type Builder = fn(
+        &Pubkey,
+        &Pubkey,
+        &Pubkey,
+        &Pubkey,
+        &Pubkey,
+        &Pubkey,
+        &Pubkey,
+        &Pubkey,
+        &Pubkey,
+        Option<RewardBAccounts>,
+        &Pubkey,
+        u64,
+    ) -> Result<Instruction, ProgramError>;
+    let builders: [(Builder, u8); 2] = [
+        (farm_instruction::deposit, 1),
+        (farm_instruction::withdraw, 2),
+    ];
+    let program_id = farm_instruction::id();
+    for (build, tag) in builders {
+        let build = |reward_b| {
+            build(
+                &program_id,
+                &key(1),
+                &key(2),
+                &key(3),
+                &key(4),
+                &key(5),
+                &key(6),
+                &key(7),
+                &key(8),
+                reward_b,
+                &key(9),
+                1_000,
+            )
+            .unwrap()
+        };
+
+        // single reward farm
+        let instruction = build(None);
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts, reward_a_accounts());
+        assert_eq!(instruction.data, amount_data(tag, 1_000));
+
+        // the reward b accounts go after the token program
+        let instruction = build(Some(RewardBAccounts {
+            dest_reward_token: key(10),
+            vault_reward_token: key(11),
+        }));
+        let mut accounts = reward_a_accounts();
+        accounts.push(AccountMeta::new(key(10), false));
+        accounts.push(AccountMeta::new(key(11), false));
+        assert_eq!(instruction.accounts, accounts);
+        assert_eq!(instruction.data, amount_data(tag, 1_000));
+    }
+}
@@ -0,0 +1,325 @@
+mod common;
+
+use common::{amount_data, InstructionCodec};
+use raydium_contract_instructions::farm_v6_instruction::{
+    self, CreateFarmInstruction, FarmV6Instruction, NewRewardAccounts, RestartRewardInstruction,
+    RewardAccounts, RewardInfoArgs, MAX_REWARD_COUNT,
+};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+fn reward_info(n: u64) -> RewardInfoArgs {
+    RewardInfoArgs {
+        is_set: 1,
+        reward_per_second: 1_000 * n,
+        reward_open_time: 1_700_000_000,
+        reward_end_time: 1_700_000_000 + 86_400 * n,
+        reward_type: n % 2,
+    }
+}
+
+fn reward_info_data(info: &RewardInfoArgs) -> Vec<u8> {
+    [
+        info.is_set,
+        info.reward_per_second,
+        info.reward_open_time,
+        info.reward_end_time,
+        info.reward_type,
+    ]
+    .iter()
+    .flat_map(|value| value.to_le_bytes())
+    .collect()
+}
+
+fn rewards() -> [RewardAccounts; 2] {
+    [
+        RewardAccounts {
+            reward_vault: key(20),
+            user_reward_token: key(21),
+        },
+        RewardAccounts {
+            reward_vault: key(22),
+            user_reward_token: key(23),
+        },
+    ]
+}
+
+fn assert_accounts(instruction: &Instruction, expected: &[AccountMeta]) {
+    assert_eq!(instruction.program_id, farm_v6_instruction::id());
+    assert_eq!(instruction.accounts, expected);
+}
+
+#[test]
+fn codec() {
+    let amount = 0x0102_0304_0506_0708;
+    let mut reward_infos = [RewardInfoArgs::default(); MAX_REWARD_COUNT];
+    reward_infos[0] = reward_info(1);
+    reward_infos[1] = reward_info(2);
+    let create_farm_data = [
+        vec![0],
+        255u64.to_le_bytes().to_vec(),
+        reward_infos.iter().flat_map(reward_info_data).collect(),
+    ]
+    .concat();
+    assert_eq!(create_farm_data.len(), 1 + 8 + 40 * MAX_REWARD_COUNT);
+    let restart_data = [
+        &[3][..],
+        &1_700_000_000u64.to_le_bytes(),
+        &1_800_000_000u64.to_le_bytes(),
+        &500u64.to_le_bytes(),
+    ]
+    .concat();
+
+    InstructionCodec {
+        serialize: FarmV6Instruction::pack,
+        deserialize: FarmV6Instruction::unpack,
+        instructions: vec![
+            (
+                FarmV6Instruction::CreateFarm(CreateFarmInstruction {
+                    nonce: 255,
+                    reward_infos,
+                }),
+                create_farm_data,
+            ),
+            (FarmV6Instruction::Deposit(amount), amount_data(1, amount)),
+            (FarmV6Instruction::Withdraw(amount), amount_data(2, amount)),
+            (
+                FarmV6Instruction::RestartReward(RestartRewardInstruction {
+                    reward_reopen_time: 1_700_000_000,
+                    reward_end_time: 1_800_000_000,
+                    reward_per_second: 500,
+                }),
+                restart_data,
+            ),
+            (
+                FarmV6Instruction::AddReward(reward_info(3)),
+                [vec![4], reward_info_data(&reward_info(3))].concat(),
+            ),
+        ],
+        unknown_tags: vec![5, 6, u8::MAX],
+        reserved: Vec::new(),
+    }
+    .check();
+}
+
+#[test]
+fn create_farm_accounts() {
+    let new_rewards = [
+        NewRewardAccounts {
+            reward_mint: key(30),
+            user_reward_token: key(31),
+            reward_vault: key(32),
+        },
+        NewRewardAccounts {
+            reward_mint: key(33),
+            user_reward_token: key(34),
+            reward_vault: key(35),
+        },
+    ];
+    let reward_infos = [reward_info(1), reward_info(2)];
+    let instruction = farm_v6_instruction::create_farm(
+        &farm_v6_instruction::id(),
+        &key(1),
+        &key(2),
+        &key(3),
+        &key(4),
+        &key(5),
+        &key(6),
+        &key(7),
+        &key(8),
+        &new_rewards,
+        255,
+        &reward_infos,
+    )
+    .unwrap();
+    assert_accounts(
+        &instruction,
+        &[
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new(key(1), false),
+            AccountMeta::new_readonly(key(2), false),
+            AccountMeta::new(key(3), false),
+            AccountMeta::new_readonly(key(4), false),
+            AccountMeta::new(key(5), false),
+            AccountMeta::new_readonly(key(6), false),
+            AccountMeta::new(key(7), false),
+            AccountMeta::new_readonly(key(8), true),
+            AccountMeta::new_readonly(key(30), false),
+            AccountMeta::new(key(31), false),
+            AccountMeta::new(key(32), false),
+            AccountMeta::new_readonly(key(33), false),
+            AccountMeta::new(key(34), false),
+            AccountMeta::new(key(35), false),
+        ],
+    );
+    // the unused reward slots are zeroed
+    match FarmV6Instruction::unpack(&instruction.data).unwrap() {
+        FarmV6Instruction::CreateFarm(create_farm) => {
+            assert_eq!(create_farm.nonce, 255);
+            assert_eq!(create_farm.reward_infos[..2], reward_infos);
+            assert_eq!(
+                create_farm.reward_infos[2..],
+                [RewardInfoArgs::default(); 3]
+            );
+        }
+        instruction => panic!("{:?}", instruction),
+    }
+
+    let create_farm = |rewards: &[NewRewardAccounts], reward_infos: &[RewardInfoArgs]| {
+        farm_v6_instruction::create_farm(
+            &farm_v6_instruction::id(),
+            &key(1),
+            &key(2),
+            &key(3),
+            &key(4),
+            &key(5),
+            &key(6),
+            &key(7),
+            &key(8),
+            rewards,
+            255,
+            reward_infos,
+        )
+    };
+    assert_eq!(create_farm(&[], &[]), Err(ProgramError::InvalidArgument));
+    assert_eq!(
+        create_farm(&new_rewards, &reward_infos[..1]),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(
+        create_farm(&[new_rewards[0]; 6], &[reward_infos[0]; 6]),
+        Err(ProgramError::InvalidArgument)
+    );
+}
+
+#[test]
+fn deposit_accounts() {
+    let instruction = farm_v6_instruction::deposit(
+        &farm_v6_instruction::id(),
+        &key(1),
+        &key(2),
+        &key(3),
+        &key(4),
+        &key(5),
+        &key(6),
+        &rewards(),
+        1_000,
+    )
+    .unwrap();
+    assert_eq!(instruction.data, amount_data(1, 1_000));
+    assert_accounts(
+        &instruction,
+        &[
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(key(1), false),
+            AccountMeta::new_readonly(key(2), false),
+            AccountMeta::new(key(3), false),
+            AccountMeta::new(key(4), false),
+            AccountMeta::new_readonly(key(5), true),
+            AccountMeta::new(key(6), false),
+            AccountMeta::new(key(20), false),
+            AccountMeta::new(key(21), false),
+            AccountMeta::new(key(22), false),
+            AccountMeta::new(key(23), false),
+        ],
+    );
+}
+
+#[test]
+fn harvest_accounts() {
+    let instruction = farm_v6_instruction::harvest(
+        &farm_v6_instruction::id(),
+        &key(1),
+        &key(2),
+        &key(3),
+        &key(4),
+        &key(5),
+        &key(6),
+        &rewards(),
+    )
+    .unwrap();
+    // a withdraw of 0, without the system program
+    assert_eq!(instruction.data, amount_data(2, 0));
+    assert_accounts(
+        &instruction,
+        &[
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(key(1), false),
+            AccountMeta::new_readonly(key(2), false),
+            AccountMeta::new(key(3), false),
+            AccountMeta::new(key(4), false),
+            AccountMeta::new_readonly(key(5), true),
+            AccountMeta::new(key(6), false),
+            AccountMeta::new(key(20), false),
+            AccountMeta::new(key(21), false),
+            AccountMeta::new(key(22), false),
+            AccountMeta::new(key(23), false),
+        ],
+    );
+}
+
+#[test]
+fn reward_admin_accounts() {
+    let reward = NewRewardAccounts {
+        reward_mint: key(30),
+        user_reward_token: key(31),
+        reward_vault: key(32),
+    };
+    let instruction = farm_v6_instruction::add_reward(
+        &farm_v6_instruction::id(),
+        &key(1),
+        &key(2),
+        &reward,
+        &key(9),
+        reward_info(1),
+    )
+    .unwrap();
+    assert_accounts(
+        &instruction,
+        &[
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new(key(1), false),
+            AccountMeta::new_readonly(key(2), false),
+            AccountMeta::new_readonly(key(30), false),
+            AccountMeta::new(key(32), false),
+            AccountMeta::new(key(31), false),
+            AccountMeta::new_readonly(key(9), true),
+        ],
+    );
+
+    let instruction = farm_v6_instruction::restart_reward(
+        &farm_v6_instruction::id(),
+        &key(1),
+        &key(3),
+        &rewards()[0],
+        &key(9),
+        1,
+        2,
+        3,
+    )
+    .unwrap();
+    assert_accounts(
+        &instruction,
+        &[
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(key(1), false),
+            AccountMeta::new_readonly(key(3), false),
+            AccountMeta::new(key(20), false),
+            AccountMeta::new(key(21), false),
+            AccountMeta::new_readonly(key(9), true),
+        ],
+    );
+}
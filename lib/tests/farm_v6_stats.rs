@@ -0,0 +1,109 @@
+#![cfg(feature = "client")]
+
+//! The account data is synthetic, written at the offsets the farm v6
+//! program lays the fields out at, with `u128` aligned on 8 bytes.
+
+use raydium_contract_instructions::farm_v6_stats::{FarmState, FarmerLedger, RewardState};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+const FARM_STATE_LEN: usize = 1976;
+const REWARD_INFO_LEN: usize = 304;
+const FARMER_LEDGER_LEN: usize = 296;
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+fn put(data: &mut [u8], offset: usize, bytes: &[u8]) {
+    data[offset..offset + bytes.len()].copy_from_slice(bytes);
+}
+
+#[test]
+fn farm_state_layout() {
+    let mut data = vec![0; FARM_STATE_LEN];
+    put(&mut data, 8, &1u64.to_le_bytes());
+    put(&mut data, 16, &254u64.to_le_bytes());
+    put(&mut data, 24, &2u64.to_le_bytes());
+    put(&mut data, 32, &(u64::MAX as u128 + 1).to_le_bytes());
+    put(&mut data, 48, &(90 * 86_400u64).to_le_bytes());
+    put(&mut data, 72, key(1).as_ref());
+    put(&mut data, 104, key(2).as_ref());
+    // the second reward
+    let reward = 136 + REWARD_INFO_LEN;
+    put(
+        &mut data,
+        reward,
+        &(RewardState::Running as u64).to_le_bytes(),
+    );
+    put(&mut data, reward + 8, &1_700_000_000u64.to_le_bytes());
+    put(&mut data, reward + 56, &500u64.to_le_bytes());
+    put(&mut data, reward + 64, &(7u128 << 64).to_le_bytes());
+    put(&mut data, reward + 80, key(3).as_ref());
+    put(&mut data, reward + 112, key(4).as_ref());
+    put(&mut data, reward + 144, key(5).as_ref());
+    put(&mut data, reward + 176, &1u64.to_le_bytes());
+    put(&mut data, 1656, key(6).as_ref());
+    put(&mut data, 1968, &9u64.to_le_bytes());
+
+    let farm = FarmState::unpack(&data).unwrap();
+    assert_eq!(farm.state, 1);
+    assert_eq!(farm.nonce, 254);
+    assert_eq!(farm.valid_reward_token_num, 2);
+    assert_eq!(farm.reward_multiplier, u64::MAX as u128 + 1);
+    assert_eq!(farm.reward_period_max, 90 * 86_400);
+    assert_eq!((farm.lp_mint, farm.lp_vault), (key(1), key(2)));
+    assert_eq!(farm.reward_infos[0], Default::default());
+    let reward = &farm.reward_infos[1];
+    assert_eq!(reward.reward_state, RewardState::Running as u64);
+    assert_eq!(reward.reward_open_time, 1_700_000_000);
+    assert_eq!(reward.reward_per_second, 500);
+    assert_eq!(reward.acc_reward_per_share, 7 << 64);
+    assert_eq!(
+        [
+            reward.reward_vault,
+            reward.reward_mint,
+            reward.reward_sender
+        ],
+        [key(3), key(4), key(5)]
+    );
+    assert_eq!(reward.reward_type, 1);
+    assert_eq!(farm.creator, key(6));
+    assert_eq!(farm.padding[31], 9);
+
+    assert_eq!(
+        FarmState::unpack(&data[..FARM_STATE_LEN - 1]),
+        Err(ProgramError::InvalidAccountData)
+    );
+}
+
+#[test]
+fn farmer_ledger_layout() {
+    let mut data = vec![0; FARMER_LEDGER_LEN];
+    put(&mut data, 8, &1u64.to_le_bytes());
+    put(&mut data, 16, key(1).as_ref());
+    put(&mut data, 48, key(2).as_ref());
+    put(&mut data, 80, &1_000_000u64.to_le_bytes());
+    put(&mut data, 88, &(u64::MAX as u128 + 1).to_le_bytes());
+    put(&mut data, 152, &5u128.to_le_bytes());
+    put(&mut data, 168, &10u64.to_le_bytes());
+
+    let ledger = FarmerLedger::unpack(&data).unwrap();
+    assert_eq!(
+        ledger,
+        FarmerLedger {
+            padding0: 0,
+            state: 1,
+            farm: key(1),
+            owner: key(2),
+            deposited: 1_000_000,
+            reward_debts: [u64::MAX as u128 + 1, 0, 0, 0, 5],
+            vote_locked_balance: 10,
+            padding: [0; 15],
+        }
+    );
+
+    assert_eq!(
+        FarmerLedger::unpack(&data[..FARMER_LEDGER_LEN - 1]),
+        Err(ProgramError::InvalidAccountData)
+    );
+}
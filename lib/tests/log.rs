@@ -0,0 +1,184 @@
+#![cfg(feature = "client")]
+
+//! The payloads are encoded like the programs log them, with made up pool
+//! values; they were not captured from mainnet transactions.
+
+use raydium_contract_instructions::{
+    amm_instruction,
+    log::{
+        decode_ray_log, parse_ray_logs, DepositLog, InitLog, LogType, RayLog, SwapBaseInLog,
+        SwapBaseOutLog, WithdrawLog, RAY_LOG_PREFIX,
+    },
+    stable_instruction,
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+const INIT: &str = "AADxU2UAAAAABglkAAAAAAAAAEBCDwAAAAAAANYRfgMAAAAA6HZIFwAAAAcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcH";
+const DEPOSIT: &str = "AQDKmjsAAAAAgNHwCAAAAAAAAAAAAAAAAADodkgXAAAAANYRfgMAAAD2W3oECQAAAAAAEGMtXsdrBQAAAAAAAAAAACDGWryO1woAAAAAAAAAAMqaOwAAAACA0fAIAAAAAB60FRcAAAAA";
+const WITHDRAW: &str = "Ah60FRcAAAAAHrQVFwAAAAAAshGEFwAAAICnAocDAAAAFBCQGwkAAAADAAAAAAAAAAAAAAAAAAAABAAAAAAAAAAAAAAAAAAAAADKmjsAAAAAgNHwCAAAAAA=";
+const SWAP_BASE_IN: &str =
+    "AwDKmjsAAAAAAE3SCAAAAAACAAAAAAAAAADyBSoBAAAAAOh2SBcAAAAA1hF+AwAAAAh01AgAAAAA";
+const SWAP_BASE_OUT: &str =
+    "BMATAAkAAAAAAMqaOwAAAAABAAAAAAAAAADC6wsAAAAAAOh2SBcAAAAA1hF+AwAAAA08/ggAAAAA";
+
+fn swap_base_in() -> RayLog {
+    RayLog::SwapBaseIn(SwapBaseInLog {
+        log_type: 3,
+        amount_in: 1_000_000_000,
+        minimum_out: 148_000_000,
+        direction: 2,
+        user_source: 5_000_000_000,
+        pool_coin: 100_000_000_000,
+        pool_pc: 15_000_000_000,
+        out_amount: 148_141_064,
+    })
+}
+
+#[test]
+fn decode_logs() {
+    assert_eq!(
+        decode_ray_log(INIT),
+        Ok(RayLog::Init(InitLog {
+            log_type: 0,
+            time: 1_700_000_000,
+            pc_decimals: 6,
+            coin_decimals: 9,
+            pc_lot_size: 100,
+            coin_lot_size: 1_000_000,
+            pc_amount: 15_000_000_000,
+            coin_amount: 100_000_000_000,
+            market: Pubkey::new_from_array([7; 32]),
+        }))
+    );
+    assert_eq!(
+        decode_ray_log(DEPOSIT),
+        Ok(RayLog::Deposit(DepositLog {
+            log_type: 1,
+            max_coin: 1_000_000_000,
+            max_pc: 150_000_000,
+            base: 0,
+            pool_coin: 100_000_000_000,
+            pool_pc: 15_000_000_000,
+            pool_lp: 38_729_833_462,
+            calc_pnl_x: 100_000_000_000_000_000_000,
+            calc_pnl_y: 200_000_000_000_000_000_000,
+            deduct_coin: 1_000_000_000,
+            deduct_pc: 150_000_000,
+            mint_lp: 387_298_334,
+        }))
+    );
+    assert_eq!(
+        decode_ray_log(WITHDRAW),
+        Ok(RayLog::Withdraw(WithdrawLog {
+            log_type: 2,
+            withdraw_lp: 387_298_334,
+            user_lp: 387_298_334,
+            pool_coin: 101_000_000_000,
+            pool_pc: 15_150_000_000,
+            pool_lp: 39_117_131_796,
+            calc_pnl_x: 3,
+            calc_pnl_y: 4,
+            out_coin: 1_000_000_000,
+            out_pc: 150_000_000,
+        }))
+    );
+    assert_eq!(decode_ray_log(SWAP_BASE_IN), Ok(swap_base_in()));
+    assert_eq!(
+        decode_ray_log(SWAP_BASE_OUT),
+        Ok(RayLog::SwapBaseOut(SwapBaseOutLog {
+            log_type: 4,
+            max_in: 151_000_000,
+            amount_out: 1_000_000_000,
+            direction: 1,
+            user_source: 200_000_000,
+            pool_coin: 100_000_000_000,
+            pool_pc: 15_000_000_000,
+            deduct_in: 150_879_245,
+        }))
+    );
+    for (log, log_type) in [
+        (INIT, LogType::Init),
+        (DEPOSIT, LogType::Deposit),
+        (WITHDRAW, LogType::Withdraw),
+        (SWAP_BASE_IN, LogType::SwapBaseIn),
+        (SWAP_BASE_OUT, LogType::SwapBaseOut),
+    ] {
+        assert_eq!(decode_ray_log(log).unwrap().log_type(), log_type);
+    }
+}
+
+#[test]
+fn decode_invalid_logs() {
+    // not base64, empty, unknown type and truncated
+    for log in ["not base64!", "", "BQ==", &SWAP_BASE_IN[..40]] {
+        assert_eq!(decode_ray_log(log), Err(ProgramError::InvalidArgument));
+    }
+}
+
+#[test]
+fn parse_nested_logs() {
+    let amm = amm_instruction::id().to_string();
+    let stable = stable_instruction::id().to_string();
+    let aggregator = Pubkey::new_unique().to_string();
+    let token = spl_token::id().to_string();
+    let messages = [
+        format!("Program {} invoke [1]", aggregator),
+        // printed by another program
+        format!("{}{}", RAY_LOG_PREFIX, DEPOSIT),
+        format!("Program {} invoke [2]", amm),
+        format!("{}{}", RAY_LOG_PREFIX, SWAP_BASE_IN),
+        format!("Program {} invoke [3]", token),
+        "Program log: Instruction: Transfer".to_string(),
+        // noise must not end the invocations
+        "Program log: success".to_string(),
+        "Program log: failed: nothing".to_string(),
+        format!("Program {} consumed 4645 of 180000 compute units", token),
+        format!("Program {} success", token),
+        format!("{}{}", RAY_LOG_PREFIX, "AwDKmjs="),
+        format!("{}{}", RAY_LOG_PREFIX, SWAP_BASE_OUT),
+        format!("Program {} success", amm),
+        // a program id which does not parse still pairs with its success
+        "Program not-a-program-id invoke [2]".to_string(),
+        format!("{}{}", RAY_LOG_PREFIX, WITHDRAW),
+        "Program not-a-program-id success".to_string(),
+        format!("Program {} invoke [2]", stable),
+        format!("{}{}", RAY_LOG_PREFIX, INIT),
+        format!("Program {} failed: custom program error: 0x1e", stable),
+        format!("{}{}", RAY_LOG_PREFIX, DEPOSIT),
+        format!("Program {} success", aggregator),
+    ];
+    let events = parse_ray_logs(&messages);
+    assert_eq!(events.len(), 4);
+    assert_eq!(events[0].program_id, amm_instruction::id());
+    assert_eq!(events[0].log, Ok(swap_base_in()));
+    assert_eq!(events[1].program_id, amm_instruction::id());
+    assert_eq!(events[1].log, Err(ProgramError::InvalidArgument));
+    assert_eq!(
+        events[2].log.as_ref().map(RayLog::log_type),
+        Ok(LogType::SwapBaseOut)
+    );
+    assert_eq!(events[3].program_id, stable_instruction::id());
+    assert_eq!(
+        events[3].log.as_ref().map(RayLog::log_type),
+        Ok(LogType::Init)
+    );
+}
+
+#[test]
+fn parse_top_level_logs() {
+    let amm = amm_instruction::id().to_string();
+    let messages = vec![
+        format!("Program {} invoke [1]", amm),
+        format!("{}{}", RAY_LOG_PREFIX, SWAP_BASE_IN),
+        format!("Program {} consumed 30000 of 200000 compute units", amm),
+        format!("Program {} success", amm),
+        // the failure of the first invocation was truncated
+        format!("Program {} invoke [1]", amm),
+        format!("Program {} invoke [1]", Pubkey::new_unique()),
+        format!("{}{}", RAY_LOG_PREFIX, SWAP_BASE_IN),
+    ];
+    let events = parse_ray_logs(&messages);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].log, Ok(swap_base_in()));
+    assert!(parse_ray_logs::<&str>(&[]).is_empty());
+}
@@ -0,0 +1,126 @@
+#![cfg(feature = "client")]
+
+use raydium_contract_instructions::{
+    amm_stats::{AmmInfo, AmmStatus},
+    amount::{PoolPrice, TokenAmount},
+    farm_stats::FarmerInfoV2,
+    lp::LpPoolState,
+    market_stats::OpenOrders,
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+fn assert_close(value: f64, expected: f64, tolerance: f64) {
+    assert!(
+        (value - expected).abs() <= tolerance,
+        "{} is not {} within {}",
+        value,
+        expected,
+        tolerance
+    );
+}
+
+fn farmer(deposit_balance: u64) -> FarmerInfoV2 {
+    FarmerInfoV2 {
+        deposit_balance,
+        ..FarmerInfoV2::default()
+    }
+}
+
+#[test]
+fn amm_lp_position() {
+    let amm = AmmInfo {
+        status: AmmStatus::Initialized as u64,
+        coin_decimals: 6,
+        pc_decimals: 9,
+        lp_amount: 1_000_000_000,
+        need_take_pnl_coin: 1_000,
+        need_take_pnl_pc: 2_000,
+        ..AmmInfo::default()
+    };
+    let open_orders = OpenOrders {
+        account_flags: 0,
+        market: Pubkey::default(),
+        owner: Pubkey::default(),
+        native_coin_free: 0,
+        native_coin_total: 1_000,
+        native_pc_free: 0,
+        native_pc_total: 2_000,
+        free_slot_bits: 0,
+        is_bid_bits: 0,
+        orders: [0; 128],
+        client_order_ids: [0; 128],
+        referrer_rebates_accrued: 0,
+    };
+    // the pnl not taken is the open orders funds
+    let state = LpPoolState::from_amm(&amm, 1_000_000_000, 2_000_000_000, &open_orders).unwrap();
+    assert_eq!(
+        state,
+        LpPoolState::new(1_000_000_000, 1_000_000_000, 2_000_000_000, 6, 9)
+    );
+
+    // rounded down like the withdraw
+    assert_eq!(state.withdraw_amounts(3), Ok((3, 6)));
+    let state = LpPoolState {
+        lp_supply: 3_000,
+        ..state
+    };
+    assert_eq!(state.withdraw_amounts(1), Ok((333_333, 666_666)));
+    assert_eq!(
+        state.withdraw_amounts(3_001),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(
+        LpPoolState::default().withdraw_amounts(0),
+        Err(ProgramError::InsufficientFunds)
+    );
+}
+
+#[test]
+fn staked_lp_position() {
+    let state = LpPoolState::new(1_000_000_000, 1_000_000_000, 2_000_000_000, 6, 9);
+    let farmers = [farmer(2_000_000), farmer(3_000_000)];
+    let position = state.position(5_000_000, &farmers).unwrap();
+    assert_eq!(position.wallet_lp_amount, 5_000_000);
+    assert_eq!(position.staked_lp_amount, 5_000_000);
+    assert_eq!(position.lp_amount(), 10_000_000);
+    assert_eq!(position.coin_amount, TokenAmount::new(10_000_000, 6));
+    assert_eq!(position.pc_amount, TokenAmount::new(20_000_000, 9));
+
+    let price = state.reserves_price().unwrap();
+    assert_eq!(position.pc_value(&price), TokenAmount::new(40_000_000, 9));
+
+    assert_eq!(
+        state.position(u64::MAX, &farmers),
+        Err(ProgramError::InvalidArgument)
+    );
+}
+
+#[test]
+fn impermanent_loss() {
+    let entry = LpPoolState::new(1_000_000_000, 1_000_000_000, 2_000_000_000, 6, 9);
+    // the coin price went up 4 times along the constant product
+    let state = LpPoolState::new(1_000_000_000, 500_000_000, 4_000_000_000, 6, 9);
+    let price = state.reserves_price().unwrap();
+    assert_eq!(price.raw, 8.0);
+
+    let loss = state.impermanent_loss(&entry, 1_000_000, &price).unwrap();
+    assert_eq!(loss.lp_value, 8_000_000.0);
+    assert_eq!(loss.hold_value, 10_000_000.0);
+    assert_close(loss.loss_bps().unwrap(), 2_000.0, 1e-9);
+
+    // no price move, fees accrued to the reserves
+    let state = LpPoolState::new(1_000_000_000, 1_010_000_000, 2_020_000_000, 6, 9);
+    let loss = state
+        .impermanent_loss(&entry, 1_000_000, &state.reserves_price().unwrap())
+        .unwrap();
+    assert_close(loss.loss_bps().unwrap(), -100.0, 1e-9);
+
+    // valued at another price, a stable pool one
+    let price = PoolPrice {
+        raw: 2.0,
+        coin_decimals: 6,
+        pc_decimals: 9,
+    };
+    let loss = entry.impermanent_loss(&entry, 1_000_000, &price).unwrap();
+    assert_eq!(loss.loss_bps(), Some(0.0));
+}
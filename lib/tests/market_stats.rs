@@ -0,0 +1,252 @@
+//! The accounts are laid out field by field like the dex program writes them,
+//! between the `serum` head and `padding` tail. They are synthetic: the keys
+//! are made up and no mainnet dump is checked in.
+
+use raydium_contract_instructions::{
+    amm_stats::AmmInfo,
+    market_stats::{openbook_program, strip_account_padding, AccountFlag, MarketState, OpenOrders},
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+/// Dex account data writer.
+struct Account(Vec<u8>);
+
+impl Account {
+    fn new() -> Self {
+        Self(b"serum".to_vec())
+    }
+
+    fn u64(mut self, value: u64) -> Self {
+        self.0.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    fn u128(mut self, value: u128) -> Self {
+        self.0.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    fn key(mut self, n: u8) -> Self {
+        self.0.extend_from_slice(key(n).as_ref());
+        self
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        self.0.extend_from_slice(b"padding");
+        self.0
+    }
+}
+
+fn market_fields(flags: u64) -> Account {
+    Account::new()
+        .u64(flags)
+        .key(1)
+        .u64(254)
+        .key(2)
+        .key(3)
+        .key(4)
+        .u64(1_000_000)
+        .u64(10)
+        .key(5)
+        .u64(2_000_000)
+        .u64(20)
+        .u64(100)
+        .key(6)
+        .key(7)
+        .key(8)
+        .key(9)
+        .u64(100_000_000)
+        .u64(100)
+        .u64(22)
+        .u64(30)
+}
+
+fn market_data() -> Vec<u8> {
+    market_fields(AccountFlag::Initialized as u64 | AccountFlag::Market as u64).finish()
+}
+
+#[test]
+fn market_state() {
+    let data = market_data();
+    assert_eq!(data.len(), MarketState::LEN);
+    let market = MarketState::unpack(&data).unwrap();
+    assert_eq!(
+        market,
+        MarketState {
+            account_flags: 3,
+            own_address: key(1),
+            vault_signer_nonce: 254,
+            coin_mint: key(2),
+            pc_mint: key(3),
+            coin_vault: key(4),
+            coin_deposits_total: 1_000_000,
+            coin_fees_accrued: 10,
+            pc_vault: key(5),
+            pc_deposits_total: 2_000_000,
+            pc_fees_accrued: 20,
+            pc_dust_threshold: 100,
+            req_q: key(6),
+            event_q: key(7),
+            bids: key(8),
+            asks: key(9),
+            coin_lot_size: 100_000_000,
+            pc_lot_size: 100,
+            fee_rate_bps: 22,
+            referrer_rebates_accrued: 30,
+        }
+    );
+
+    // permissioned markets carry more state before the tail
+    let mut permissioned = market_fields(
+        AccountFlag::Initialized as u64
+            | AccountFlag::Market as u64
+            | AccountFlag::Permissioned as u64,
+    )
+    .key(10)
+    .key(11)
+    .finish();
+    assert_eq!(
+        MarketState::unpack(&permissioned).unwrap().coin_lot_size,
+        100_000_000
+    );
+    permissioned.truncate(MarketState::LEN - 1);
+    assert_eq!(
+        MarketState::unpack(&permissioned),
+        Err(ProgramError::InvalidAccountData)
+    );
+}
+
+#[test]
+fn market_vault_signer() {
+    let program_id = openbook_program::id();
+    let mut market = MarketState::unpack(&market_data()).unwrap();
+    market.vault_signer_nonce = (0..)
+        .find(|nonce: &u64| {
+            Pubkey::create_program_address(&[key(1).as_ref(), &nonce.to_le_bytes()], &program_id)
+                .is_ok()
+        })
+        .unwrap();
+    let signer = market.vault_signer(&program_id).unwrap();
+    assert_eq!(
+        Pubkey::create_program_address(
+            &[key(1).as_ref(), &market.vault_signer_nonce.to_le_bytes()],
+            &program_id
+        ),
+        Ok(signer)
+    );
+}
+
+#[test]
+fn lot_sizes() {
+    let market = MarketState::unpack(&market_data()).unwrap();
+    assert_eq!(market.check_lot_sizes(100_000_000, 100), Ok(()));
+    assert_eq!(
+        market.check_lot_sizes(100_000_000, 10),
+        Err(ProgramError::InvalidAccountData)
+    );
+    let amm = AmmInfo {
+        coin_lot_size: 100_000_000,
+        pc_lot_size: 100,
+        ..AmmInfo::default()
+    };
+    assert_eq!(amm.check_market_lot_sizes(&market), Ok(()));
+    let amm = AmmInfo {
+        coin_lot_size: 1,
+        ..amm
+    };
+    assert_eq!(
+        amm.check_market_lot_sizes(&market),
+        Err(ProgramError::InvalidAccountData)
+    );
+}
+
+#[test]
+fn account_framing() {
+    let data = market_data();
+    assert_eq!(
+        strip_account_padding(&data, MarketState::STATE_LEN).map(<[u8]>::len),
+        Ok(MarketState::STATE_LEN)
+    );
+
+    let mut head = data.clone();
+    head[..5].copy_from_slice(b"serun");
+    let mut tail = data.clone();
+    let len = tail.len();
+    tail[len - 7..].copy_from_slice(b"paddinq");
+    let short = &data[..data.len() - 1];
+    let mut not_market = data.clone();
+    not_market[5..13].copy_from_slice(&(AccountFlag::Initialized as u64).to_le_bytes());
+    let mut uninitialized = data.clone();
+    uninitialized[5..13].copy_from_slice(&(AccountFlag::Market as u64).to_le_bytes());
+    for data in [
+        &head[..],
+        &tail[..],
+        short,
+        &not_market[..],
+        &uninitialized[..],
+    ] {
+        assert_eq!(
+            MarketState::unpack(data),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+    // a market is no open orders
+    assert_eq!(
+        OpenOrders::unpack(&data),
+        Err(ProgramError::InvalidAccountData)
+    );
+}
+
+#[test]
+fn open_orders() {
+    let mut account = Account::new()
+        .u64(AccountFlag::Initialized as u64 | AccountFlag::OpenOrders as u64)
+        .key(1)
+        .key(12)
+        .u64(100)
+        .u64(1_100)
+        .u64(200)
+        .u64(2_200)
+        .u128(!0b11)
+        .u128(0b01);
+    for i in 0..128u128 {
+        account = account.u128(if i < 2 { 1_000 + i } else { 0 });
+    }
+    for i in 0..128u64 {
+        account = account.u64(if i < 2 { 7 + i } else { 0 });
+    }
+    let data = account.u64(40).finish();
+    assert_eq!(data.len(), OpenOrders::LEN);
+
+    let open_orders = OpenOrders::unpack(&data).unwrap();
+    assert_eq!(open_orders.account_flags, 5);
+    assert_eq!(open_orders.market, key(1));
+    assert_eq!(open_orders.owner, key(12));
+    assert_eq!(
+        (
+            open_orders.native_coin_free,
+            open_orders.native_coin_total,
+            open_orders.native_pc_free,
+            open_orders.native_pc_total
+        ),
+        (100, 1_100, 200, 2_200)
+    );
+    assert_eq!(open_orders.free_slot_bits, !0b11);
+    assert_eq!(open_orders.is_bid_bits, 0b01);
+    assert_eq!(open_orders.orders[..3], [1_000, 1_001, 0]);
+    assert_eq!(open_orders.client_order_ids[..3], [7, 8, 0]);
+    assert_eq!(open_orders.referrer_rebates_accrued, 40);
+
+    assert_eq!(
+        OpenOrders::unpack(&data[..data.len() - 8]),
+        Err(ProgramError::InvalidAccountData)
+    );
+    assert_eq!(
+        OpenOrders::unpack(&market_data()),
+        Err(ProgramError::InvalidAccountData)
+    );
+}
@@ -0,0 +1,242 @@
+#![cfg(feature = "client")]
+
+use raydium_contract_instructions::{
+    amm_instruction::{self, AmmInstruction},
+    amm_math,
+    pool::{AmmPool, RaydiumPool, StablePool, SwapDirection, UserPoolAccounts},
+    route_instruction::RoutePoolKeys,
+    router::RoutePool,
+    stable_instruction,
+    stable_math::{ModelData, MODEL_DATA_LEN},
+};
+use solana_program::{instruction::Instruction, program_error::ProgramError, pubkey::Pubkey};
+use spl_associated_token_account::get_associated_token_address;
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+fn amm_pool() -> AmmPool {
+    AmmPool {
+        keys: RoutePoolKeys {
+            program_id: amm_instruction::id(),
+            amm_pool: key(10),
+            ..RoutePoolKeys::default()
+        },
+        amm_target_orders: key(11),
+        lp_mint: key(12),
+        coin_mint: key(1),
+        pc_mint: key(2),
+        coin_decimals: 6,
+        pc_decimals: 9,
+        coin_reserve: 1_000_000_000,
+        pc_reserve: 2_000_000_000,
+        swap_fee_numerator: 25,
+        swap_fee_denominator: 10_000,
+    }
+}
+
+/// Model data sampling the `x * y = 10^12` curve.
+fn constant_product_model_data() -> ModelData {
+    let count = 50_000u64;
+    let mut data = vec![0u8; MODEL_DATA_LEN];
+    data[16..24].copy_from_slice(&1_000_000u64.to_le_bytes());
+    data[24..32].copy_from_slice(&count.to_le_bytes());
+    for i in 0..count {
+        let x = 100_000 + i * 200;
+        let y = 1_000_000_000_000 / x;
+        let offset = 32 + i as usize * 24;
+        data[offset..offset + 8].copy_from_slice(&x.to_le_bytes());
+        data[offset + 8..offset + 16].copy_from_slice(&y.to_le_bytes());
+    }
+    ModelData::unpack(&data).unwrap()
+}
+
+fn user() -> UserPoolAccounts {
+    UserPoolAccounts {
+        owner: key(100),
+        token_coin: key(101),
+        token_pc: key(102),
+        token_lp: key(103),
+    }
+}
+
+fn has_account(instruction: &Instruction, pubkey: &Pubkey) -> bool {
+    instruction
+        .accounts
+        .iter()
+        .any(|account| account.pubkey == *pubkey)
+}
+
+/// Pc value of `amount_in` of the pool coin, as a dashboard would price it
+/// without knowing the pool type.
+fn pc_value(pool: &impl RaydiumPool, amount_in: u64) -> u64 {
+    let (coin_mint, _) = pool.mints();
+    pool.quote_in(&coin_mint, amount_in).unwrap()
+}
+
+#[test]
+fn amm_pool_quotes() {
+    let pool = amm_pool();
+    assert_eq!(pool.mints(), (key(1), key(2)));
+    assert_eq!(pool.reserves(), (1_000_000_000, 2_000_000_000));
+    assert_eq!(pool.other_mint(&key(2)), Some(key(1)));
+    assert_eq!(pool.other_mint(&key(3)), None);
+
+    assert_eq!(pool.quote_in(&key(1), 1_000_000), Ok(1_993_011));
+    assert_eq!(pool.quote_out(&key(2), 1_990_000), Ok(998_489));
+    assert_eq!(
+        pool.quote_in(&key(3), 1_000_000),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(
+        pool.quote_out(&key(1), 1_000_000_000),
+        Err(ProgramError::InsufficientFunds)
+    );
+    assert_eq!(pc_value(&pool, 1_000_000), 1_993_011);
+}
+
+#[test]
+fn amm_pool_instructions() {
+    let pool = amm_pool();
+    let user = user();
+
+    let swap = pool.build_swap(&user, &key(2), 1_000, 900, false).unwrap();
+    assert_eq!(swap.program_id, amm_instruction::id());
+    assert!(matches!(
+        AmmInstruction::unpack(&swap.data).unwrap(),
+        AmmInstruction::SwapBaseOut(_)
+    ));
+    // from pc to coin
+    let accounts = &swap.accounts;
+    assert_eq!(accounts[accounts.len() - 3].pubkey, user.token_pc);
+    assert_eq!(accounts[accounts.len() - 2].pubkey, user.token_coin);
+    assert_eq!(accounts[accounts.len() - 1].pubkey, user.owner);
+
+    let deposit = pool.build_deposit(&user, 10, 20, 0).unwrap();
+    assert!(matches!(
+        AmmInstruction::unpack(&deposit.data).unwrap(),
+        AmmInstruction::Deposit(_)
+    ));
+    for pubkey in [pool.amm_target_orders, pool.lp_mint, user.token_lp] {
+        assert!(has_account(&deposit, &pubkey));
+    }
+
+    let withdraw = pool.build_withdraw(&user, 10).unwrap();
+    assert!(matches!(
+        AmmInstruction::unpack(&withdraw.data).unwrap(),
+        AmmInstruction::Withdraw(_)
+    ));
+    assert!(has_account(&withdraw, &user.token_lp));
+}
+
+#[test]
+fn stable_pool() {
+    let model_data = constant_product_model_data();
+    let amm = amm_pool();
+    let pool = StablePool {
+        keys: RoutePoolKeys {
+            program_id: stable_instruction::id(),
+            model_data_account: Some(key(13)),
+            ..amm.keys
+        },
+        amm_target_orders: amm.amm_target_orders,
+        lp_mint: amm.lp_mint,
+        coin_mint: amm.coin_mint,
+        pc_mint: amm.pc_mint,
+        coin_decimals: amm.coin_decimals,
+        pc_decimals: amm.pc_decimals,
+        coin_reserve: 1_000_000_000,
+        pc_reserve: 1_000_000_000,
+        swap_fee_numerator: 25,
+        swap_fee_denominator: 10_000,
+        model_data: &model_data,
+    };
+
+    // close to the constant product quote, within the interpolation error
+    let exact =
+        amm_math::swap_base_out_amount_in(50_000_000, 1_000_000_000, 1_000_000_000, 25, 10_000)
+            .unwrap();
+    let quote = pool.quote_out(&key(2), 50_000_000).unwrap();
+    assert!(quote <= exact + exact / 1_000 && quote + exact / 1_000 >= exact);
+    let amount_out = pool.quote_in(&key(1), quote).unwrap();
+    assert!(amount_out + 50_000 >= 50_000_000 && amount_out <= 50_050_000);
+    assert_eq!(
+        pool.quote_out(&key(1), 1_000_000_000),
+        Err(ProgramError::InsufficientFunds)
+    );
+
+    let user = user();
+    for instruction in [
+        pool.build_swap(&user, &key(1), 1_000, 1, true).unwrap(),
+        pool.build_deposit(&user, 10, 20, 1).unwrap(),
+        pool.build_withdraw(&user, 10).unwrap(),
+    ] {
+        assert_eq!(instruction.program_id, stable_instruction::id());
+        assert!(has_account(&instruction, &key(13)));
+    }
+
+    // pools of both types behind the route pool
+    let pools = [RoutePool::Amm(amm), RoutePool::Stable(pool)];
+    assert_eq!(pools[0].quote_in(&key(1), 1_000_000), Ok(1_993_011));
+    assert_eq!(pools[1].lp_mint(), key(12));
+    assert_eq!(pools[1].keys().model_data_account, Some(key(13)));
+}
+
+#[test]
+fn swap_direction() {
+    let pool = amm_pool();
+    let owner = key(100);
+    let owner_coin = get_associated_token_address(&owner, &key(1));
+    let owner_pc = get_associated_token_address(&owner, &key(2));
+
+    let direction = SwapDirection::from_input_mint(&pool, &key(2)).unwrap();
+    assert_eq!(direction, SwapDirection::PcToCoin);
+    assert_eq!(
+        SwapDirection::from_input_mint(&pool, &key(3)),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(direction.mints(&pool), (key(2), key(1)));
+    assert_eq!(
+        direction.user_token_accounts(&pool, &owner),
+        (owner_pc, owner_coin)
+    );
+    assert_eq!(
+        UserPoolAccounts::associated(&owner, &pool).token_lp,
+        get_associated_token_address(&owner, &pool.lp_mint)
+    );
+
+    let swap = SwapDirection::CoinToPc
+        .build_swap(&pool, &owner, 1_000, 1, true)
+        .unwrap();
+    let accounts = &swap.accounts;
+    assert_eq!(accounts[accounts.len() - 3].pubkey, owner_coin);
+    assert_eq!(accounts[accounts.len() - 2].pubkey, owner_pc);
+    assert_eq!(accounts[accounts.len() - 1].pubkey, owner);
+
+    assert_eq!(
+        SwapDirection::CoinToPc.build_swap_checked(
+            &pool,
+            &owner,
+            &owner_coin,
+            &owner_pc,
+            1_000,
+            1,
+            true
+        ),
+        Ok(swap)
+    );
+    // source and destination swapped
+    assert_eq!(
+        SwapDirection::CoinToPc.build_swap_checked(
+            &pool,
+            &owner,
+            &owner_pc,
+            &owner_coin,
+            1_000,
+            1,
+            true
+        ),
+        Err(ProgramError::InvalidArgument)
+    );
+}
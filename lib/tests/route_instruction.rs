@@ -0,0 +1,98 @@
+use raydium_contract_instructions::route_instruction::{
+    self, get_middle_status, RouteInstruction, RoutePoolKeys, RouteSwapInInstruction,
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+fn pool_keys(seed: u8, model_data_account: Option<Pubkey>) -> RoutePoolKeys {
+    let key = |n: u8| Pubkey::new_from_array([seed + n; 32]);
+    RoutePoolKeys {
+        program_id: key(0),
+        amm_pool: key(1),
+        amm_authority: key(2),
+        amm_open_orders: key(3),
+        amm_coin_vault: key(4),
+        amm_pc_vault: key(5),
+        model_data_account,
+        market_program: key(6),
+        market: key(7),
+        market_bids: key(8),
+        market_asks: key(9),
+        market_event_queue: key(10),
+        market_coin_vault: key(11),
+        market_pc_vault: key(12),
+        market_vault_signer: key(13),
+    }
+}
+
+#[test]
+fn pack_exact_bytes() {
+    let instruction = RouteInstruction::SwapInFixedIn(RouteSwapInInstruction {
+        amount_in: 1_000,
+        minimum_amount_out: 990,
+    });
+    let mut expected = vec![0];
+    expected.extend_from_slice(&1_000u64.to_le_bytes());
+    expected.extend_from_slice(&990u64.to_le_bytes());
+    assert_eq!(instruction.pack().unwrap(), expected);
+    assert_eq!(RouteInstruction::unpack(&expected).unwrap(), instruction);
+
+    assert_eq!(RouteInstruction::SwapOutFixedIn.pack().unwrap(), vec![1]);
+    assert_eq!(
+        RouteInstruction::unpack(&[1]).unwrap(),
+        RouteInstruction::SwapOutFixedIn
+    );
+
+    for data in [&[][..], &expected[..16], &[2]] {
+        assert_eq!(
+            RouteInstruction::unpack(data),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
+
+#[test]
+fn route_swap_accounts() {
+    let program_id = route_instruction::id();
+    let from_pool = pool_keys(10, None);
+    let to_pool = pool_keys(40, Some(Pubkey::new_from_array([99; 32])));
+    let middle_mint = Pubkey::new_from_array([100; 32]);
+    let source = Pubkey::new_from_array([101; 32]);
+    let middle = Pubkey::new_from_array([102; 32]);
+    let destination = Pubkey::new_from_array([103; 32]);
+    let owner = Pubkey::new_from_array([104; 32]);
+
+    let [swap_in, swap_out] = route_instruction::route_swap_base_in(
+        &program_id,
+        &from_pool,
+        &to_pool,
+        &middle_mint,
+        &source,
+        &middle,
+        &destination,
+        &owner,
+        1_000,
+        990,
+    )
+    .unwrap();
+    let middle_status = get_middle_status(&program_id, &from_pool.amm_pool, &middle_mint, &owner);
+
+    // amm v4 hop: 2 programs, 14 pool accounts, 4 user accounts
+    assert_eq!(swap_in.accounts.len(), 20);
+    assert_eq!(swap_in.accounts[2].pubkey, from_pool.program_id);
+    assert_eq!(swap_in.accounts[7].pubkey, from_pool.amm_pc_vault);
+    assert_eq!(swap_in.accounts[8].pubkey, from_pool.market_program);
+    let user: Vec<_> = swap_in.accounts[16..].iter().map(|a| a.pubkey).collect();
+    assert_eq!(user, vec![source, middle, middle_status, owner]);
+    assert!(swap_in.accounts[19].is_signer);
+
+    // stable hop: model data account after the vaults
+    assert_eq!(swap_out.accounts.len(), 21);
+    assert_eq!(
+        swap_out.accounts[8].pubkey,
+        Pubkey::new_from_array([99; 32])
+    );
+    assert!(!swap_out.accounts[8].is_writable);
+    let user: Vec<_> = swap_out.accounts[17..].iter().map(|a| a.pubkey).collect();
+    assert_eq!(user, vec![middle, destination, middle_status, owner]);
+    assert_eq!(swap_out.data, vec![1]);
+}
@@ -0,0 +1,304 @@
+#![cfg(feature = "client")]
+
+use raydium_contract_instructions::{
+    amm_instruction, amm_math,
+    pool::{AmmPool, RaydiumPool, StablePool, UserPoolAccounts},
+    route_instruction::RoutePoolKeys,
+    router, slippage, stable_instruction,
+    stable_math::{ModelData, MODEL_DATA_LEN},
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+fn mint(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+fn amm_pool(seed: u8, coin: (u8, u64), pc: (u8, u64)) -> AmmPool {
+    AmmPool {
+        keys: RoutePoolKeys {
+            program_id: amm_instruction::id(),
+            amm_pool: Pubkey::new_from_array([seed; 32]),
+            ..RoutePoolKeys::default()
+        },
+        coin_mint: mint(coin.0),
+        pc_mint: mint(pc.0),
+        coin_reserve: coin.1,
+        pc_reserve: pc.1,
+        swap_fee_numerator: 25,
+        swap_fee_denominator: 10_000,
+        ..AmmPool::default()
+    }
+}
+
+#[test]
+fn amm_swap_amounts() {
+    assert_eq!(amm_math::checked_ceil_div(25, 10_000), Some(0));
+    assert_eq!(amm_math::checked_ceil_div(5_000, 10_000), Some(1));
+    assert_eq!(amm_math::checked_ceil_div(10_001, 10_000), Some(2));
+    assert_eq!(amm_math::checked_ceil_div(1, 0), None);
+
+    assert_eq!(amm_math::swap_fee(1_000_000, 25, 10_000), Ok(2_500));
+    assert_eq!(
+        amm_math::swap_base_in_amount_out(1_000_000, 1_000_000_000, 2_000_000_000, 25, 10_000),
+        Ok(1_993_011)
+    );
+    assert_eq!(
+        amm_math::swap_base_out_amount_in(1_990_000, 1_000_000_000, 2_000_000_000, 25, 10_000),
+        Ok(998_489)
+    );
+    assert_eq!(
+        amm_math::swap_base_out_amount_in(2_000_000_000, 1_000_000_000, 2_000_000_000, 25, 10_000),
+        Err(ProgramError::InsufficientFunds)
+    );
+}
+
+#[test]
+fn best_route() {
+    let (a, b, c) = (1, 2, 3);
+    let pools = [
+        amm_pool(10, (a, 1_000_000_000), (b, 1_000_000_000)),
+        amm_pool(11, (a, 1_000_000_000), (c, 4_000_000_000)),
+        amm_pool(12, (c, 1_000_000_000), (b, 1_000_000_000)),
+    ];
+    let amount_in = 1_000_000;
+
+    let route = router::find_best_route(&pools, &mint(a), &mint(b), amount_in, 3)
+        .unwrap()
+        .unwrap();
+    let middle = pools[1].quote_in(&mint(a), amount_in).unwrap();
+    let amount_out = pools[2].quote_in(&mint(c), middle).unwrap();
+    assert_eq!(route.hops.len(), 2);
+    assert_eq!((route.hops[0].pool, route.hops[1].pool), (1, 2));
+    assert_eq!(route.hops[0].amount_out, middle);
+    assert_eq!(route.hops[1].output_mint, mint(b));
+    assert_eq!(route.amount_out, amount_out);
+
+    // direct pool only
+    let route = router::find_best_route(&pools, &mint(a), &mint(b), amount_in, 1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(route.hops.len(), 1);
+    assert_eq!(route.hops[0].pool, 0);
+    assert_eq!(
+        route.amount_out,
+        amm_math::swap_base_in_amount_out(amount_in, 1_000_000_000, 1_000_000_000, 25, 10_000)
+            .unwrap()
+    );
+
+    assert_eq!(
+        router::find_best_route(&pools, &mint(a), &mint(9), amount_in, 3),
+        Ok(None)
+    );
+    assert_eq!(
+        router::find_best_route(&pools, &mint(a), &mint(b), amount_in, 4),
+        Err(ProgramError::InvalidArgument)
+    );
+}
+
+#[test]
+fn route_swap_instructions() {
+    let (a, b, c) = (1, 2, 3);
+    let pools = [
+        amm_pool(11, (a, 1_000_000_000), (c, 4_000_000_000)),
+        amm_pool(12, (c, 1_000_000_000), (b, 1_000_000_000)),
+    ];
+    let owner = Pubkey::new_unique();
+    let route = router::find_best_route(&pools, &mint(a), &mint(b), 1_000_000, 2)
+        .unwrap()
+        .unwrap();
+    let user_token_account = |mint: &Pubkey| Pubkey::new_from_array([mint.to_bytes()[0] + 100; 32]);
+    let swaps = route
+        .swap_instructions(&pools, &owner, user_token_account, 50)
+        .unwrap();
+
+    assert_eq!(swaps.len(), 2);
+    assert_eq!(swaps[0].amount_in, 1_000_000);
+    assert_eq!(
+        swaps[0].minimum_amount_out,
+        slippage::minimum_amount_out(route.hops[0].amount_out, 50).unwrap()
+    );
+    // the second hop swaps the guaranteed amount of the first one
+    assert_eq!(swaps[1].amount_in, swaps[0].minimum_amount_out);
+    let expected = pools[1].quote_in(&mint(c), swaps[1].amount_in).unwrap();
+    assert_eq!(
+        swaps[1].minimum_amount_out,
+        slippage::minimum_amount_out(expected, 50).unwrap()
+    );
+    for swap in &swaps {
+        assert_eq!(swap.instruction.program_id, amm_instruction::id());
+    }
+    // user source, destination and owner
+    let accounts = &swaps[1].instruction.accounts;
+    assert_eq!(
+        accounts[accounts.len() - 3].pubkey,
+        user_token_account(&mint(c))
+    );
+    assert_eq!(
+        accounts[accounts.len() - 2].pubkey,
+        user_token_account(&mint(b))
+    );
+    assert_eq!(accounts[accounts.len() - 1].pubkey, owner);
+}
+
+/// Model data sampling the `x * y = 10^12` curve.
+fn constant_product_model_data() -> ModelData {
+    let count = 50_000u64;
+    let mut data = vec![0u8; MODEL_DATA_LEN];
+    data[16..24].copy_from_slice(&1_000_000u64.to_le_bytes());
+    data[24..32].copy_from_slice(&count.to_le_bytes());
+    for i in 0..count {
+        let x = 100_000 + i * 200;
+        let y = 1_000_000_000_000 / x;
+        let offset = 32 + i as usize * 24;
+        data[offset..offset + 8].copy_from_slice(&x.to_le_bytes());
+        data[offset + 8..offset + 16].copy_from_slice(&y.to_le_bytes());
+    }
+    ModelData::unpack(&data).unwrap()
+}
+
+#[test]
+fn stable_curve() {
+    let model_data = constant_product_model_data();
+    assert_eq!(model_data.elements.len(), 50_000);
+
+    let amm = amm_pool(20, (1, 1_000_000_000), (2, 1_000_000_000));
+    let mut pool = StablePool {
+        keys: amm.keys,
+        amm_target_orders: amm.amm_target_orders,
+        lp_mint: amm.lp_mint,
+        coin_mint: amm.coin_mint,
+        pc_mint: amm.pc_mint,
+        coin_decimals: amm.coin_decimals,
+        pc_decimals: amm.pc_decimals,
+        coin_reserve: amm.coin_reserve,
+        pc_reserve: amm.pc_reserve,
+        swap_fee_numerator: amm.swap_fee_numerator,
+        swap_fee_denominator: amm.swap_fee_denominator,
+        model_data: &model_data,
+    };
+    pool.keys.program_id = stable_instruction::id();
+    pool.keys.model_data_account = Some(Pubkey::new_unique());
+
+    // close to the constant product quote, within the interpolation error
+    for (input_mint, amount_in) in [(1, 1_000_000), (2, 50_000_000)] {
+        let exact =
+            amm_math::swap_base_in_amount_out(amount_in, 1_000_000_000, 1_000_000_000, 25, 10_000)
+                .unwrap();
+        let quote = pool.quote_in(&mint(input_mint), amount_in).unwrap();
+        assert!(quote <= exact + exact / 1_000 && quote + exact / 1_000 >= exact);
+    }
+    // out of the sampled curve
+    assert_eq!(
+        pool.quote_in(&mint(1), 100_000_000_000),
+        Err(ProgramError::InsufficientFunds)
+    );
+
+    let user = UserPoolAccounts {
+        owner: mint(103),
+        token_coin: mint(101),
+        token_pc: mint(102),
+        token_lp: mint(104),
+    };
+    let swap = pool.build_swap(&user, &mint(1), 1_000, 1, true).unwrap();
+    assert_eq!(swap.program_id, stable_instruction::id());
+    assert_eq!(
+        swap.accounts[6].pubkey,
+        pool.keys.model_data_account.unwrap()
+    );
+
+    // the quote is the integer curve result, the minimum allows the slippage only
+    let pools = [pool];
+    let route = router::find_best_route(&pools, &mint(1), &mint(2), 1_000_000, 1)
+        .unwrap()
+        .unwrap();
+    let swaps = route
+        .swap_instructions(&pools, &mint(103), |mint| *mint, 50)
+        .unwrap();
+    assert_eq!(
+        route.amount_out,
+        pools[0].quote_in(&mint(1), 1_000_000).unwrap()
+    );
+    assert_eq!(
+        swaps[0].minimum_amount_out,
+        slippage::minimum_amount_out(route.amount_out, 50).unwrap()
+    );
+}
+
+/// Model data of five samples with a multiplier of 1 000.
+fn sampled_model_data() -> ModelData {
+    let mut data = vec![0u8; MODEL_DATA_LEN];
+    data[16..24].copy_from_slice(&1_000u64.to_le_bytes());
+    data[24..32].copy_from_slice(&5u64.to_le_bytes());
+    for (i, (x, y)) in [
+        (1_000u64, 4_000u64),
+        (2_000, 2_000),
+        (3_000, 1_400),
+        (4_000, 1_000),
+        (6_000, 600),
+    ]
+    .iter()
+    .enumerate()
+    {
+        let offset = 32 + i * 24;
+        data[offset..offset + 8].copy_from_slice(&x.to_le_bytes());
+        data[offset + 8..offset + 16].copy_from_slice(&y.to_le_bytes());
+    }
+    ModelData::unpack(&data).unwrap()
+}
+
+fn stable_pool(model_data: &ModelData, coin_reserve: u64, pc_reserve: u64) -> StablePool<'_> {
+    let amm = amm_pool(21, (1, coin_reserve), (2, pc_reserve));
+    StablePool {
+        keys: amm.keys,
+        amm_target_orders: amm.amm_target_orders,
+        lp_mint: amm.lp_mint,
+        coin_mint: amm.coin_mint,
+        pc_mint: amm.pc_mint,
+        coin_decimals: amm.coin_decimals,
+        pc_decimals: amm.pc_decimals,
+        coin_reserve,
+        pc_reserve,
+        swap_fee_numerator: amm.swap_fee_numerator,
+        swap_fee_denominator: amm.swap_fee_denominator,
+        model_data,
+    }
+}
+
+#[test]
+fn stable_integer_quotes() {
+    let model_data = sampled_model_data();
+
+    // The reserves are on the sample (2 000, 2 000): the ratio is
+    // 2 000 000 * 1 000 / 2 000, a table unit is 1 000 real units. 500 000 in
+    // less a fee of 1 250 is 498 table units, y at x 2 498 is
+    // 2 000 - 498 * 600 / 1 000 rounded up to 1 702, so 298 table units out.
+    let pool = stable_pool(&model_data, 2_000_000, 2_000_000);
+    assert_eq!(pool.quote_in(&mint(1), 500_000), Ok(298_000));
+    assert_eq!(pool.quote_in(&mint(2), 500_000), Ok(249_000));
+    // 298 table units out need x at y 1 702, 2 000 + 298 * 1 000 / 600 rounded
+    // up to 2 497, and the fee on the 497 000 in
+    assert_eq!(
+        model_data.get_amount_in(2_000_000, 2_000_000, 298_000, true),
+        Ok(497_000)
+    );
+    assert_eq!(pool.quote_out(&mint(2), 298_000), Ok(498_246));
+
+    // reserves between the samples (2 000, 2 000) and (3 000, 1 400)
+    let pool = stable_pool(&model_data, 3_000_000, 2_000_000);
+    assert_eq!(pool.quote_in(&mint(1), 500_000), Ok(298_099));
+    assert_eq!(pool.quote_in(&mint(2), 500_000), Ok(685_272));
+    assert_eq!(pool.quote_out(&mint(2), 298_099), Ok(498_873));
+    assert_eq!(pool.quote_out(&mint(1), 685_272), Ok(497_682));
+
+    // past the last sample
+    assert_eq!(
+        pool.quote_in(&mint(1), 10_000_000),
+        Err(ProgramError::InsufficientFunds)
+    );
+    // reserves off the sampled ratios
+    let pool = stable_pool(&model_data, 100_000, 2_000_000);
+    assert_eq!(
+        pool.quote_in(&mint(1), 1_000),
+        Err(ProgramError::InvalidArgument)
+    );
+}
@@ -0,0 +1,151 @@
+#![cfg(feature = "serde")]
+#![recursion_limit = "256"]
+
+use raydium_contract_instructions::{amm_stats::AmmInfo, farm_stats::FarmPool};
+use serde_json::json;
+use solana_program::pubkey::Pubkey;
+
+const DEFAULT_KEY: &str = "11111111111111111111111111111111";
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+#[test]
+fn amm_info_json() {
+    let amm = AmmInfo {
+        status: 6,
+        nonce: 254,
+        coin_decimals: 9,
+        pc_decimals: 6,
+        trade_fee_numerator: 25,
+        trade_fee_denominator: 10_000,
+        padding: [1, 2],
+        swap_coin_in_amount: u128::MAX,
+        swap_pc_out_amount: 1 << 64,
+        swap_acc_pc_fee: u64::MAX,
+        coin_vault: key(1),
+        pc_vault: key(2),
+        lp_mint: key(3),
+        lp_amount: 1_000,
+        ..AmmInfo::default()
+    };
+    let value = serde_json::to_value(amm).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "status": 6,
+            "nonce": 254,
+            "order_num": 0,
+            "depth": 0,
+            "coin_decimals": 9,
+            "pc_decimals": 6,
+            "state": 0,
+            "reset_flag": 0,
+            "min_size": 0,
+            "vol_max_cut_ratio": 0,
+            "amount_wave": 0,
+            "coin_lot_size": 0,
+            "pc_lot_size": 0,
+            "min_price_multiplier": 0,
+            "max_price_multiplier": 0,
+            "sys_decimal_value": 0,
+            "min_separate_numerator": 0,
+            "min_separate_denominator": 0,
+            "trade_fee_numerator": 25,
+            "trade_fee_denominator": 10_000,
+            "pnl_numerator": 0,
+            "pnl_denominator": 0,
+            "swap_fee_numerator": 0,
+            "swap_fee_denominator": 0,
+            "need_take_pnl_coin": 0,
+            "need_take_pnl_pc": 0,
+            "total_pnl_pc": 0,
+            "total_pnl_coin": 0,
+            "pool_open_time": 0,
+            "padding": [1, 2],
+            "orderbook_to_init_time": 0,
+            "swap_coin_in_amount": "340282366920938463463374607431768211455",
+            "swap_pc_out_amount": "18446744073709551616",
+            "swap_acc_pc_fee": 18_446_744_073_709_551_615u64,
+            "swap_pc_in_amount": "0",
+            "swap_coin_out_amount": "0",
+            "swap_acc_coin_fee": 0,
+            "coin_vault": "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi",
+            "pc_vault": "8qbHbw2BbbTHBW1sbeqakYXVKRQM8Ne7pLK7m6CVfeR",
+            "coin_vault_mint": DEFAULT_KEY,
+            "pc_vault_mint": DEFAULT_KEY,
+            "lp_mint": "CktRuQ2mttgRGkXJtyksdKHjUdc2C4TgDzyB98oEzy8",
+            "open_orders": DEFAULT_KEY,
+            "market": DEFAULT_KEY,
+            "market_program": DEFAULT_KEY,
+            "target_orders": DEFAULT_KEY,
+            "padding1": [0, 0, 0, 0, 0, 0, 0, 0],
+            "amm_owner": DEFAULT_KEY,
+            "lp_amount": 1_000,
+            "client_order_id": 0,
+            "padding2": [0, 0],
+        })
+    );
+    assert!(serde_json::from_value::<AmmInfo>(value).unwrap() == amm);
+}
+
+#[test]
+fn farm_pool_json() {
+    let farm = FarmPool {
+        state: 1,
+        nonce: 255,
+        lp_vault: key(1),
+        reward_vault_a: key(2),
+        reward_total_a: 500,
+        acc_ray_per_share_a: 12_345_678_901_234_567_890_123,
+        reward_per_slot_a: 7,
+        reward_vault_b: Some(key(3)),
+        reward_total_b: 0,
+        acc_ray_per_share_b: 0,
+        reward_per_slot_b: 0,
+        last_pool_update_slot: 99,
+        owner: Pubkey::default(),
+    };
+    let json = serde_json::to_string(&farm).unwrap();
+    assert_eq!(
+        json,
+        concat!(
+            r#"{"state":1,"nonce":255,"#,
+            r#""lp_vault":"4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi","#,
+            r#""reward_vault_a":"8qbHbw2BbbTHBW1sbeqakYXVKRQM8Ne7pLK7m6CVfeR","#,
+            r#""reward_total_a":500,"acc_ray_per_share_a":"12345678901234567890123","#,
+            r#""reward_per_slot_a":7,"#,
+            r#""reward_vault_b":"CktRuQ2mttgRGkXJtyksdKHjUdc2C4TgDzyB98oEzy8","#,
+            r#""reward_total_b":0,"acc_ray_per_share_b":"0","reward_per_slot_b":0,"#,
+            r#""last_pool_update_slot":99,"owner":"11111111111111111111111111111111"}"#,
+        )
+    );
+    assert_eq!(serde_json::from_str::<FarmPool>(&json).unwrap(), farm);
+}
+
+#[test]
+fn option_pubkey_json() {
+    let farm = FarmPool {
+        reward_vault_b: None,
+        ..FarmPool::default()
+    };
+    let value = serde_json::to_value(farm).unwrap();
+    assert_eq!(value["reward_vault_b"], json!(null));
+    assert_eq!(serde_json::from_value::<FarmPool>(value).unwrap(), farm);
+
+    // keys are base58 strings, u128 values decimal strings
+    let mut value = serde_json::to_value(farm).unwrap();
+    value["reward_vault_b"] = json!(key(1).to_string());
+    assert_eq!(
+        serde_json::from_value::<FarmPool>(value.clone())
+            .unwrap()
+            .reward_vault_b,
+        Some(key(1))
+    );
+    value["reward_vault_b"] = json!("not a key");
+    assert!(serde_json::from_value::<FarmPool>(value.clone()).is_err());
+    value["reward_vault_b"] = json!(null);
+    value["acc_ray_per_share_a"] = json!(1);
+    assert!(serde_json::from_value::<FarmPool>(value).is_err());
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "client")]
+
+use raydium_contract_instructions::{
+    amm_math,
+    slippage::{self, SwapPriceReport},
+};
+use solana_program::program_error::ProgramError;
+
+#[test]
+fn slippage_bounds() {
+    assert_eq!(slippage::minimum_amount_out(1_000_000, 300), Ok(970_000));
+    // rounded down
+    assert_eq!(slippage::minimum_amount_out(999, 50), Ok(994));
+    assert_eq!(slippage::minimum_amount_out(999, 0), Ok(999));
+    assert_eq!(
+        slippage::minimum_amount_out(1, 50),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(
+        slippage::minimum_amount_out(1_000_000, 10_000),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(
+        slippage::minimum_amount_out(1_000_000, 10_001),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(
+        slippage::check_minimum_amount_out(0),
+        Err(ProgramError::InvalidArgument)
+    );
+
+    assert_eq!(slippage::maximum_amount_in(1_000_000, 300), Ok(1_030_000));
+    // rounded up
+    assert_eq!(slippage::maximum_amount_in(999, 50), Ok(1_004));
+    assert_eq!(slippage::maximum_amount_in(999, 0), Ok(999));
+    assert_eq!(
+        slippage::maximum_amount_in(u64::MAX, 1),
+        Err(ProgramError::InvalidArgument)
+    );
+}
+
+#[test]
+fn price_report() {
+    let (reserve_in, reserve_out) = (1_000_000_000, 2_000_000_000);
+    let amount_in = 10_000_000;
+    let amount_out =
+        amm_math::swap_base_in_amount_out(amount_in, reserve_in, reserve_out, 25, 10_000).unwrap();
+
+    let report = SwapPriceReport::new(reserve_in, reserve_out, amount_in, amount_out).unwrap();
+    assert_eq!(report.spot_price, 2.0);
+    assert_eq!(report.effective_price, amount_out as f64 / amount_in as f64);
+    // 25 bps of fee and about 1% of impact of a 1% deep swap
+    assert!(report.price_impact_bps > 120.0 && report.price_impact_bps < 125.0);
+
+    assert_eq!(slippage::spot_price(0, 1), None);
+    assert_eq!(slippage::effective_price(0, 1), None);
+    assert_eq!(slippage::price_impact_bps(1.0, 1_000, 1_000), Some(0.0));
+    assert_eq!(
+        SwapPriceReport::with_spot_price(0.0, 1, 1),
+        Err(ProgramError::InvalidArgument)
+    );
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "client")]
+
+use raydium_contract_instructions::{
+    market_stats::OpenOrders,
+    stable_stats::{AmmInfo, OutPutData},
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+fn open_orders(native_coin_total: u64, native_pc_total: u64) -> OpenOrders {
+    OpenOrders {
+        account_flags: 0,
+        market: Pubkey::default(),
+        owner: Pubkey::default(),
+        native_coin_free: 0,
+        native_coin_total,
+        native_pc_free: 0,
+        native_pc_total,
+        free_slot_bits: 0,
+        is_bid_bits: 0,
+        orders: [0; 128],
+        client_order_ids: [0; 128],
+        referrer_rebates_accrued: 0,
+    }
+}
+
+fn amm(need_take_pnl_coin: u64, need_take_pnl_pc: u64) -> AmmInfo {
+    AmmInfo {
+        out_put: OutPutData {
+            need_take_pnl_coin,
+            need_take_pnl_pc,
+            ..OutPutData::default()
+        },
+        // a zeroed stable pool account, 1232 bytes long
+        ..AmmInfo::unpack(&[0; 1232]).unwrap()
+    }
+}
+
+#[test]
+fn effective_reserves() {
+    // the open orders funds always count, whatever the status
+    for status in 0..8 {
+        let amm = AmmInfo {
+            status,
+            ..amm(10, 20)
+        };
+        assert_eq!(
+            amm.effective_reserves(100_000, 200_000, &open_orders(1_000, 2_000)),
+            Ok((100_990, 201_980))
+        );
+    }
+
+    let amm = amm(10, 20);
+    assert_eq!(
+        amm.effective_reserves(0, 0, &open_orders(10, 20)),
+        Ok((0, 0))
+    );
+    assert_eq!(
+        amm.effective_reserves(5, 20, &open_orders(4, 0)),
+        Err(ProgramError::InvalidAccountData)
+    );
+    assert_eq!(
+        amm.effective_reserves(10, 19, &open_orders(0, 0)),
+        Err(ProgramError::InvalidAccountData)
+    );
+    assert_eq!(
+        amm.effective_reserves(0, u64::MAX, &open_orders(10, 1)),
+        Err(ProgramError::InvalidAccountData)
+    );
+}
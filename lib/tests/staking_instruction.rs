@@ -0,0 +1,134 @@
+mod common;
+
+use common::{amount_data, InstructionCodec};
+use raydium_contract_instructions::staking_instruction::{self, InitArgs, StakePoolInstruction};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar,
+};
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+/// Accounts of a 'DepositV2' or 'WithdrawV2' built from the keys 1 to 9.
+fn v2_accounts() -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(key(1), false),
+        AccountMeta::new_readonly(key(2), false),
+        AccountMeta::new(key(3), false),
+        AccountMeta::new_readonly(key(4), true),
+        AccountMeta::new(key(5), false),
+        AccountMeta::new(key(6), false),
+        AccountMeta::new(key(7), false),
+        AccountMeta::new(key(8), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(key(9), false),
+    ]
+}
+
+#[test]
+fn codec() {
+    let amount = 0x0102_0304_0506_0708;
+    let init = InitArgs {
+        nonce: 254,
+        reward_per_slot: 1_000,
+        ignore: u128::MAX - 1,
+    };
+    let init_data = [
+        &[0][..],
+        &254u64.to_le_bytes(),
+        &1_000u64.to_le_bytes(),
+        &(u128::MAX - 1).to_le_bytes(),
+    ]
+    .concat();
+
+    InstructionCodec {
+        serialize: StakePoolInstruction::serialize,
+        deserialize: StakePoolInstruction::deserialize,
+        instructions: vec![
+            (StakePoolInstruction::Initialize(init), init_data),
+            (
+                StakePoolInstruction::Deposit(amount),
+                amount_data(1, amount),
+            ),
+            (
+                StakePoolInstruction::Withdraw(amount),
+                amount_data(2, amount),
+            ),
+            (StakePoolInstruction::UpdatePool, vec![3]),
+            (StakePoolInstruction::EmergencyWithdraw, vec![7]),
+            (StakePoolInstruction::CreateAssociatedAccount, vec![9]),
+            (
+                StakePoolInstruction::DepositV2(amount),
+                amount_data(10, amount),
+            ),
+            (
+                StakePoolInstruction::WithdrawV2(amount),
+                amount_data(11, amount),
+            ),
+        ],
+        unknown_tags: vec![4, 5, 6, 8, 12, u8::MAX],
+        reserved: vec![
+            StakePoolInstruction::Reserved0,
+            StakePoolInstruction::Reserved1,
+            StakePoolInstruction::Reserved2,
+            StakePoolInstruction::Reserved3,
+        ],
+    }
+    .check();
+}
+
+#[test]
+fn v2_instructions() {
+    type Builder = fn(
+        &Pubkey,
+        &Pubkey,
+        &Pubkey,
+        &Pubkey,
+        &Pubkey,
+        &Pubkey,
+        &Pubkey,
+        &Pubkey,
+        &Pubkey,
+        &Pubkey,
+        Option<&Pubkey>,
+        u64,
+    ) -> Result<Instruction, ProgramError>;
+    let builders: [(Builder, u8); 2] = [
+        (staking_instruction::deposit_v2, 10),
+        (staking_instruction::withdraw_v2, 11),
+    ];
+    let program_id = staking_instruction::id();
+    for (build, tag) in builders {
+        let build = |staker_info_v1| {
+            build(
+                &program_id,
+                &key(1),
+                &key(2),
+                &key(3),
+                &key(4),
+                &key(5),
+                &key(6),
+                &key(7),
+                &key(8),
+                &key(9),
+                staker_info_v1,
+                1_000,
+            )
+            .unwrap()
+        };
+
+        let instruction = build(None);
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts, v2_accounts());
+        assert_eq!(instruction.data, amount_data(tag, 1_000));
+
+        // the v1 staker info to migrate goes last, writable and signing
+        let mut accounts = v2_accounts();
+        accounts.push(AccountMeta::new(key(10), true));
+        assert_eq!(build(Some(&key(10))).accounts, accounts);
+    }
+}
@@ -0,0 +1,162 @@
+#![cfg(feature = "client")]
+
+use raydium_contract_instructions::transfer_fee::{
+    self, SwapTransferFees, TransferFee, TransferFeeConfig, MINT_LEN,
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+const OLDER_FEE: TransferFee = TransferFee {
+    epoch: 0,
+    maximum_fee: u64::MAX,
+    transfer_fee_basis_points: 50,
+};
+const NEWER_FEE: TransferFee = TransferFee {
+    epoch: 600,
+    maximum_fee: 5_000,
+    transfer_fee_basis_points: 100,
+};
+
+fn push_fee(data: &mut Vec<u8>, fee: &TransferFee) {
+    data.extend_from_slice(&fee.epoch.to_le_bytes());
+    data.extend_from_slice(&fee.maximum_fee.to_le_bytes());
+    data.extend_from_slice(&fee.transfer_fee_basis_points.to_le_bytes());
+}
+
+/// Token-2022 mint with a metadata pointer then a transfer fee config extension.
+fn fee_mint() -> Vec<u8> {
+    let mut data = vec![0; 165];
+    data.push(1);
+    // metadata pointer
+    data.extend_from_slice(&18u16.to_le_bytes());
+    data.extend_from_slice(&64u16.to_le_bytes());
+    data.extend_from_slice(&[7; 64]);
+    // transfer fee config
+    data.extend_from_slice(&1u16.to_le_bytes());
+    data.extend_from_slice(&108u16.to_le_bytes());
+    data.extend_from_slice(&[3; 32]);
+    data.extend_from_slice(&[0; 32]);
+    data.extend_from_slice(&42u64.to_le_bytes());
+    push_fee(&mut data, &OLDER_FEE);
+    push_fee(&mut data, &NEWER_FEE);
+    data
+}
+
+#[test]
+fn unpack_mint() {
+    let config = TransferFeeConfig::unpack_from_mint(&fee_mint())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        config.transfer_fee_config_authority,
+        Some(Pubkey::new_from_array([3; 32]))
+    );
+    assert_eq!(config.withdraw_withheld_authority, None);
+    assert_eq!(config.withheld_amount, 42);
+    assert_eq!(config.older_transfer_fee, OLDER_FEE);
+    assert_eq!(config.newer_transfer_fee, NEWER_FEE);
+    assert_eq!(config.get_epoch_fee(599), &OLDER_FEE);
+    assert_eq!(config.get_epoch_fee(600), &NEWER_FEE);
+
+    // spl token mint and Token-2022 mint without extension
+    assert_eq!(
+        TransferFeeConfig::unpack_from_mint(&[0; MINT_LEN]),
+        Ok(None)
+    );
+    let mut data = vec![0; 165];
+    data.push(1);
+    assert_eq!(TransferFeeConfig::unpack_from_mint(&data), Ok(None));
+
+    // token account and truncated extension
+    let mut data = fee_mint();
+    data[165] = 2;
+    assert_eq!(
+        TransferFeeConfig::unpack_from_mint(&data),
+        Err(ProgramError::InvalidAccountData)
+    );
+    let data = fee_mint();
+    assert_eq!(
+        TransferFeeConfig::unpack_from_mint(&data[..data.len() - 1]),
+        Err(ProgramError::InvalidAccountData)
+    );
+
+    // fee rate above 100%, in the older then the newer fee
+    for offset in [18, 0] {
+        let mut data = fee_mint();
+        let bps = data.len() - offset - 2;
+        data[bps..bps + 2].copy_from_slice(&10_001u16.to_le_bytes());
+        assert_eq!(
+            TransferFeeConfig::unpack_from_mint(&data),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+}
+
+#[test]
+fn fee_amounts() {
+    assert_eq!(NEWER_FEE.calculate_fee(0), Some(0));
+    assert_eq!(NEWER_FEE.calculate_fee(100), Some(1));
+    assert_eq!(NEWER_FEE.calculate_fee(12_345), Some(124));
+    assert_eq!(NEWER_FEE.calculate_fee(1_000_000), Some(5_000));
+
+    assert_eq!(NEWER_FEE.calculate_pre_fee_amount(0), Some(0));
+    assert_eq!(NEWER_FEE.calculate_pre_fee_amount(99), Some(100));
+    assert_eq!(NEWER_FEE.calculate_pre_fee_amount(995_000), Some(1_000_000));
+    assert_eq!(NEWER_FEE.calculate_inverse_fee(995_000), Some(5_000));
+
+    let full = TransferFee {
+        transfer_fee_basis_points: 10_000,
+        ..NEWER_FEE
+    };
+    assert_eq!(full.calculate_pre_fee_amount(10), Some(5_010));
+
+    // a rate above 100% is rejected rather than underflowing
+    let invalid = TransferFee {
+        transfer_fee_basis_points: 10_001,
+        ..NEWER_FEE
+    };
+    assert_eq!(invalid.calculate_fee(1_000), None);
+    assert_eq!(invalid.calculate_pre_fee_amount(1_000), None);
+    let config = TransferFeeConfig {
+        newer_transfer_fee: TransferFee {
+            maximum_fee: u64::MAX,
+            ..invalid
+        },
+        ..TransferFeeConfig::default()
+    };
+    assert_eq!(
+        transfer_fee::net_amount(Some(&config), 700, 1_000),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(
+        transfer_fee::gross_amount(Some(&config), 700, 1_000),
+        Err(ProgramError::InvalidArgument)
+    );
+
+    for amount in [1, 99, 100, 101, 12_345, 499_999, 500_000, 10_000_000] {
+        let pre_fee_amount = NEWER_FEE.calculate_pre_fee_amount(amount).unwrap();
+        assert_eq!(
+            pre_fee_amount - NEWER_FEE.calculate_fee(pre_fee_amount).unwrap(),
+            amount
+        );
+    }
+}
+
+#[test]
+fn swap_legs() {
+    let fees = SwapTransferFees::from_mints(&fee_mint(), &[0; MINT_LEN], 700).unwrap();
+    assert_eq!(fees.input_net_amount(12_345).unwrap(), 12_221);
+    assert_eq!(fees.input_gross_amount(12_221).unwrap(), 12_345);
+    assert_eq!(fees.output_net_amount(12_345).unwrap(), 12_345);
+    assert_eq!(fees.output_gross_amount(12_345).unwrap(), 12_345);
+
+    // older fee before the newer epoch
+    let fees = SwapTransferFees { epoch: 10, ..fees };
+    assert_eq!(fees.input_net_amount(12_345).unwrap(), 12_283);
+
+    // output leg on a fee bearing mint
+    let fees = SwapTransferFees::from_mints(&[0; MINT_LEN], &fee_mint(), 700).unwrap();
+    let amount_out = fees.quote_base_in(1_000, |vault_amount_in| Ok(vault_amount_in * 2));
+    assert_eq!(amount_out, Ok(1_980));
+    let amount_in = fees.quote_base_out(1_980, |vault_amount_out| Ok(vault_amount_out / 2));
+    assert_eq!(amount_in, Ok(1_000));
+}
@@ -0,0 +1,116 @@
+use raydium_contract_instructions::{
+    amm_instruction::{self, AUTHORITY_AMM},
+    amm_stats::AmmInfo,
+    validation::{self, AccountMismatch},
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+fn amm_info() -> (AmmInfo, Pubkey) {
+    let program_id = amm_instruction::id();
+    let (authority, nonce) = Pubkey::find_program_address(&[AUTHORITY_AMM], &program_id);
+    let amm = AmmInfo {
+        nonce: nonce as u64,
+        coin_vault: key(1),
+        pc_vault: key(2),
+        lp_mint: key(3),
+        open_orders: key(4),
+        market: key(5),
+        market_program: key(6),
+        target_orders: key(7),
+        ..AmmInfo::default()
+    };
+    (amm, authority)
+}
+
+#[test]
+fn authority() {
+    let (amm, authority) = amm_info();
+    assert_eq!(
+        amm_instruction::get_amm_authority(&amm_instruction::id(), amm.nonce),
+        Ok(authority)
+    );
+}
+
+#[test]
+fn validate_accounts() {
+    let program_id = amm_instruction::id();
+    let (amm, authority) = amm_info();
+
+    let report = validation::validate_swap_accounts(
+        &program_id,
+        &amm,
+        &authority,
+        &key(4),
+        &key(1),
+        &key(2),
+        &key(6),
+        &key(5),
+    );
+    assert!(report.is_valid());
+    assert_eq!(report.to_string(), "all accounts match");
+    assert_eq!(report.into_result(), Ok(()));
+
+    // vaults swapped
+    let report = validation::validate_deposit_accounts(
+        &program_id,
+        &amm,
+        &authority,
+        &key(4),
+        &key(7),
+        &key(3),
+        &key(2),
+        &key(1),
+        &key(5),
+    );
+    assert_eq!(
+        report.mismatches,
+        vec![
+            AccountMismatch {
+                account: "amm_coin_vault",
+                expected: key(1),
+                actual: key(2),
+            },
+            AccountMismatch {
+                account: "amm_pc_vault",
+                expected: key(2),
+                actual: key(1),
+            },
+        ]
+    );
+    assert_eq!(
+        report.to_string(),
+        format!(
+            "amm_coin_vault: expected {}, got {}; amm_pc_vault: expected {}, got {}",
+            key(1),
+            key(2),
+            key(2),
+            key(1)
+        )
+    );
+    assert_eq!(report.into_result(), Err(ProgramError::InvalidArgument));
+
+    // authority of another program, wrong lp mint and market program
+    let report = validation::validate_withdraw_accounts(
+        &program_id,
+        &amm,
+        &key(9),
+        &key(4),
+        &key(7),
+        &key(8),
+        &key(1),
+        &key(2),
+        &key(9),
+        &key(5),
+    );
+    let accounts: Vec<_> = report
+        .mismatches
+        .iter()
+        .map(|mismatch| mismatch.account)
+        .collect();
+    assert_eq!(accounts, ["amm_authority", "amm_lp_mint", "market_program"]);
+    assert_eq!(report.mismatches[0].expected, authority);
+}
@@ -0,0 +1,214 @@
+#![cfg(feature = "client")]
+
+use raydium_contract_instructions::{
+    amm_instruction::{self, AUTHORITY_AMM},
+    amm_stats::AmmInfo,
+    verify::{AmmPoolAccounts, DistrustReason, RawAccount},
+};
+use solana_program::{program_option::COption, program_pack::Pack, pubkey::Pubkey};
+use spl_token::state::{Account, AccountState, Mint};
+
+const COIN_VAULT_OFFSET: usize = 336;
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+struct Fixture {
+    authority: Pubkey,
+    amm: Vec<u8>,
+    coin_vault: Vec<u8>,
+    pc_vault: Vec<u8>,
+    lp_mint: Vec<u8>,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        let (authority, nonce) =
+            Pubkey::find_program_address(&[AUTHORITY_AMM], &amm_instruction::id());
+        let mut amm = vec![0u8; AmmInfo::LEN];
+        // swap only status
+        amm[0..8].copy_from_slice(&6u64.to_le_bytes());
+        amm[8..16].copy_from_slice(&(nonce as u64).to_le_bytes());
+        // coin vault, pc vault, coin mint, pc mint, lp mint
+        for (i, n) in [1, 2, 11, 12, 3].into_iter().enumerate() {
+            let offset = COIN_VAULT_OFFSET + i * 32;
+            amm[offset..offset + 32].copy_from_slice(key(n).as_ref());
+        }
+        Self {
+            authority,
+            amm,
+            coin_vault: token_account(key(11), authority),
+            pc_vault: token_account(key(12), authority),
+            lp_mint: mint(COption::Some(authority)),
+        }
+    }
+
+    fn accounts(&self) -> AmmPoolAccounts<'_> {
+        let token = |n, data| RawAccount {
+            key: key(n),
+            owner: spl_token::id(),
+            data,
+        };
+        AmmPoolAccounts {
+            amm: RawAccount {
+                key: key(9),
+                owner: amm_instruction::id(),
+                data: &self.amm,
+            },
+            coin_vault: token(1, &self.coin_vault),
+            pc_vault: token(2, &self.pc_vault),
+            lp_mint: token(3, &self.lp_mint),
+        }
+    }
+}
+
+fn token_account(mint: Pubkey, owner: Pubkey) -> Vec<u8> {
+    let mut data = vec![0u8; Account::LEN];
+    Account {
+        mint,
+        owner,
+        amount: 1_000_000,
+        state: AccountState::Initialized,
+        ..Account::default()
+    }
+    .pack_into_slice(&mut data);
+    data
+}
+
+fn mint(mint_authority: COption<Pubkey>) -> Vec<u8> {
+    let mut data = vec![0u8; Mint::LEN];
+    Mint {
+        mint_authority,
+        supply: 1_000_000,
+        decimals: 6,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    data
+}
+
+#[test]
+fn trusted_pool() {
+    let fixture = Fixture::new();
+    let verdict = fixture.accounts().verify();
+    assert!(verdict.is_trusted(), "{}", verdict);
+    assert_eq!(verdict.to_string(), "trusted");
+}
+
+#[test]
+fn spoofed_amm() {
+    let fixture = Fixture::new();
+
+    // copy of the amm owned by another program
+    let mut accounts = fixture.accounts();
+    accounts.amm.owner = key(7);
+    let verdict = accounts.verify();
+    assert_eq!(
+        verdict.reasons,
+        vec![DistrustReason::ProgramOwner {
+            account: "amm",
+            expected: amm_instruction::id(),
+            actual: key(7),
+        }]
+    );
+    assert_eq!(
+        verdict.to_string(),
+        format!(
+            "untrusted: amm is owned by {}, expected {}",
+            key(7),
+            amm_instruction::id()
+        )
+    );
+
+    let mut accounts = fixture.accounts();
+    accounts.amm.data = &fixture.amm[..AmmInfo::LEN - 8];
+    assert_eq!(
+        accounts.verify().reasons,
+        vec![DistrustReason::DataSize {
+            account: "amm",
+            expected: AmmInfo::LEN,
+            actual: AmmInfo::LEN - 8,
+        }]
+    );
+
+    let mut amm = fixture.amm.clone();
+    amm[0..8].copy_from_slice(&0u64.to_le_bytes());
+    let mut accounts = fixture.accounts();
+    accounts.amm.data = &amm;
+    assert_eq!(
+        accounts.verify().reasons,
+        vec![DistrustReason::AmmStatus(0)]
+    );
+}
+
+#[test]
+fn spoofed_vaults_and_lp_mint() {
+    let fixture = Fixture::new();
+
+    // vault of the right mint held by an attacker, lp mint it controls
+    let coin_vault = token_account(key(11), key(8));
+    let lp_mint = mint(COption::Some(key(8)));
+    let mut accounts = fixture.accounts();
+    accounts.coin_vault.data = &coin_vault;
+    accounts.lp_mint.data = &lp_mint;
+    assert_eq!(
+        accounts.verify().reasons,
+        vec![
+            DistrustReason::VaultOwner {
+                account: "coin_vault",
+                expected: fixture.authority,
+                actual: key(8),
+            },
+            DistrustReason::LpMintAuthority {
+                expected: fixture.authority,
+                actual: Some(key(8)),
+            },
+        ]
+    );
+
+    // vaults swapped, and a pc vault not owned by the token program
+    let mut accounts = fixture.accounts();
+    accounts.coin_vault = fixture.accounts().pc_vault;
+    accounts.pc_vault = fixture.accounts().coin_vault;
+    accounts.pc_vault.owner = key(7);
+    let verdict = accounts.verify();
+    assert_eq!(
+        verdict.reasons,
+        vec![
+            DistrustReason::Key {
+                account: "coin_vault",
+                expected: key(1),
+                actual: key(2),
+            },
+            DistrustReason::VaultMint {
+                account: "coin_vault",
+                expected: key(11),
+                actual: key(12),
+            },
+            DistrustReason::Key {
+                account: "pc_vault",
+                expected: key(2),
+                actual: key(1),
+            },
+            DistrustReason::ProgramOwner {
+                account: "pc_vault",
+                expected: spl_token::id(),
+                actual: key(7),
+            },
+        ]
+    );
+    assert!(!verdict.is_trusted());
+
+    let lp_mint = mint(COption::None);
+    let mut accounts = fixture.accounts();
+    accounts.lp_mint.data = &lp_mint;
+    assert_eq!(
+        accounts.verify().reasons,
+        vec![DistrustReason::LpMintAuthority {
+            expected: fixture.authority,
+            actual: None,
+        }]
+    );
+}
@@ -127,13 +127,12 @@ pub fn deposit<'a, 'b, 'c, 'info>(
 /// See [raydium_contract_instructions::stable_instruction::WithdrawInstruction].
 ///
 /// * `amount` - Pool token amount to transfer. token_a and token_b amount are set by
-///             the current exchange rate and size of the pool.
-
+///   the current exchange rate and size of the pool.
 pub fn withdraw<'a, 'b, 'c, 'info>(
     ctx: CpiContext<'a, 'b, 'c, 'info, Withdraw<'info>>,
     amount: u64,
 ) -> Result<()> {
-    let referrer_pc_account = ctx.remaining_accounts.get(0);
+    let referrer_pc_account = ctx.remaining_accounts.first();
     let serum_event_q = ctx.remaining_accounts.get(1);
     let serum_bids = ctx.remaining_accounts.get(2);
     let serum_asks = ctx.remaining_accounts.get(3);
@@ -58,6 +58,35 @@ pub struct Deposit<'info> {
     pub spl_token_program: Program<'info, Token>,
 }
 
+/// Accounts for an `DepositV2` instruction.
+#[derive(Accounts, Clone)]
+pub struct DepositV2<'info> {
+    /// CHECK: Safe.
+    #[account(mut)]
+    pub stake_pool: UncheckedAccount<'info>,
+    /// CHECK: Safe.
+    pub pool_authority: UncheckedAccount<'info>,
+    /// CHECK: Safe.
+    #[account(mut)]
+    pub staker_info: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub staker_owner: Signer<'info>,
+    /// CHECK: Safe.
+    #[account(mut)]
+    pub src_lp_token: UncheckedAccount<'info>,
+    /// CHECK: Safe.
+    #[account(mut)]
+    pub vault_lp_token: UncheckedAccount<'info>,
+    /// CHECK: Safe.
+    #[account(mut)]
+    pub dest_reward_token: UncheckedAccount<'info>,
+    /// CHECK: Safe.
+    #[account(mut)]
+    pub vault_reward_token: UncheckedAccount<'info>,
+    pub clock: Sysvar<'info, Clock>,
+    pub spl_token_program: Program<'info, Token>,
+}
+
 /// Accounts for an `Withdraw` instruction.
 #[derive(Accounts, Clone)]
 pub struct Withdraw<'info> {
@@ -87,6 +116,35 @@ pub struct Withdraw<'info> {
     pub spl_token_program: Program<'info, Token>,
 }
 
+/// Accounts for an `WithdrawV2` instruction.
+#[derive(Accounts, Clone)]
+pub struct WithdrawV2<'info> {
+    /// CHECK: Safe.
+    #[account(mut)]
+    pub stake_pool: UncheckedAccount<'info>,
+    /// CHECK: Safe.
+    pub pool_authority: UncheckedAccount<'info>,
+    /// CHECK: Safe.
+    #[account(mut)]
+    pub staker_info: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub staker_owner: Signer<'info>,
+    /// CHECK: Safe.
+    #[account(mut)]
+    pub dest_lp_token: UncheckedAccount<'info>,
+    /// CHECK: Safe.
+    #[account(mut)]
+    pub vault_lp_token: UncheckedAccount<'info>,
+    /// CHECK: Safe.
+    #[account(mut)]
+    pub dest_reward_token: UncheckedAccount<'info>,
+    /// CHECK: Safe.
+    #[account(mut)]
+    pub vault_reward_token: UncheckedAccount<'info>,
+    pub clock: Sysvar<'info, Clock>,
+    pub spl_token_program: Program<'info, Token>,
+}
+
 /// Accounts for an `EmergencyWithdraw` instruction.
 #[derive(Accounts, Clone)]
 pub struct EmergencyWithdraw<'info> {
@@ -64,6 +64,39 @@ pub fn deposit<'a, 'b, 'c, 'info>(
     Ok(())
 }
 
+/// Creates and invokes a [raydium_contract_instructions::staking_instruction::deposit_v2] instruction.
+///
+/// # Arguments
+///
+/// * `amount` - The amount to deposit.
+pub fn deposit_v2<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, DepositV2<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let staker_info_v1 = ctx.remaining_accounts.first();
+    let ix = staking_instruction::deposit_v2(
+        ctx.program.key,
+        ctx.accounts.stake_pool.key,
+        ctx.accounts.pool_authority.key,
+        ctx.accounts.staker_info.key,
+        ctx.accounts.staker_owner.key,
+        ctx.accounts.src_lp_token.key,
+        ctx.accounts.vault_lp_token.key,
+        ctx.accounts.dest_reward_token.key,
+        ctx.accounts.vault_reward_token.key,
+        ctx.accounts.spl_token_program.key,
+        staker_info_v1.map(|info| info.key),
+        amount,
+    )?;
+
+    solana_program::program::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+    )?;
+    Ok(())
+}
+
 /// Creates and invokes a [raydium_contract_instructions::staking_instruction::withdraw] instruction.
 ///
 /// # Arguments
@@ -95,6 +128,39 @@ pub fn withdraw<'a, 'b, 'c, 'info>(
     Ok(())
 }
 
+/// Creates and invokes a [raydium_contract_instructions::staking_instruction::withdraw_v2] instruction.
+///
+/// # Arguments
+///
+/// * `amount` - The amount to withdraw.
+pub fn withdraw_v2<'a, 'b, 'c, 'info>(
+    ctx: CpiContext<'a, 'b, 'c, 'info, WithdrawV2<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let staker_info_v1 = ctx.remaining_accounts.first();
+    let ix = staking_instruction::withdraw_v2(
+        ctx.program.key,
+        ctx.accounts.stake_pool.key,
+        ctx.accounts.pool_authority.key,
+        ctx.accounts.staker_info.key,
+        ctx.accounts.staker_owner.key,
+        ctx.accounts.dest_lp_token.key,
+        ctx.accounts.vault_lp_token.key,
+        ctx.accounts.dest_reward_token.key,
+        ctx.accounts.vault_reward_token.key,
+        ctx.accounts.spl_token_program.key,
+        staker_info_v1.map(|info| info.key),
+        amount,
+    )?;
+
+    solana_program::program::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+    )?;
+    Ok(())
+}
+
 /// Creates and invokes a [raydium_contract_instructions::staking_instruction::emergency_withdraw] instruction.
 ///
 /// # Arguments